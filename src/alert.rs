@@ -0,0 +1,468 @@
+use crate::app::{App, ToastLevel};
+use crate::config::name_of;
+use crate::ui::format_bytes;
+use serde::{Deserialize, Deserializer, Serialize};
+use std::process::{Command, Stdio};
+
+/// Entries kept in the alert log
+const LOG_SIZE: usize = 100;
+
+/// Value an alert rule watches
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Metric {
+    /// Overall CPU usage percentage
+    Cpu,
+    /// Memory usage percentage
+    Memory,
+    /// Swap usage percentage
+    Swap,
+    /// Utilization percentage of the busiest GPU, or of `gpu`
+    GpuUsage,
+    /// Temperature in °C of the hottest GPU, or of `gpu`
+    GpuTemperature,
+    /// Reading of the sensor labelled `sensor` (°C, RPM or V)
+    Sensor,
+    /// CPU usage percentage of the busiest process named `process`
+    ProcessCpu,
+    /// Resident memory in bytes of the largest process named `process`
+    ProcessMemory,
+}
+
+impl Metric {
+    fn is_process(self) -> bool {
+        matches!(self, Metric::ProcessCpu | Metric::ProcessMemory)
+    }
+
+    fn is_gpu(self) -> bool {
+        matches!(self, Metric::GpuUsage | Metric::GpuTemperature)
+    }
+
+    /// Format a value in the metric's unit
+    fn format(self, value: f64) -> String {
+        match self {
+            Metric::ProcessMemory => format_bytes(value as u64),
+            Metric::GpuTemperature => format!("{:.0}°C", value),
+            Metric::Sensor => format!("{:.1}", value),
+            _ => format!("{:.1}%", value),
+        }
+    }
+}
+
+/// A condition that raises an alert once it has held for `for_secs`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AlertRule {
+    /// Shown in toasts and the log; defaults to the condition itself
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    pub metric: Metric,
+    /// Process name, for process metrics
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub process: Option<String>,
+    /// GPU index, for GPU metrics; all GPUs when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gpu: Option<u32>,
+    /// Sensor label, for the sensor metric
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sensor: Option<String>,
+    /// Fire when the value goes above this; sizes like "2GB" are allowed
+    #[serde(default, deserialize_with = "threshold", skip_serializing_if = "Option::is_none")]
+    pub above: Option<f64>,
+    /// Fire when the value goes below this
+    #[serde(default, deserialize_with = "threshold", skip_serializing_if = "Option::is_none")]
+    pub below: Option<f64>,
+    /// How long the condition must hold before firing
+    #[serde(default)]
+    pub for_secs: u64,
+    /// Toast level when firing
+    #[serde(default = "default_level")]
+    pub level: ToastLevel,
+    /// Shell command run when the alert fires
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_fire: Option<String>,
+    /// Shell command run when the alert clears
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_clear: Option<String>,
+}
+
+fn default_level() -> ToastLevel {
+    ToastLevel::Warning
+}
+
+impl AlertRule {
+    /// Name, or a description such as "process_memory(postgres) > 2.0 GB"
+    pub fn label(&self) -> String {
+        if let Some(ref name) = self.name {
+            return name.clone();
+        }
+
+        let subject = match (&self.process, &self.sensor, self.gpu) {
+            (Some(process), _, _) if self.metric.is_process() => format!("({})", process),
+            (_, Some(sensor), _) if self.metric == Metric::Sensor => format!("({})", sensor),
+            (_, _, Some(gpu)) if self.metric.is_gpu() => format!("({})", gpu),
+            _ => String::new(),
+        };
+        format!("{}{} {}", name_of(&self.metric), subject, self.condition())
+    }
+
+    /// The comparison, e.g. "> 90.0%"
+    fn condition(&self) -> String {
+        match (self.above, self.below) {
+            (Some(above), _) => format!("> {}", self.metric.format(above)),
+            (_, Some(below)) => format!("< {}", self.metric.format(below)),
+            _ => String::new(),
+        }
+    }
+
+    fn breached(&self, value: f64) -> bool {
+        self.above.is_some_and(|above| value > above) || self.below.is_some_and(|below| value < below)
+    }
+
+    /// Check the fields that depend on each other
+    pub fn validate(&self) -> Result<(), String> {
+        if self.above.is_some() == self.below.is_some() {
+            return Err("needs exactly one of `above` or `below`".to_string());
+        }
+        if self.metric.is_process() && self.process.is_none() {
+            return Err(format!("metric \"{}\" needs a `process` name", name_of(&self.metric)));
+        }
+        if self.metric == Metric::Sensor && self.sensor.is_none() {
+            return Err("metric \"sensor\" needs a `sensor` label".to_string());
+        }
+        Ok(())
+    }
+
+    /// Current value from the app's history buffers and snapshot, if any
+    fn value(&self, app: &App) -> Option<f64> {
+        let snapshot = &app.snapshot;
+        let max = |values: &mut dyn Iterator<Item = f64>| values.reduce(f64::max);
+
+        match self.metric {
+            Metric::Cpu => app.overall_cpu_history.last().map(|&v| v as f64),
+            Metric::Memory => app.memory_history.last().map(|&v| v as f64),
+            Metric::Swap => app.swap_history.last().map(|&v| v as f64),
+            Metric::GpuUsage | Metric::GpuTemperature => max(&mut snapshot
+                .gpus
+                .iter()
+                .zip(&app.gpu_history)
+                .filter(|(gpu, _)| self.gpu.is_none() || self.gpu == Some(gpu.index))
+                .filter_map(|(_, history)| match self.metric {
+                    Metric::GpuUsage => history.usage.last(),
+                    _ => history.temperature.last(),
+                })
+                .map(|&v| v as f64)),
+            Metric::Sensor => max(&mut snapshot
+                .sensors
+                .iter()
+                .filter(|sensor| Some(&sensor.label) == self.sensor.as_ref())
+                .filter_map(|sensor| app.sensor_history.get(&sensor.key())?.last())
+                .map(|&v| v as f64)),
+            Metric::ProcessCpu | Metric::ProcessMemory => max(&mut snapshot
+                .processes
+                .iter()
+                .filter(|process| Some(&process.name) == self.process.as_ref())
+                .map(|process| match self.metric {
+                    Metric::ProcessCpu => process.cpu_usage as f64,
+                    _ => process.memory as f64,
+                })),
+        }
+    }
+}
+
+/// Accept plain numbers, or sizes with a binary suffix ("512M", "2GB")
+fn threshold<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<f64>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Raw {
+        Number(f64),
+        Text(String),
+    }
+
+    let text = match Raw::deserialize(deserializer)? {
+        Raw::Number(value) => return Ok(Some(value)),
+        Raw::Text(text) => text,
+    };
+
+    let upper = text.trim().to_uppercase();
+    let digits = upper.trim_end_matches('B');
+    let (number, scale) = match digits.chars().last() {
+        Some('K') => (&digits[..digits.len() - 1], 1u64 << 10),
+        Some('M') => (&digits[..digits.len() - 1], 1 << 20),
+        Some('G') => (&digits[..digits.len() - 1], 1 << 30),
+        Some('T') => (&digits[..digits.len() - 1], 1 << 40),
+        _ => (digits, 1),
+    };
+    number
+        .trim()
+        .parse::<f64>()
+        .map(|value| Some(value * scale as f64))
+        .map_err(|_| serde::de::Error::custom(format!("invalid threshold \"{}\"", text)))
+}
+
+/// An alert firing or clearing
+#[derive(Debug, Clone)]
+pub struct AlertEvent {
+    /// Snapshot time, in milliseconds since the Unix epoch
+    pub timestamp: u64,
+    pub rule: String,
+    pub fired: bool,
+    /// Value with its unit, e.g. "93.2%"
+    pub value: String,
+    pub level: ToastLevel,
+}
+
+/// Where a rule stands between ticks
+#[derive(Debug, Clone, Default)]
+struct RuleState {
+    /// When the condition started holding
+    since: Option<u64>,
+    firing: bool,
+}
+
+/// Evaluates alert rules against each snapshot and keeps a log
+#[derive(Debug, Default)]
+pub struct Alerts {
+    rules: Vec<AlertRule>,
+    states: Vec<RuleState>,
+    /// Newest last
+    pub log: Vec<AlertEvent>,
+}
+
+impl Alerts {
+    pub fn new(rules: Vec<AlertRule>) -> Self {
+        let states = vec![RuleState::default(); rules.len()];
+        Self {
+            rules,
+            states,
+            log: Vec::new(),
+        }
+    }
+
+    /// Forget rule states and log entries from `timestamp` on, before
+    /// evaluating a replay again from there
+    pub fn rewind(&mut self, timestamp: u64) {
+        self.states.iter_mut().for_each(|state| *state = RuleState::default());
+        self.log.retain(|event| event.timestamp < timestamp);
+    }
+
+    /// Labels of the rules currently firing
+    pub fn firing(&self) -> Vec<String> {
+        self.rules
+            .iter()
+            .zip(&self.states)
+            .filter(|(_, state)| state.firing)
+            .map(|(rule, _)| rule.label())
+            .collect()
+    }
+
+    /// Check every rule against the app's latest data and return the alerts
+    /// that fired or cleared. Durations use snapshot time, so replays fire
+    /// the same alerts as the live session did.
+    pub fn evaluate(&mut self, app: &App, run_commands: bool) -> Vec<AlertEvent> {
+        let now = app.snapshot.timestamp;
+        let mut events = Vec::new();
+
+        for (rule, state) in self.rules.iter().zip(self.states.iter_mut()) {
+            let value = rule.value(app);
+
+            match value.filter(|&v| rule.breached(v)) {
+                Some(value) => {
+                    let since = *state.since.get_or_insert(now);
+                    if !state.firing && now.saturating_sub(since) >= rule.for_secs * 1000 {
+                        state.firing = true;
+                        events.push(rule_event(rule, now, true, value));
+                        if run_commands {
+                            run_command(rule, events.last().unwrap());
+                        }
+                    }
+                }
+                None => {
+                    state.since = None;
+                    if state.firing {
+                        state.firing = false;
+                        events.push(rule_event(rule, now, false, value.unwrap_or_default()));
+                        if run_commands {
+                            run_command(rule, events.last().unwrap());
+                        }
+                    }
+                }
+            }
+        }
+
+        self.log.extend(events.iter().cloned());
+        if self.log.len() > LOG_SIZE {
+            self.log.drain(..self.log.len() - LOG_SIZE);
+        }
+        events
+    }
+}
+
+fn rule_event(rule: &AlertRule, timestamp: u64, fired: bool, value: f64) -> AlertEvent {
+    AlertEvent {
+        timestamp,
+        rule: rule.label(),
+        fired,
+        value: rule.metric.format(value),
+        level: rule.level,
+    }
+}
+
+/// Run the rule's fire or clear command in the background
+fn run_command(rule: &AlertRule, event: &AlertEvent) {
+    let command = if event.fired { &rule.on_fire } else { &rule.on_clear };
+    let Some(command) = command else {
+        return;
+    };
+
+    let mut shell = shell_command(command);
+    shell
+        .env("RTUI_ALERT_NAME", &event.rule)
+        .env("RTUI_ALERT_STATE", if event.fired { "fired" } else { "cleared" })
+        .env("RTUI_ALERT_VALUE", &event.value)
+        .env("RTUI_ALERT_CONDITION", rule.condition())
+        // Output would draw over the UI
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null());
+
+    // Reap the child off the UI thread so it never turns into a zombie
+    if let Ok(mut child) = shell.spawn() {
+        std::thread::spawn(move || child.wait());
+    }
+}
+
+#[cfg(target_family = "unix")]
+fn shell_command(command: &str) -> Command {
+    let mut shell = Command::new("sh");
+    shell.arg("-c").arg(command);
+    shell
+}
+
+#[cfg(not(target_family = "unix"))]
+fn shell_command(command: &str) -> Command {
+    let mut shell = Command::new("cmd");
+    shell.arg("/C").arg(command);
+    shell
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use crate::snapshot::Snapshot;
+    use std::sync::Arc;
+
+    fn rule(toml: &str) -> AlertRule {
+        toml::from_str(toml).unwrap()
+    }
+
+    /// Feed one snapshot at `secs` with the given CPU usage and evaluate
+    fn evaluate_at(alerts: &mut Alerts, app: &mut App, secs: u64, cpu: f32) -> Vec<(u64, bool)> {
+        app.update(Arc::new(Snapshot {
+            timestamp: secs * 1000,
+            global_cpu_usage: cpu,
+            ..Default::default()
+        }));
+        alerts
+            .evaluate(app, false)
+            .iter()
+            .map(|event| (event.timestamp / 1000, event.fired))
+            .collect()
+    }
+
+    #[test]
+    fn parses_size_thresholds() {
+        let above = |text: &str| rule(&format!("metric = \"cpu\"\nabove = {}", text)).above;
+
+        assert_eq!(above("90"), Some(90.0));
+        assert_eq!(above("12.5"), Some(12.5));
+        assert_eq!(above("\"2GB\""), Some(2.0 * (1u64 << 30) as f64));
+        assert_eq!(above("\"512M\""), Some(512.0 * (1u64 << 20) as f64));
+        assert_eq!(above("\"1.5 kb\""), Some(1536.0));
+        assert_eq!(above("\"1T\""), Some((1u64 << 40) as f64));
+        assert_eq!(above("\"100\""), Some(100.0));
+
+        let err = toml::from_str::<AlertRule>("metric = \"cpu\"\nabove = \"lots\"").unwrap_err();
+        assert!(err.to_string().contains("invalid threshold \"lots\""));
+    }
+
+    #[test]
+    fn validates_rules() {
+        assert!(rule("metric = \"cpu\"\nabove = 90").validate().is_ok());
+        assert!(rule("metric = \"process_memory\"\nprocess = \"postgres\"\nabove = \"2GB\"")
+            .validate()
+            .is_ok());
+
+        let err = |toml: &str| rule(toml).validate().unwrap_err();
+        assert!(err("metric = \"cpu\"").contains("exactly one"));
+        assert!(err("metric = \"cpu\"\nabove = 90\nbelow = 10").contains("exactly one"));
+        assert!(err("metric = \"process_cpu\"\nabove = 90").contains("`process`"));
+        assert!(err("metric = \"sensor\"\nabove = 90").contains("`sensor`"));
+    }
+
+    #[test]
+    fn labels_describe_the_condition() {
+        assert_eq!(rule("metric = \"cpu\"\nabove = 90").label(), "cpu > 90.0%");
+        assert_eq!(
+            rule("metric = \"process_memory\"\nprocess = \"postgres\"\nabove = \"2GB\"").label(),
+            "process_memory(postgres) > 2.0 GB"
+        );
+        assert_eq!(rule("name = \"Hot\"\nmetric = \"cpu\"\nabove = 90").label(), "Hot");
+    }
+
+    #[test]
+    fn fires_once_the_condition_has_held_for_for_secs() {
+        let mut app = App::new(Arc::new(Snapshot::default()), Config::default(), None);
+        let mut alerts = Alerts::new(vec![rule("metric = \"cpu\"\nabove = 50\nfor_secs = 3")]);
+
+        assert!(evaluate_at(&mut alerts, &mut app, 100, 90.0).is_empty());
+        assert!(evaluate_at(&mut alerts, &mut app, 102, 90.0).is_empty());
+        assert_eq!(evaluate_at(&mut alerts, &mut app, 103, 90.0), [(103, true)]);
+        // Stays quiet while it keeps firing
+        assert!(evaluate_at(&mut alerts, &mut app, 104, 95.0).is_empty());
+        assert_eq!(alerts.firing(), ["cpu > 50.0%"]);
+
+        assert_eq!(evaluate_at(&mut alerts, &mut app, 105, 10.0), [(105, false)]);
+        assert!(alerts.firing().is_empty());
+        assert_eq!(alerts.log.len(), 2);
+    }
+
+    #[test]
+    fn a_dip_restarts_the_for_secs_timer() {
+        let mut app = App::new(Arc::new(Snapshot::default()), Config::default(), None);
+        let mut alerts = Alerts::new(vec![rule("metric = \"cpu\"\nabove = 50\nfor_secs = 3")]);
+
+        evaluate_at(&mut alerts, &mut app, 100, 90.0);
+        evaluate_at(&mut alerts, &mut app, 102, 90.0);
+        // Dropping below for one tick doesn't clear anything, but resets the wait
+        assert!(evaluate_at(&mut alerts, &mut app, 103, 10.0).is_empty());
+        assert!(evaluate_at(&mut alerts, &mut app, 104, 90.0).is_empty());
+        assert!(evaluate_at(&mut alerts, &mut app, 106, 90.0).is_empty());
+        assert_eq!(evaluate_at(&mut alerts, &mut app, 107, 90.0), [(107, true)]);
+    }
+
+    #[test]
+    fn below_rules_fire_immediately_without_for_secs() {
+        let mut app = App::new(Arc::new(Snapshot::default()), Config::default(), None);
+        let mut alerts = Alerts::new(vec![rule("metric = \"cpu\"\nbelow = 5")]);
+
+        assert_eq!(evaluate_at(&mut alerts, &mut app, 100, 1.0), [(100, true)]);
+        assert_eq!(evaluate_at(&mut alerts, &mut app, 101, 50.0), [(101, false)]);
+    }
+
+    #[test]
+    fn rewind_forgets_state_and_later_log_entries() {
+        let mut app = App::new(Arc::new(Snapshot::default()), Config::default(), None);
+        let mut alerts = Alerts::new(vec![rule("metric = \"cpu\"\nabove = 50")]);
+
+        evaluate_at(&mut alerts, &mut app, 100, 90.0);
+        evaluate_at(&mut alerts, &mut app, 110, 10.0);
+        evaluate_at(&mut alerts, &mut app, 120, 90.0);
+        alerts.rewind(110 * 1000);
+
+        let log: Vec<u64> = alerts.log.iter().map(|event| event.timestamp / 1000).collect();
+        assert_eq!(log, [100]);
+        assert!(alerts.firing().is_empty());
+    }
+}
@@ -1,16 +1,52 @@
-use std::time::{Duration, Instant};
-use sysinfo::{System, Process, Pid};
+use crate::alert::Alerts;
+use crate::column::Column;
+use crate::config::Config;
+use crate::export;
+use crate::keymap::Keymap;
+use crate::priority::{self, IoClass, IoPriority, IO_LEVEL_MAX, NICE_RANGE};
+use crate::process_tree::{self, ProcessRow};
+use crate::procfs;
+use crate::replay::{Recorder, Replay};
+use crate::snapshot::{ProcessInfo, Snapshot};
+use crate::theme::Theme;
+use serde::{Deserialize, Serialize};
+use std::{
+    cell::Cell,
+    collections::{HashMap, HashSet},
+    path::PathBuf,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use sysinfo::{Pid, Process, ProcessRefreshKind, ProcessesToUpdate, Signal, System};
 
-#[derive(Debug, Clone, Copy, PartialEq)]
-pub enum SortOrder {
-    Cpu,
+/// Recorded time skipped by one seek key press at 1x speed
+const REPLAY_SEEK_MS: i64 = 10_000;
+
+/// Panels shown in the grid below the CPU overview
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Panel {
+    Gpu,
     Memory,
-    Pid,
-    Runtime,
+    Network,
+    Disks,
+    Sensors,
+    Alerts,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq)]
-#[allow(dead_code)]
+impl Panel {
+    /// Panels shown when the config doesn't list any
+    pub const DEFAULT: [Panel; 5] = [
+        Panel::Gpu,
+        Panel::Memory,
+        Panel::Network,
+        Panel::Disks,
+        Panel::Sensors,
+    ];
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum ToastLevel {
     Info,
     Success,
@@ -27,38 +63,153 @@ pub struct Toast {
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum ProcessAction {
+    /// Ask the process to exit with SIGTERM (a hard kill on Windows)
     Kill,
     Suspend,
     Resume,
+    /// Send a signal picked from the signal menu
+    Signal(Signal),
+    /// Set the nice value
+    Renice(i32),
+    /// Set the I/O scheduling class and level
+    SetIoPriority(IoPriority),
+}
+
+/// Which priority the priority input overlay edits
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PriorityField {
+    Nice,
+    Io,
+}
+
+/// State of the priority input overlay
+#[derive(Debug, Clone)]
+pub struct PriorityInput {
+    pub field: PriorityField,
+    /// Typed nice value, or I/O level
+    pub text: String,
+    /// I/O scheduling class, when editing the I/O priority
+    pub class: IoClass,
+}
+
+impl ProcessAction {
+    /// What was done, for result messages such as "Suspended 3 processes"
+    pub fn past_tense(self) -> String {
+        match self {
+            ProcessAction::Kill if cfg!(target_family = "unix") => "Sent SIGTERM to".to_string(),
+            ProcessAction::Kill => "Killed".to_string(),
+            ProcessAction::Suspend => "Suspended".to_string(),
+            ProcessAction::Resume => "Resumed".to_string(),
+            ProcessAction::Signal(signal) => format!("Sent {} to", signal_name(signal)),
+            ProcessAction::Renice(nice) => format!("Set nice {} on", nice),
+            ProcessAction::SetIoPriority(io) => format!("Set I/O priority {} on", io),
+        }
+    }
+}
+
+/// Signals offered by the signal menu: signal, name and what it does
+pub const SIGNALS: [(Signal, &str, &str); 11] = [
+    (Signal::Term, "SIGTERM", "Ask the process to exit cleanly"),
+    (Signal::Interrupt, "SIGINT", "Interrupt, like Ctrl+C"),
+    (Signal::Hangup, "SIGHUP", "Hang up; many daemons reload their config"),
+    (Signal::Quit, "SIGQUIT", "Quit and dump core"),
+    (Signal::User1, "SIGUSR1", "User-defined signal 1"),
+    (Signal::User2, "SIGUSR2", "User-defined signal 2"),
+    (Signal::Alarm, "SIGALRM", "Timer expired"),
+    (Signal::Winch, "SIGWINCH", "Terminal window resized"),
+    (Signal::Stop, "SIGSTOP", "Suspend; cannot be caught"),
+    (Signal::Continue, "SIGCONT", "Resume a suspended process"),
+    (Signal::Kill, "SIGKILL", "Kill at once, without cleanup"),
+];
+
+/// Conventional name of a signal, such as "SIGTERM"
+pub fn signal_name(signal: Signal) -> String {
+    SIGNALS
+        .iter()
+        .find(|(s, _, _)| *s == signal)
+        .map(|(_, name, _)| name.to_string())
+        .unwrap_or_else(|| format!("SIG{}", signal).to_uppercase())
+}
+
+/// Rolling throughput history for one network interface (bytes per second)
+#[derive(Debug, Clone, Default)]
+pub struct NetworkHistory {
+    pub rx: Vec<f64>,
+    pub tx: Vec<f64>,
+}
+
+/// Rolling history for one GPU (percentages, °C and bytes per second).
+///
+/// Readings only NVML provides stay empty for other GPUs.
+#[derive(Debug, Clone, Default)]
+pub struct GpuHistory {
+    pub usage: Vec<f32>,
+    pub memory: Vec<f32>,
+    pub temperature: Vec<f32>,
+    /// Power draw as a percentage of the limit
+    pub power: Vec<f32>,
+    pub encoder: Vec<f32>,
+    pub decoder: Vec<f32>,
+    pub pcie_rx: Vec<f64>,
+    pub pcie_tx: Vec<f64>,
+}
+
+/// Rolling I/O history for one block device (bytes per second)
+#[derive(Debug, Clone, Default)]
+pub struct DiskHistory {
+    pub read: Vec<f64>,
+    pub write: Vec<f64>,
+}
+
+/// Rolling resource history for one process
+#[derive(Debug, Clone, Default)]
+pub struct ProcessHistory {
+    /// Start time of the process, so a reused PID starts a fresh history
+    pub start_time: u64,
+    /// CPU usage percentage
+    pub cpu: Vec<f32>,
+    /// Resident memory in bytes
+    pub memory: Vec<u64>,
+    /// Disk read rate in bytes per second
+    pub disk_read: Vec<f64>,
+    /// Disk write rate in bytes per second
+    pub disk_write: Vec<f64>,
 }
 
 #[derive(Debug, Clone)]
 pub struct ActionConfirmation {
     pub action: ProcessAction,
-    pub pid: Pid,
-    pub process_name: String,
+    /// Processes the action applies to, as (PID, name): the selected or
+    /// marked processes first, then any descendants
+    pub targets: Vec<(Pid, String)>,
+    /// Whether the targets come from the marked set instead of the selection
+    pub marked: bool,
+    /// How many of the targets are descendants added by a tree action
+    pub descendants: usize,
 }
 
-#[cfg(feature = "gpu-nvidia")]
-use nvml_wrapper::Nvml;
-
-#[cfg(feature = "gpu-nvidia")]
-pub struct GpuInfo {
+/// A process sent SIGTERM that gets SIGKILL if it outlives the grace period
+#[derive(Debug, Clone)]
+pub struct Termination {
+    pub pid: Pid,
     pub name: String,
-    pub usage: f32,
-    pub memory_used: u64,
-    pub memory_total: u64,
-    pub temperature: u32,
+    /// Tells the process apart from a later one reusing its PID
+    pub start_time: u64,
+    pub started: Instant,
+    /// When SIGKILL was sent
+    pub escalated: Option<Instant>,
 }
 
 /// Application state
 pub struct App {
-    /// System information
-    pub system: System,
+    /// Latest snapshot from the collector
+    pub snapshot: Arc<Snapshot>,
     /// Selected process index in the list
     pub selected_process: Option<usize>,
     /// Scroll offset for process list
     pub scroll_offset: usize,
+    /// Rows the process list had room for when it was last drawn
+    pub process_rows: Cell<usize>,
     /// Whether to show detailed view
     pub show_details: bool,
     /// Last update time
@@ -69,186 +220,431 @@ pub struct App {
     pub overall_cpu_history: Vec<f32>,
     /// Memory usage history
     pub memory_history: Vec<f32>,
+    /// Swap usage history
+    pub swap_history: Vec<f32>,
+    /// Throughput history per network interface
+    pub network_history: HashMap<String, NetworkHistory>,
+    /// Index of the interface shown in the network panel
+    pub selected_interface: usize,
+    /// I/O history per block device
+    pub disk_history: HashMap<String, DiskHistory>,
+    /// Reading history per sensor, keyed by `SensorInfo::key`
+    pub sensor_history: HashMap<String, Vec<f32>>,
+    /// History of the selected and pinned processes
+    pub process_history: HashMap<Pid, ProcessHistory>,
+    /// Processes whose history is kept while they aren't selected
+    pub pinned: HashSet<Pid>,
+    /// Processes marked for batch actions
+    pub marked: HashSet<Pid>,
+    /// Panels shown below the CPU overview
+    pub panels: Vec<Panel>,
     /// Should the app quit
     pub should_quit: bool,
     /// Whether to show help overlay
     pub show_help: bool,
     /// Refresh rate in milliseconds
     pub refresh_rate_ms: u64,
-    /// Current sort order for processes
-    pub sort_order: SortOrder,
+    /// Column the process list is sorted by
+    pub sort_column: Column,
+    /// Whether the sort is largest-first
+    pub sort_descending: bool,
+    /// Process table columns, in display order
+    pub columns: Vec<Column>,
+    /// Whether the column editor overlay is open
+    pub show_column_editor: bool,
+    /// Highlighted entry in the column editor
+    pub column_cursor: usize,
+    /// Whether the signal menu overlay is open
+    pub show_signal_menu: bool,
+    /// Highlighted entry in the signal menu, an index into `SIGNALS`
+    pub signal_cursor: usize,
+    /// Whether the signal menu sends to the whole process tree
+    pub signal_tree: bool,
+    /// Whether processes are nested under their parents
+    pub tree_view: bool,
+    /// PIDs whose subtrees are collapsed in tree view
+    pub collapsed: HashSet<Pid>,
     /// Whether in search mode
     pub search_mode: bool,
     /// Current search query
     pub search_query: String,
-    /// Filtered process indices
-    pub filtered_processes: Option<Vec<usize>>,
+    /// Only show these PIDs (empty shows all)
+    pub pid_filter: Vec<Pid>,
+    /// Only show processes owned by this user
+    pub user_filter: Option<String>,
     /// Current toast notification
     pub toast: Option<Toast>,
     /// Pending action confirmation
     pub pending_action: Option<ActionConfirmation>,
+    /// Open priority input overlay
+    pub priority_input: Option<PriorityInput>,
+    /// Terminated processes being watched until they exit
+    pub terminating: Vec<Termination>,
+    /// How long a terminated process gets before SIGKILL; zero never escalates
+    pub terminate_grace: Duration,
     /// History buffer size
     pub history_size: usize,
-    /// GPU information (if available)
-    #[cfg(feature = "gpu-nvidia")]
-    pub gpu_info: Option<GpuInfo>,
-    #[cfg(feature = "gpu-nvidia")]
-    nvml: Option<Nvml>,
-    /// GPU usage history
-    #[cfg(feature = "gpu-nvidia")]
-    pub gpu_usage_history: Vec<f32>,
+    /// History per GPU, in snapshot order
+    pub gpu_history: Vec<GpuHistory>,
+    /// Index of the GPU shown in detail in the GPU panel
+    pub selected_gpu: usize,
+    /// Colors and thresholds
+    pub theme: Theme,
+    /// Normal-mode key bindings
+    pub keymap: Keymap,
+    /// Alert rules and the log of alerts raised
+    pub alerts: Alerts,
+    /// Playback state when showing a recording instead of the live system
+    pub replay: Option<Replay>,
+    /// Appends each snapshot to a file while recording
+    pub recorder: Option<Recorder>,
+    /// Config the app started from, including command-line overrides
+    config: Config,
+    /// Config as loaded from the file, used as the base when saving
+    saved_config: Config,
+    /// Where the config is saved, if there is a config directory
+    config_path: Option<PathBuf>,
 }
 
 impl App {
-    pub fn new() -> Self {
-        let mut system = System::new_all();
-        system.refresh_all();
-
-        let cpu_count = system.cpus().len();
-        let history_size = 60; // Keep 60 data points
-
-        #[cfg(feature = "gpu-nvidia")]
-        let (nvml, gpu_info) = match Nvml::init() {
-            Ok(nvml) => {
-                match nvml.device_by_index(0) {
-                    Ok(device) => {
-                        let name = device.name().unwrap_or_else(|_| "Unknown GPU".to_string());
-                        let info = GpuInfo {
-                            name,
-                            usage: 0.0,
-                            memory_used: 0,
-                            memory_total: device.memory_info().map(|m| m.total / 1024 / 1024).unwrap_or(0),
-                            temperature: 0,
-                        };
-                        (Some(nvml), Some(info))
-                    }
-                    Err(_) => (None, None),
-                }
-            }
-            Err(_) => (None, None),
-        };
+    pub fn new(snapshot: Arc<Snapshot>, config: Config, config_path: Option<PathBuf>) -> Self {
+        let cpu_count = snapshot.cpus.len();
+        let processes = &config.processes;
 
         Self {
-            system,
+            snapshot,
             selected_process: None,
             scroll_offset: 0,
+            process_rows: Cell::new(0),
             show_details: false,
             last_update: Instant::now(),
             cpu_history: vec![Vec::new(); cpu_count],
             overall_cpu_history: Vec::new(),
             memory_history: Vec::new(),
+            swap_history: Vec::new(),
+            network_history: HashMap::new(),
+            selected_interface: 0,
+            disk_history: HashMap::new(),
+            sensor_history: HashMap::new(),
+            process_history: HashMap::new(),
+            pinned: HashSet::new(),
+            marked: HashSet::new(),
+            panels: config.panels.clone(),
             should_quit: false,
             show_help: false,
-            refresh_rate_ms: 500,
-            sort_order: SortOrder::Cpu,
+            refresh_rate_ms: config.refresh_rate_ms,
+            sort_column: processes.sort,
+            sort_descending: processes
+                .sort_descending
+                .unwrap_or_else(|| processes.sort.default_descending()),
+            columns: processes.columns.clone(),
+            show_column_editor: false,
+            column_cursor: 0,
+            show_signal_menu: false,
+            signal_cursor: 0,
+            signal_tree: false,
+            tree_view: processes.tree_view,
+            collapsed: HashSet::new(),
             search_mode: false,
             search_query: String::new(),
-            filtered_processes: None,
+            pid_filter: Vec::new(),
+            user_filter: None,
             toast: None,
             pending_action: None,
-            history_size,
-            #[cfg(feature = "gpu-nvidia")]
-            gpu_info,
-            #[cfg(feature = "gpu-nvidia")]
-            nvml,
-            #[cfg(feature = "gpu-nvidia")]
-            gpu_usage_history: Vec::new(),
+            priority_input: None,
+            terminating: Vec::new(),
+            terminate_grace: Duration::from_secs(processes.terminate_grace_secs),
+            history_size: config.history_size,
+            gpu_history: Vec::new(),
+            selected_gpu: 0,
+            theme: config.theme.clone(),
+            // Validated when the config was loaded
+            keymap: Keymap::new(&config.keys).unwrap_or_default(),
+            alerts: Alerts::new(config.alerts.clone()),
+            replay: None,
+            recorder: None,
+            saved_config: config.clone(),
+            config,
+            config_path,
         }
     }
 
-    /// Update system information
-    pub fn update(&mut self) {
-        self.system.refresh_all();
-        self.last_update = Instant::now();
+    /// Apply a new snapshot from the collector
+    pub fn update(&mut self, snapshot: Arc<Snapshot>) {
+        if let Some(ref mut recorder) = self.recorder {
+            if let Err(err) = recorder.record(&snapshot) {
+                self.recorder = None;
+                self.show_toast(err, ToastLevel::Error);
+            }
+        }
 
-        // Update toast expiration
-        self.update_toast();
+        self.snapshot = snapshot;
+        self.last_update = Instant::now();
 
         // Update CPU history
-        for (i, cpu) in self.system.cpus().iter().enumerate() {
-            if i < self.cpu_history.len() {
-                self.cpu_history[i].push(cpu.cpu_usage());
-                if self.cpu_history[i].len() > self.history_size {
-                    self.cpu_history[i].remove(0);
-                }
-            }
+        if self.cpu_history.len() != self.snapshot.cpus.len() {
+            self.cpu_history.resize(self.snapshot.cpus.len(), Vec::new());
+        }
+        for (i, cpu) in self.snapshot.cpus.iter().enumerate() {
+            push_history(&mut self.cpu_history[i], cpu.usage, self.history_size);
         }
 
         // Update overall CPU usage
-        let overall_cpu = self.system.global_cpu_usage();
-        self.overall_cpu_history.push(overall_cpu);
-        if self.overall_cpu_history.len() > self.history_size {
-            self.overall_cpu_history.remove(0);
-        }
+        push_history(
+            &mut self.overall_cpu_history,
+            self.snapshot.global_cpu_usage,
+            self.history_size,
+        );
 
         // Update memory history
-        let mem_usage = (self.system.used_memory() as f32 / self.system.total_memory() as f32) * 100.0;
-        self.memory_history.push(mem_usage);
-        if self.memory_history.len() > self.history_size {
-            self.memory_history.remove(0);
-        }
-
-        // Update GPU information
-        #[cfg(feature = "gpu-nvidia")]
-        if let Some(ref nvml) = self.nvml {
-            if let Ok(device) = nvml.device_by_index(0) {
-                if let Some(ref mut gpu_info) = self.gpu_info {
-                    gpu_info.usage = device
-                        .utilization_rates()
-                        .map(|u| u.gpu as f32)
-                        .unwrap_or(0.0);
-
-                    if let Ok(mem_info) = device.memory_info() {
-                        gpu_info.memory_used = mem_info.used / 1024 / 1024;
-                    }
+        push_history(
+            &mut self.memory_history,
+            self.snapshot.memory_usage(),
+            self.history_size,
+        );
+        push_history(
+            &mut self.swap_history,
+            self.snapshot.swap_usage(),
+            self.history_size,
+        );
 
-                    gpu_info.temperature = device
-                        .temperature(nvml_wrapper::enum_wrappers::device::TemperatureSensor::Gpu)
-                        .unwrap_or(0);
+        // Update network history, dropping interfaces that disappeared
+        let snapshot = Arc::clone(&self.snapshot);
+        self.network_history
+            .retain(|name, _| snapshot.networks.iter().any(|n| &n.name == name));
+        for network in &snapshot.networks {
+            let history = self.network_history.entry(network.name.clone()).or_default();
+            push_history(&mut history.rx, network.rx_rate, self.history_size);
+            push_history(&mut history.tx, network.tx_rate, self.history_size);
+        }
+        if self.selected_interface >= snapshot.networks.len() {
+            self.selected_interface = 0;
+        }
 
-                    // Update GPU usage history
-                    self.gpu_usage_history.push(gpu_info.usage);
-                    if self.gpu_usage_history.len() > self.history_size {
-                        self.gpu_usage_history.remove(0);
-                    }
-                }
-            }
+        // Update block device history
+        self.disk_history
+            .retain(|name, _| snapshot.block_devices.iter().any(|d| &d.name == name));
+        for device in &snapshot.block_devices {
+            let history = self.disk_history.entry(device.name.clone()).or_default();
+            push_history(&mut history.read, device.read_rate, self.history_size);
+            push_history(&mut history.write, device.write_rate, self.history_size);
         }
-    }
 
-    /// Get sorted processes based on current sort order
-    pub fn get_sorted_processes(&self) -> Vec<(&Pid, &Process)> {
-        let mut processes: Vec<_> = self.system.processes().iter().collect();
+        // Update sensor history
+        let keys: Vec<String> = snapshot.sensors.iter().map(|s| s.key()).collect();
+        self.sensor_history.retain(|key, _| keys.contains(key));
+        for (sensor, key) in snapshot.sensors.iter().zip(keys) {
+            let history = self.sensor_history.entry(key).or_default();
+            push_history(history, sensor.value, self.history_size);
+        }
 
-        match self.sort_order {
-            SortOrder::Cpu => {
-                processes.sort_by(|a, b| {
-                    b.1.cpu_usage().partial_cmp(&a.1.cpu_usage()).unwrap_or(std::cmp::Ordering::Equal)
-                });
+        // Update history of the selected and pinned processes; pins go away
+        // with their process
+        let processes: HashMap<Pid, &ProcessInfo> =
+            snapshot.processes.iter().map(|process| (process.pid, process)).collect();
+        self.pinned.retain(|pid| processes.contains_key(pid));
+        self.marked.retain(|pid| processes.contains_key(pid));
+        let mut tracked: Vec<Pid> = self.pinned.iter().copied().collect();
+        if let Some(pid) = self.get_selected_process().map(|process| process.pid) {
+            if !tracked.contains(&pid) {
+                tracked.push(pid);
             }
-            SortOrder::Memory => {
-                processes.sort_by(|a, b| {
-                    b.1.memory().cmp(&a.1.memory())
-                });
+        }
+        self.process_history.retain(|pid, _| tracked.contains(pid));
+        for pid in tracked {
+            let Some(process) = processes.get(&pid) else {
+                continue;
+            };
+            let history = self.process_history.entry(pid).or_default();
+            if history.start_time != process.start_time {
+                *history = ProcessHistory {
+                    start_time: process.start_time,
+                    ..Default::default()
+                };
             }
-            SortOrder::Pid => {
-                processes.sort_by(|a, b| {
-                    a.0.as_u32().cmp(&b.0.as_u32())
-                });
+            push_history(&mut history.cpu, process.cpu_usage, self.history_size);
+            push_history(&mut history.memory, process.memory, self.history_size);
+            push_history(&mut history.disk_read, process.disk_read_rate, self.history_size);
+            push_history(&mut history.disk_write, process.disk_write_rate, self.history_size);
+        }
+
+        // Update GPU history
+        self.gpu_history.resize(snapshot.gpus.len(), GpuHistory::default());
+        for (gpu, history) in snapshot.gpus.iter().zip(self.gpu_history.iter_mut()) {
+            push_history(&mut history.usage, gpu.usage, self.history_size);
+            push_history(&mut history.memory, gpu.memory_usage(), self.history_size);
+            push_history(&mut history.temperature, gpu.temperature as f32, self.history_size);
+            if let Some(power) = gpu.power_usage() {
+                push_history(&mut history.power, power, self.history_size);
+            }
+            if let Some(encoder) = gpu.encoder_usage {
+                push_history(&mut history.encoder, encoder, self.history_size);
+            }
+            if let Some(decoder) = gpu.decoder_usage {
+                push_history(&mut history.decoder, decoder, self.history_size);
             }
-            SortOrder::Runtime => {
-                processes.sort_by(|a, b| {
-                    b.1.run_time().cmp(&a.1.run_time())
-                });
+            if let (Some(rx), Some(tx)) = (gpu.pcie_rx_rate, gpu.pcie_tx_rate) {
+                push_history(&mut history.pcie_rx, rx, self.history_size);
+                push_history(&mut history.pcie_tx, tx, self.history_size);
             }
         }
+        if self.selected_gpu >= snapshot.gpus.len() {
+            self.selected_gpu = 0;
+        }
+
+        self.check_terminations();
+
+        // Check alert rules against the updated history; replays only toast
+        let mut alerts = std::mem::take(&mut self.alerts);
+        for event in alerts.evaluate(self, self.replay.is_none()) {
+            let message = if event.fired {
+                format!("Alert: {} ({})", event.rule, event.value)
+            } else {
+                format!("Cleared: {} ({})", event.rule, event.value)
+            };
+            let level = if event.fired { event.level } else { ToastLevel::Success };
+            self.show_toast(message, level);
+        }
+        self.alerts = alerts;
+
+        // Keep the selection inside the (possibly shorter) process list
+        let process_count = self.get_display_processes().len();
+        if let Some(selected) = self.selected_process {
+            self.selected_process = if process_count == 0 {
+                None
+            } else {
+                Some(selected.min(process_count - 1))
+            };
+        }
+    }
+
+    /// Forget all history, before refilling it from another point in a replay
+    fn clear_history(&mut self) {
+        self.cpu_history.iter_mut().for_each(Vec::clear);
+        self.overall_cpu_history.clear();
+        self.memory_history.clear();
+        self.swap_history.clear();
+        self.network_history.clear();
+        self.disk_history.clear();
+        self.sensor_history.clear();
+        self.process_history.clear();
+        self.gpu_history.clear();
+    }
+
+    /// Feed the frames a playing replay has reached
+    pub fn tick_replay(&mut self) {
+        let Some(ref mut replay) = self.replay else {
+            return;
+        };
+        for frame in replay.tick() {
+            self.update(frame);
+        }
+    }
+
+    /// Pause or resume the replay
+    pub fn toggle_replay_pause(&mut self) {
+        if let Some(ref mut replay) = self.replay {
+            replay.toggle_pause();
+        }
+    }
+
+    /// Play the replay faster or slower
+    pub fn change_replay_speed(&mut self, faster: bool) {
+        if let Some(ref mut replay) = self.replay {
+            replay.change_speed(faster);
+        }
+    }
+
+    /// Jump the replay back or forward; the step grows with the speed
+    pub fn seek_replay(&mut self, forward: bool) {
+        let Some(ref mut replay) = self.replay else {
+            return;
+        };
+        let step = (REPLAY_SEEK_MS as f64 * replay.speed().max(1.0)) as i64;
+        let delta = if forward { step } else { -step };
+
+        let frames = replay.seek(delta, self.history_size);
+        self.clear_history();
+        // Alerts are re-evaluated over the refilled history
+        if let Some(first) = frames.first() {
+            self.alerts.rewind(first.timestamp);
+        }
+        for frame in frames {
+            self.update(frame);
+        }
+    }
+
+    /// Get processes sorted by the current sort column
+    pub fn get_sorted_processes(&self) -> Vec<&ProcessInfo> {
+        let mut processes: Vec<_> = self.snapshot.processes.iter().collect();
+
+        processes.sort_by(|a, b| {
+            let ordering = self.sort_column.compare(a, b);
+            let ordering = if self.sort_descending {
+                ordering.reverse()
+            } else {
+                ordering
+            };
+            // Break ties by PID so equal rows don't shuffle between refreshes
+            ordering.then_with(|| a.pid.cmp(&b.pid))
+        });
 
         processes
     }
 
+    /// First row of the process list to draw when `visible` rows fit,
+    /// moved just enough to keep the selection on screen
+    pub fn process_scroll(&self, visible: usize) -> usize {
+        match self.selected_process {
+            Some(selected) if selected < self.scroll_offset => selected,
+            Some(selected) if selected >= self.scroll_offset + visible => selected + 1 - visible,
+            Some(_) => self.scroll_offset,
+            None => 0,
+        }
+    }
+
+    /// Read nice values and priorities, which the collector leaves out so it
+    /// doesn't open `/proc/<pid>/stat` for every process each tick.
+    ///
+    /// Only the rows on screen and the selected process are read, or every
+    /// process while the list is sorted by one of them; processes already
+    /// read since the last snapshot are skipped. Replays keep their recorded
+    /// values, as the PIDs may belong to other processes by now.
+    pub fn read_priorities(&mut self) {
+        if self.replay.is_some() {
+            return;
+        }
+
+        let pids: HashSet<Pid> = if matches!(self.sort_column, Column::Nice | Column::Priority) {
+            self.snapshot.processes.iter().map(|process| process.pid).collect()
+        } else {
+            let visible = self.process_rows.get();
+            self.get_process_rows()
+                .iter()
+                .skip(self.process_scroll(visible))
+                .take(visible)
+                .map(|row| row.process.pid)
+                .chain(self.get_selected_process().map(|process| process.pid))
+                .collect()
+        };
+        let unread = |process: &ProcessInfo| process.nice.is_none() && pids.contains(&process.pid);
+        if !self.snapshot.processes.iter().any(unread) {
+            return;
+        }
+
+        let snapshot = Arc::make_mut(&mut self.snapshot);
+        for process in snapshot.processes.iter_mut().filter(|process| unread(process)) {
+            if let Some(stat) = procfs::read_process_stat(process.pid.as_u32()) {
+                process.nice = Some(stat.nice);
+                process.priority = Some(stat.priority);
+                process.threads = Some(stat.threads);
+            }
+        }
+    }
+
     /// Navigate process list down
     pub fn next_process(&mut self) {
-        let process_count = self.system.processes().len();
+        let process_count = self.get_display_processes().len();
         if process_count == 0 {
             return;
         }
@@ -271,15 +667,40 @@ impl App {
         }
     }
 
+    /// Cycle the network panel to the next interface
+    pub fn next_interface(&mut self) {
+        let count = self.snapshot.networks.len();
+        if count > 0 {
+            self.selected_interface = (self.selected_interface + 1) % count;
+        }
+    }
+
+    /// Cycle the GPU panel to the next GPU
+    pub fn next_gpu(&mut self) {
+        let count = self.snapshot.gpus.len();
+        if count > 0 {
+            self.selected_gpu = (self.selected_gpu + 1) % count;
+        }
+    }
+
+    /// Show or hide the alert log panel
+    pub fn toggle_alerts_panel(&mut self) {
+        if self.panels.contains(&Panel::Alerts) {
+            self.panels.retain(|&panel| panel != Panel::Alerts);
+        } else {
+            self.panels.push(Panel::Alerts);
+        }
+    }
+
     /// Toggle details view
     pub fn toggle_details(&mut self) {
         self.show_details = !self.show_details;
     }
 
     /// Get selected process
-    pub fn get_selected_process(&self) -> Option<(&Pid, &Process)> {
+    pub fn get_selected_process(&self) -> Option<&ProcessInfo> {
         if let Some(idx) = self.selected_process {
-            self.get_sorted_processes().get(idx).copied()
+            self.get_display_processes().get(idx).copied()
         } else {
             None
         }
@@ -317,73 +738,263 @@ impl App {
         Duration::from_millis(self.refresh_rate_ms)
     }
 
-    /// Set sort order
-    pub fn set_sort_order(&mut self, order: SortOrder) {
-        self.sort_order = order;
+    /// Sort by a column, or flip the direction if already sorted by it
+    pub fn sort_by(&mut self, column: Column) {
+        if self.sort_column == column {
+            self.sort_descending = !self.sort_descending;
+        } else {
+            self.sort_column = column;
+            self.sort_descending = column.default_descending();
+        }
+    }
+
+    /// Move the sort to the next (or previous) visible column
+    pub fn cycle_sort_column(&mut self, forward: bool) {
+        let sortable: Vec<Column> = self
+            .visible_columns()
+            .into_iter()
+            .filter(|column| column.is_sortable())
+            .collect();
+        if sortable.is_empty() {
+            return;
+        }
+
+        let next = match sortable.iter().position(|&c| c == self.sort_column) {
+            Some(i) if forward => (i + 1) % sortable.len(),
+            Some(i) => (i + sortable.len() - 1) % sortable.len(),
+            None => 0,
+        };
+        self.sort_column = sortable[next];
+        self.sort_descending = sortable[next].default_descending();
+    }
+
+    /// Columns to draw, including the subtree totals in tree view
+    pub fn visible_columns(&self) -> Vec<Column> {
+        let mut columns = self.columns.clone();
+        if self.tree_view {
+            columns.extend([Column::SubtreeCpu, Column::SubtreeMemory]);
+        }
+        columns
+    }
+
+    /// Entries of the column editor: shown columns in order, then hidden ones
+    pub fn editor_columns(&self) -> Vec<(Column, bool)> {
+        let hidden = Column::ALL
+            .into_iter()
+            .filter(|column| !self.columns.contains(column))
+            .map(|column| (column, false));
+        self.columns
+            .iter()
+            .map(|&column| (column, true))
+            .chain(hidden)
+            .collect()
+    }
+
+    /// Open or close the column editor
+    pub fn toggle_column_editor(&mut self) {
+        self.show_column_editor = !self.show_column_editor;
+        self.column_cursor = 0;
+    }
+
+    /// Move the column editor cursor
+    pub fn move_column_cursor(&mut self, forward: bool) {
+        let count = Column::ALL.len();
+        self.column_cursor = if forward {
+            (self.column_cursor + 1).min(count - 1)
+        } else {
+            self.column_cursor.saturating_sub(1)
+        };
+    }
+
+    /// Show or hide the column under the editor cursor
+    pub fn toggle_column(&mut self) {
+        let Some(&(column, shown)) = self.editor_columns().get(self.column_cursor) else {
+            return;
+        };
+
+        if shown {
+            // Keep at least one column on screen
+            if self.columns.len() > 1 {
+                self.columns.retain(|&c| c != column);
+                // Follow the column to its new place among the hidden ones
+                self.column_cursor = self.editor_columns().iter().position(|&(c, _)| c == column).unwrap_or(0);
+            }
+        } else {
+            self.columns.push(column);
+            self.column_cursor = self.columns.len() - 1;
+        }
+    }
+
+    /// Move the column under the editor cursor left or right in the table
+    pub fn move_column(&mut self, forward: bool) {
+        let from = self.column_cursor;
+        if from >= self.columns.len() {
+            return;
+        }
+
+        let to = if forward {
+            from + 1
+        } else {
+            from.wrapping_sub(1)
+        };
+        if to < self.columns.len() {
+            self.columns.swap(from, to);
+            self.column_cursor = to;
+        }
     }
 
     /// Start search mode
     pub fn start_search(&mut self) {
         self.search_mode = true;
         self.search_query.clear();
-        self.filtered_processes = None;
     }
 
     /// Exit search mode
     pub fn exit_search(&mut self) {
         self.search_mode = false;
         self.search_query.clear();
-        self.filtered_processes = None;
     }
 
     /// Add character to search query
     pub fn search_input(&mut self, c: char) {
         self.search_query.push(c);
-        self.update_filter();
+        self.selected_process = None;
     }
 
     /// Remove last character from search query
     pub fn search_backspace(&mut self) {
         self.search_query.pop();
-        self.update_filter();
+        self.selected_process = None;
     }
 
-    /// Update process filter based on search query
-    pub fn update_filter(&mut self) {
+    /// Check whether a process matches the search query and filters
+    fn matches_filter(&self, process: &ProcessInfo) -> bool {
+        if !self.pid_filter.is_empty() && !self.pid_filter.contains(&process.pid) {
+            return false;
+        }
+        if let Some(ref user) = self.user_filter {
+            if process.user.as_ref() != Some(user) {
+                return false;
+            }
+        }
         if self.search_query.is_empty() {
-            self.filtered_processes = None;
-            return;
+            return true;
         }
 
         let query = self.search_query.to_lowercase();
-        let all_processes = self.get_sorted_processes();
+        process.name.to_lowercase().contains(&query) || process.pid.to_string().contains(&query)
+    }
 
-        let mut filtered_indices = Vec::new();
-        for (idx, (_pid, process)) in all_processes.iter().enumerate() {
-            let name = process.name().to_string_lossy().to_lowercase();
-            let pid_str = _pid.to_string();
+    /// Get the rows shown in the process list (flat or tree)
+    pub fn get_process_rows(&self) -> Vec<ProcessRow<'_>> {
+        let sorted = self.get_sorted_processes();
 
-            if name.contains(&query) || pid_str.contains(&query) {
-                filtered_indices.push(idx);
-            }
+        if self.tree_view {
+            process_tree::build_rows(&sorted, &self.collapsed, |p| self.matches_filter(p))
+        } else {
+            sorted
+                .into_iter()
+                .filter(|process| self.matches_filter(process))
+                .map(ProcessRow::flat)
+                .collect()
         }
-
-        self.filtered_processes = Some(filtered_indices);
     }
 
     /// Get display processes (filtered or all)
-    pub fn get_display_processes(&self) -> Vec<(&Pid, &Process)> {
-        let all_processes = self.get_sorted_processes();
+    pub fn get_display_processes(&self) -> Vec<&ProcessInfo> {
+        self.get_process_rows().into_iter().map(|row| row.process).collect()
+    }
 
-        if let Some(ref filtered_indices) = self.filtered_processes {
-            filtered_indices.iter()
-                .filter_map(|&idx| all_processes.get(idx).copied())
-                .collect()
+    /// Pin or unpin the selected process, keeping its history while it isn't selected
+    pub fn toggle_pin(&mut self) {
+        let Some(process) = self.get_selected_process() else {
+            return;
+        };
+        let (pid, name) = (process.pid, process.name.clone());
+
+        if self.pinned.remove(&pid) {
+            self.show_toast(format!("Unpinned {} ({})", name, pid), ToastLevel::Info);
         } else {
-            all_processes
+            self.pinned.insert(pid);
+            self.show_toast(format!("Pinned {} ({})", name, pid), ToastLevel::Info);
         }
     }
 
+    /// Mark or unmark the selected process and move to the next one
+    pub fn toggle_mark(&mut self) {
+        let Some(pid) = self.get_selected_process().map(|process| process.pid) else {
+            return;
+        };
+        if !self.marked.remove(&pid) {
+            self.marked.insert(pid);
+        }
+        self.next_process();
+    }
+
+    /// Mark every process the current search and filters show, or unmark
+    /// them if they are all marked already
+    pub fn mark_matching(&mut self) {
+        let pids: Vec<Pid> = self
+            .get_display_processes()
+            .iter()
+            .map(|process| process.pid)
+            .collect();
+        if pids.is_empty() {
+            return;
+        }
+
+        if pids.iter().all(|pid| self.marked.contains(pid)) {
+            for pid in &pids {
+                self.marked.remove(pid);
+            }
+            self.show_toast(format!("Unmarked {} processes", pids.len()), ToastLevel::Info);
+        } else {
+            self.marked.extend(&pids);
+            self.show_toast(
+                format!("{} processes marked", self.marked.len()),
+                ToastLevel::Info,
+            );
+        }
+    }
+
+    /// Unmark all processes
+    pub fn clear_marks(&mut self) {
+        self.marked.clear();
+    }
+
+    /// Toggle between the flat list and the process tree
+    pub fn toggle_tree_view(&mut self) {
+        self.tree_view = !self.tree_view;
+        self.selected_process = None;
+        self.scroll_offset = 0;
+    }
+
+    /// Collapse the selected subtree (tree view only)
+    pub fn collapse_selected(&mut self) {
+        if let Some(row) = self.selected_row() {
+            if row.has_children {
+                let pid = row.process.pid;
+                self.collapsed.insert(pid);
+            }
+        }
+    }
+
+    /// Expand the selected subtree (tree view only)
+    pub fn expand_selected(&mut self) {
+        if let Some(pid) = self.selected_row().map(|row| row.process.pid) {
+            self.collapsed.remove(&pid);
+        }
+    }
+
+    /// Get the selected row when in tree view
+    fn selected_row(&self) -> Option<ProcessRow<'_>> {
+        if !self.tree_view {
+            return None;
+        }
+        self.selected_process
+            .and_then(|idx| self.get_process_rows().into_iter().nth(idx))
+    }
+
     /// Show toast notification
     pub fn show_toast(&mut self, message: String, level: ToastLevel) {
         let expires_at = Instant::now() + Duration::from_secs(3);
@@ -405,16 +1016,242 @@ impl App {
 
     /// Request process action with confirmation
     pub fn request_action(&mut self, action: ProcessAction) {
-        if let Some((_pid, process)) = self.get_selected_process() {
-            let pid = *_pid;
-            let process_name = process.name().to_string_lossy().to_string();
-
-            self.pending_action = Some(ActionConfirmation {
-                action,
-                pid,
-                process_name,
-            });
+        self.request_action_on(action, false);
+    }
+
+    /// Request an action on the selected process and all its descendants
+    pub fn request_tree_action(&mut self, action: ProcessAction) {
+        self.request_action_on(action, true);
+    }
+
+    fn request_action_on(&mut self, action: ProcessAction, tree: bool) {
+        // PIDs in a recording may belong to unrelated processes by now
+        if self.replay.is_some() {
+            self.show_toast(
+                "Process actions are disabled during replay".to_string(),
+                ToastLevel::Warning,
+            );
+            return;
+        }
+
+        let (mut targets, marked) = self.action_targets();
+        if targets.is_empty() {
+            return;
+        }
+
+        let roots = targets.len();
+        if tree {
+            let mut seen: HashSet<Pid> = targets.iter().map(|(pid, _)| *pid).collect();
+            for i in 0..roots {
+                for process in process_tree::descendants(&self.snapshot.processes, targets[i].0) {
+                    if seen.insert(process.pid) {
+                        targets.push((process.pid, process.name.clone()));
+                    }
+                }
+            }
+        }
+
+        self.pending_action = Some(ActionConfirmation {
+            action,
+            descendants: targets.len() - roots,
+            targets,
+            marked,
+        });
+    }
+
+    /// Whether process actions have anything to act on: marked processes,
+    /// or else the selected one
+    pub fn has_action_targets(&self) -> bool {
+        !self.marked.is_empty() || self.get_selected_process().is_some()
+    }
+
+    /// Processes an action applies to, as (PID, name), and whether they are
+    /// the marked set. Marked processes take over from the selection.
+    fn action_targets(&self) -> (Vec<(Pid, String)>, bool) {
+        if self.marked.is_empty() {
+            let targets = self
+                .get_selected_process()
+                .map(|process| vec![(process.pid, process.name.clone())])
+                .unwrap_or_default();
+            return (targets, false);
+        }
+
+        let targets = self
+            .get_sorted_processes()
+            .into_iter()
+            .filter(|process| self.marked.contains(&process.pid))
+            .map(|process| (process.pid, process.name.clone()))
+            .collect();
+        (targets, true)
+    }
+
+    /// Open the priority input for the marked or selected processes, starting
+    /// from the selected (or first marked) process's current value
+    pub fn open_priority_input(&mut self, field: PriorityField) {
+        if self.replay.is_some() {
+            self.show_toast(
+                "Process actions are disabled during replay".to_string(),
+                ToastLevel::Warning,
+            );
+            return;
+        }
+
+        let (targets, _) = self.action_targets();
+        let Some(&(pid, _)) = targets.first() else {
+            return;
+        };
+        let current = self
+            .get_selected_process()
+            .filter(|process| self.marked.is_empty() || self.marked.contains(&process.pid))
+            .or_else(|| self.snapshot.processes.iter().find(|process| process.pid == pid));
+        let nice = current.and_then(|process| process.nice).unwrap_or(0);
+
+        let (text, class) = match field {
+            PriorityField::Nice => (nice.to_string(), IoClass::BestEffort),
+            PriorityField::Io => {
+                let pid = current.map_or(pid, |process| process.pid);
+                match priority::io_priority(pid.as_u32()) {
+                    Some(io) if io.class != IoClass::None => (io.level.to_string(), io.class),
+                    // Without a class the kernel uses best-effort at a level
+                    // derived from the nice value
+                    _ => (((nice + 20) / 5).clamp(0, IO_LEVEL_MAX as i64).to_string(), IoClass::BestEffort),
+                }
+            }
+        };
+        self.priority_input = Some(PriorityInput { field, text, class });
+    }
+
+    /// I/O priority of the selected process, read when asked rather than
+    /// collected for every process; `None` during replay, where the PID may
+    /// belong to another process by now
+    pub fn selected_io_priority(&self) -> Option<IoPriority> {
+        if self.replay.is_some() {
+            return None;
         }
+        priority::io_priority(self.get_selected_process()?.pid.as_u32())
+    }
+
+    /// Close the priority input without changing anything
+    pub fn close_priority_input(&mut self) {
+        self.priority_input = None;
+    }
+
+    /// Type into the priority input; only digits and a leading minus sign
+    pub fn priority_input_char(&mut self, c: char) {
+        let Some(ref mut input) = self.priority_input else {
+            return;
+        };
+        let allowed = c.is_ascii_digit()
+            || (c == '-' && input.field == PriorityField::Nice && input.text.is_empty());
+        if allowed && input.text.len() < 3 {
+            input.text.push(c);
+        }
+    }
+
+    /// Delete the last typed character of the priority input
+    pub fn priority_input_backspace(&mut self) {
+        if let Some(ref mut input) = self.priority_input {
+            input.text.pop();
+        }
+    }
+
+    /// Step the typed value up or down within its range
+    pub fn adjust_priority(&mut self, up: bool) {
+        let Some(ref mut input) = self.priority_input else {
+            return;
+        };
+        let (min, max) = match input.field {
+            PriorityField::Nice => NICE_RANGE,
+            PriorityField::Io => (0, IO_LEVEL_MAX as i32),
+        };
+        let value = input.text.parse::<i32>().unwrap_or(0) + if up { 1 } else { -1 };
+        input.text = value.clamp(min, max).to_string();
+    }
+
+    /// Cycle the I/O scheduling class
+    pub fn cycle_io_class(&mut self, forward: bool) {
+        let Some(ref mut input) = self.priority_input else {
+            return;
+        };
+        let classes = IoClass::SETTABLE;
+        let i = classes.iter().position(|&c| c == input.class).unwrap_or(0);
+        input.class = if forward {
+            classes[(i + 1) % classes.len()]
+        } else {
+            classes[(i + classes.len() - 1) % classes.len()]
+        };
+    }
+
+    /// Ask to apply the typed priority to the marked or selected processes
+    pub fn apply_priority(&mut self) {
+        let Some(input) = self.priority_input.clone() else {
+            return;
+        };
+
+        let action = match input.field {
+            PriorityField::Nice => match input.text.parse::<i32>() {
+                Ok(nice) if (NICE_RANGE.0..=NICE_RANGE.1).contains(&nice) => ProcessAction::Renice(nice),
+                _ => {
+                    self.show_toast(
+                        format!("Nice value must be between {} and {}", NICE_RANGE.0, NICE_RANGE.1),
+                        ToastLevel::Error,
+                    );
+                    return;
+                }
+            },
+            PriorityField::Io => match input.text.parse::<u8>() {
+                Ok(level) if level <= IO_LEVEL_MAX || !input.class.has_level() => {
+                    ProcessAction::SetIoPriority(IoPriority {
+                        class: input.class,
+                        level: level.min(IO_LEVEL_MAX),
+                    })
+                }
+                _ => {
+                    self.show_toast(
+                        format!("I/O level must be between 0 and {}", IO_LEVEL_MAX),
+                        ToastLevel::Error,
+                    );
+                    return;
+                }
+            },
+        };
+        self.priority_input = None;
+        self.request_action_on(action, false);
+    }
+
+    /// Open the signal menu for the marked or selected processes
+    pub fn open_signal_menu(&mut self) {
+        if self.has_action_targets() {
+            self.show_signal_menu = true;
+            self.signal_cursor = 0;
+            self.signal_tree = false;
+        }
+    }
+
+    /// Close the signal menu without sending anything
+    pub fn close_signal_menu(&mut self) {
+        self.show_signal_menu = false;
+    }
+
+    /// Move the signal menu cursor
+    pub fn move_signal_cursor(&mut self, forward: bool) {
+        self.signal_cursor = if forward {
+            (self.signal_cursor + 1).min(SIGNALS.len() - 1)
+        } else {
+            self.signal_cursor.saturating_sub(1)
+        };
+    }
+
+    /// Switch the signal menu between the process and its whole tree
+    pub fn toggle_signal_tree(&mut self) {
+        self.signal_tree = !self.signal_tree;
+    }
+
+    /// Ask for confirmation to send the highlighted signal
+    pub fn choose_signal(&mut self) {
+        self.show_signal_menu = false;
+        let (signal, _, _) = SIGNALS[self.signal_cursor];
+        self.request_action_on(ProcessAction::Signal(signal), self.signal_tree);
     }
 
     /// Cancel pending action
@@ -425,51 +1262,214 @@ impl App {
     /// Execute pending action
     pub fn execute_action(&mut self) {
         if let Some(ref confirmation) = self.pending_action.clone() {
-            let result = match confirmation.action {
-                ProcessAction::Kill => self.kill_process(confirmation.pid),
-                ProcessAction::Suspend => self.suspend_process(confirmation.pid),
-                ProcessAction::Resume => self.resume_process(confirmation.pid),
-            };
-
-            match result {
-                Ok(msg) => {
-                    self.show_toast(msg, ToastLevel::Success);
-                }
-                Err(err) => {
-                    self.show_toast(err, ToastLevel::Error);
+            if let [(pid, _)] = confirmation.targets[..] {
+                match self.apply_action(confirmation.action, pid) {
+                    Ok(msg) => {
+                        self.show_toast(msg, ToastLevel::Success);
+                    }
+                    Err(err) => {
+                        self.show_toast(err, ToastLevel::Error);
+                    }
                 }
+            } else {
+                // Parents go first so they can't start replacement children
+                let pids: Vec<Pid> = confirmation.targets.iter().map(|(pid, _)| *pid).collect();
+                let (message, level) = self.apply_action_to_all(confirmation.action, &pids);
+                self.show_toast(message, level);
             }
 
             self.pending_action = None;
         }
     }
 
-    /// Kill a process
+    /// Run an action on one process
+    fn apply_action(&mut self, action: ProcessAction, pid: Pid) -> Result<String, String> {
+        match action {
+            ProcessAction::Kill => self.kill_process(pid),
+            ProcessAction::Suspend => self.suspend_process(pid),
+            ProcessAction::Resume => self.resume_process(pid),
+            ProcessAction::Signal(signal) => self.send_signal(pid, signal),
+            ProcessAction::Renice(nice) => priority::set_nice(pid.as_u32(), nice)
+                .map(|()| format!("Set nice {} on process {}", nice, pid)),
+            ProcessAction::SetIoPriority(io) => priority::set_io_priority(pid.as_u32(), io)
+                .map(|()| format!("Set I/O priority {} on process {}", io, pid)),
+        }
+    }
+
+    /// Run an action on several processes and sum up the per-PID results
+    /// in one toast message
+    fn apply_action_to_all(&mut self, action: ProcessAction, pids: &[Pid]) -> (String, ToastLevel) {
+        let mut failed = Vec::new();
+        let mut first_error = None;
+        for &pid in pids {
+            if let Err(err) = self.apply_action(action, pid) {
+                failed.push(pid);
+                first_error.get_or_insert(err);
+            }
+        }
+
+        let Some(err) = first_error else {
+            return (
+                format!("{} {} processes", action.past_tense(), pids.len()),
+                ToastLevel::Success,
+            );
+        };
+        if failed.len() == pids.len() {
+            return (
+                format!("Failed on all {} processes: {}", pids.len(), err),
+                ToastLevel::Error,
+            );
+        }
+
+        // The toast is one line, so only the first few PIDs are listed
+        let mut listed: Vec<String> = failed.iter().take(5).map(|pid| pid.to_string()).collect();
+        if failed.len() > listed.len() {
+            listed.push("…".to_string());
+        }
+        (
+            format!(
+                "{} {} of {} processes; failed on PID {}",
+                action.past_tense(),
+                pids.len() - failed.len(),
+                pids.len(),
+                listed.join(", ")
+            ),
+            ToastLevel::Warning,
+        )
+    }
+
+    /// Ask a process to terminate, and watch it so it can be sent SIGKILL
+    /// if it is still running after the grace period (Unix)
+    #[cfg(target_family = "unix")]
     fn kill_process(&mut self, pid: Pid) -> Result<String, String> {
-        if let Some(process) = self.system.process(pid) {
-            if process.kill() {
-                Ok(format!("Process {} killed successfully", pid))
-            } else {
-                Err(format!("Failed to kill process {}", pid))
+        let message = self.send_signal(pid, Signal::Term)?;
+        if self.terminate_grace.is_zero() {
+            return Ok(message);
+        }
+
+        let Some(process) = self.snapshot.processes.iter().find(|p| p.pid == pid) else {
+            return Ok(message);
+        };
+        let termination = Termination {
+            pid,
+            name: process.name.clone(),
+            start_time: process.start_time,
+            started: Instant::now(),
+            escalated: None,
+        };
+        self.terminating.retain(|t| t.pid != pid);
+        self.terminating.push(termination);
+
+        Ok(format!(
+            "{}; SIGKILL in {}s if it doesn't exit",
+            message,
+            self.terminate_grace.as_secs()
+        ))
+    }
+
+    /// Follow up on terminated processes: report the ones that exited, and
+    /// send SIGKILL to those still running after the grace period
+    fn check_terminations(&mut self) {
+        let now = Instant::now();
+        let mut toasts = Vec::new();
+
+        for mut termination in std::mem::take(&mut self.terminating) {
+            let label = format!("{} ({})", termination.name, termination.pid);
+            // A zombie has exited; it only waits for its parent to reap it
+            let running = self.snapshot.processes.iter().any(|p| {
+                p.pid == termination.pid
+                    && p.start_time == termination.start_time
+                    && p.status != "Zombie"
+            });
+
+            if !running {
+                let message = match termination.escalated {
+                    Some(_) => format!("{} killed with SIGKILL", label),
+                    None => format!("{} exited after SIGTERM", label),
+                };
+                toasts.push((message, ToastLevel::Success));
+                continue;
             }
-        } else {
-            Err(format!("Process {} not found", pid))
+
+            match termination.escalated {
+                None if now.duration_since(termination.started) >= self.terminate_grace => {
+                    match self.send_signal(termination.pid, Signal::Kill) {
+                        Ok(_) => {
+                            toasts.push((
+                                format!(
+                                    "{} ignored SIGTERM for {}s; sent SIGKILL",
+                                    label,
+                                    self.terminate_grace.as_secs()
+                                ),
+                                ToastLevel::Warning,
+                            ));
+                            termination.escalated = Some(now);
+                        }
+                        Err(err) => {
+                            toasts.push((err, ToastLevel::Error));
+                            continue;
+                        }
+                    }
+                }
+                None => {
+                    let left = self.terminate_grace - now.duration_since(termination.started);
+                    toasts.push((
+                        format!(
+                            "Waiting for {} to exit; SIGKILL in {}s",
+                            label,
+                            left.as_secs_f32().ceil()
+                        ),
+                        ToastLevel::Info,
+                    ));
+                }
+                // SIGKILL can't be ignored, but a process stuck in the kernel
+                // won't die until it leaves
+                Some(escalated) if now.duration_since(escalated) >= self.terminate_grace => {
+                    toasts.push((
+                        format!("{} is still running after SIGKILL", label),
+                        ToastLevel::Error,
+                    ));
+                    continue;
+                }
+                Some(_) => {}
+            }
+            self.terminating.push(termination);
+        }
+
+        // The most severe message wins when several processes are watched
+        if let Some((message, level)) = toasts.into_iter().max_by_key(|(_, level)| *level as u8) {
+            self.show_toast(message, level);
+        }
+    }
+
+    /// Kill a process (Windows has no SIGTERM)
+    #[cfg(not(target_family = "unix"))]
+    fn kill_process(&mut self, pid: Pid) -> Result<String, String> {
+        match with_process(pid, |process| process.kill()) {
+            Some(true) => Ok(format!("Process {} killed successfully", pid)),
+            Some(false) => Err(format!("Failed to kill process {}", pid)),
+            None => Err(format!("Process {} not found", pid)),
+        }
+    }
+
+    /// Send any signal to a process
+    fn send_signal(&mut self, pid: Pid, signal: Signal) -> Result<String, String> {
+        let name = signal_name(signal);
+        match with_process(pid, |process| process.kill_with(signal)) {
+            Some(Some(true)) => Ok(format!("Sent {} to process {}", name, pid)),
+            Some(Some(false)) => Err(format!("Failed to send {} to process {}", name, pid)),
+            Some(None) => Err(format!("{} is not supported on this platform", name)),
+            None => Err(format!("Process {} not found", pid)),
         }
     }
 
     /// Suspend a process (Unix only)
     #[cfg(target_family = "unix")]
     fn suspend_process(&mut self, pid: Pid) -> Result<String, String> {
-        use sysinfo::Signal;
-
-        if let Some(process) = self.system.process(pid) {
-            if process.kill_with(Signal::Stop).is_some() {
-                Ok(format!("Process {} suspended", pid))
-            } else {
-                Err(format!("Failed to suspend process {}", pid))
-            }
-        } else {
-            Err(format!("Process {} not found", pid))
+        match with_process(pid, |process| process.kill_with(Signal::Stop)) {
+            Some(Some(true)) => Ok(format!("Process {} suspended", pid)),
+            Some(_) => Err(format!("Failed to suspend process {}", pid)),
+            None => Err(format!("Process {} not found", pid)),
         }
     }
 
@@ -482,16 +1482,10 @@ impl App {
     /// Resume a process (Unix only)
     #[cfg(target_family = "unix")]
     fn resume_process(&mut self, pid: Pid) -> Result<String, String> {
-        use sysinfo::Signal;
-
-        if let Some(process) = self.system.process(pid) {
-            if process.kill_with(Signal::Continue).is_some() {
-                Ok(format!("Process {} resumed", pid))
-            } else {
-                Err(format!("Failed to resume process {}", pid))
-            }
-        } else {
-            Err(format!("Process {} not found", pid))
+        match with_process(pid, |process| process.kill_with(Signal::Continue)) {
+            Some(Some(true)) => Ok(format!("Process {} resumed", pid)),
+            Some(_) => Err(format!("Failed to resume process {}", pid)),
+            None => Err(format!("Process {} not found", pid)),
         }
     }
 
@@ -505,4 +1499,334 @@ impl App {
     pub fn quit(&mut self) {
         self.should_quit = true;
     }
+
+    /// Write the current snapshot to a timestamped file
+    pub fn export_snapshot(&self) -> Result<String, String> {
+        let format = self.config.export.format;
+        let name = export::file_name(format);
+        let path = match self.config.export.directory {
+            Some(ref dir) => dir.join(name),
+            None => name,
+        };
+
+        export::write_file(&path, format, &self.snapshot)
+            .map(|()| format!("Snapshot exported to {}", path.display()))
+            .map_err(|err| format!("{:#}", err))
+    }
+
+    /// Whether runtime changes should be saved when quitting
+    pub fn save_on_exit(&self) -> bool {
+        self.config.save_on_exit
+    }
+
+    /// Save on top of `config` rather than the config the app started from,
+    /// so command-line overrides aren't written back to the file
+    pub fn set_saved_config(&mut self, config: Config) {
+        self.saved_config = config;
+    }
+
+    /// The saved config with settings changed since startup applied
+    pub fn current_config(&self) -> Config {
+        let startup = &self.config;
+        let mut config = self.saved_config.clone();
+
+        if self.refresh_rate_ms != startup.refresh_rate_ms {
+            config.refresh_rate_ms = self.refresh_rate_ms;
+        }
+        if self.panels != startup.panels {
+            config.panels = self.panels.clone();
+        }
+        let startup_descending = startup
+            .processes
+            .sort_descending
+            .unwrap_or_else(|| startup.processes.sort.default_descending());
+        if self.sort_column != startup.processes.sort || self.sort_descending != startup_descending {
+            config.processes.sort = self.sort_column;
+            config.processes.sort_descending = Some(self.sort_descending);
+        }
+        if self.columns != startup.processes.columns {
+            config.processes.columns = self.columns.clone();
+        }
+        if self.tree_view != startup.processes.tree_view {
+            config.processes.tree_view = self.tree_view;
+        }
+        config
+    }
+
+    /// Write runtime changes back to the config file
+    pub fn save_config(&self) -> Result<String, String> {
+        let Some(ref path) = self.config_path else {
+            return Err("No config directory found".to_string());
+        };
+
+        self.current_config()
+            .save(path)
+            .map(|()| format!("Config saved to {}", path.display()))
+            .map_err(|err| format!("{:#}", err))
+    }
+}
+
+/// Append a sample to a history buffer, dropping the oldest beyond `size`
+pub fn push_history<T>(history: &mut Vec<T>, value: T, size: usize) {
+    history.push(value);
+    if history.len() > size {
+        history.drain(..history.len() - size);
+    }
+}
+
+/// Look up a live process by PID and run `f` against it.
+///
+/// Snapshots are detached from sysinfo, so actions refresh just the target
+/// process instead of going through the collector.
+fn with_process<T>(pid: Pid, f: impl FnOnce(&Process) -> T) -> Option<T> {
+    let mut system = System::new();
+    system.refresh_processes_specifics(ProcessesToUpdate::Some(&[pid]), ProcessRefreshKind::new());
+    system.process(pid).map(f)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// App started with `--refresh 2000 --history 500 --panels none --sort pid`
+    fn app_with_overrides() -> App {
+        let saved = Config::default();
+        let mut config = saved.clone();
+        config.refresh_rate_ms = 2000;
+        config.history_size = 500;
+        config.panels = Vec::new();
+        config.processes.sort = Column::Pid;
+
+        let mut app = App::new(Arc::new(Snapshot::default()), config, None);
+        app.set_saved_config(saved);
+        app
+    }
+
+    #[test]
+    fn saving_leaves_command_line_overrides_out() {
+        let app = app_with_overrides();
+        let config = app.current_config();
+        let default = Config::default();
+
+        assert_eq!(config.refresh_rate_ms, default.refresh_rate_ms);
+        assert_eq!(config.history_size, default.history_size);
+        assert_eq!(config.panels, default.panels);
+        assert_eq!(config.processes.sort, default.processes.sort);
+        assert_eq!(config.processes.sort_descending, None);
+    }
+
+    #[test]
+    fn saving_keeps_changes_made_at_runtime() {
+        let mut app = app_with_overrides();
+        app.refresh_rate_ms = 1000;
+        app.panels = vec![Panel::Memory];
+        app.sort_column = Column::Memory;
+        app.sort_descending = false;
+        app.tree_view = true;
+        let config = app.current_config();
+
+        assert_eq!(config.refresh_rate_ms, 1000);
+        assert_eq!(config.panels, [Panel::Memory]);
+        assert_eq!(config.processes.sort, Column::Memory);
+        assert_eq!(config.processes.sort_descending, Some(false));
+        assert!(config.processes.tree_view);
+        // Still untouched
+        assert_eq!(config.history_size, Config::default().history_size);
+    }
+
+    /// Replay of 40 one-second frames with CPU at 80% for frames 10-19,
+    /// watched by a "cpu > 50% for 3s" rule
+    fn replay_app() -> App {
+        let frames: Vec<Arc<Snapshot>> = (0..40)
+            .map(|i| {
+                Arc::new(Snapshot {
+                    timestamp: 1_000_000 + i * 1000,
+                    global_cpu_usage: if (10..20).contains(&i) { 80.0 } else { 10.0 },
+                    ..Default::default()
+                })
+            })
+            .collect();
+        let config = Config {
+            alerts: vec![toml::from_str("metric = \"cpu\"\nabove = 50\nfor_secs = 3").unwrap()],
+            ..Default::default()
+        };
+
+        let mut app = App::new(Arc::clone(&frames[0]), config, None);
+        app.update(Arc::clone(&frames[0]));
+        app.replay = Some(Replay::from_frames(frames));
+        app
+    }
+
+    fn alert_log(app: &App) -> Vec<(u64, bool)> {
+        app.alerts
+            .log
+            .iter()
+            .map(|event| ((event.timestamp - 1_000_000) / 1000, event.fired))
+            .collect()
+    }
+
+    #[test]
+    fn seeking_a_replay_does_not_duplicate_alerts() {
+        let mut app = replay_app();
+        for _ in 0..4 {
+            app.seek_replay(true);
+        }
+        assert_eq!(alert_log(&app), [(13, true), (20, false)]);
+
+        // Seeking again over the same frames logs them once
+        app.seek_replay(true);
+        assert_eq!(alert_log(&app), [(13, true), (20, false)]);
+    }
+
+    #[test]
+    fn seeking_a_replay_back_re_evaluates_alerts() {
+        let mut app = replay_app();
+        for _ in 0..4 {
+            app.seek_replay(true);
+        }
+
+        // Back to frame 19, where the rule is still firing
+        app.seek_replay(false);
+        app.seek_replay(false);
+        assert_eq!(alert_log(&app), [(13, true)]);
+        assert_eq!(app.alerts.firing().len(), 1);
+
+        // Back before the breach; nothing has fired yet
+        app.seek_replay(false);
+        assert!(alert_log(&app).is_empty());
+        assert!(app.alerts.firing().is_empty());
+
+        app.seek_replay(true);
+        app.seek_replay(true);
+        assert_eq!(alert_log(&app), [(13, true), (20, false)]);
+    }
+
+    fn app_with_process() -> App {
+        let snapshot = Snapshot {
+            processes: vec![ProcessInfo {
+                nice: Some(0),
+                ..ProcessInfo::fixture(4242, None, "worker")
+            }],
+            ..Default::default()
+        };
+        let mut app = App::new(Arc::new(snapshot), Config::default(), None);
+        app.selected_process = Some(0);
+        app
+    }
+
+    #[test]
+    fn priority_changes_ask_for_confirmation() {
+        let mut app = app_with_process();
+        app.priority_input = Some(PriorityInput {
+            field: PriorityField::Nice,
+            text: "5".to_string(),
+            class: IoClass::BestEffort,
+        });
+        app.apply_priority();
+
+        assert!(app.priority_input.is_none());
+        let pending = app.pending_action.as_ref().unwrap();
+        assert_eq!(pending.action, ProcessAction::Renice(5));
+        assert_eq!(pending.targets, [(Pid::from_u32(4242), "worker".to_string())]);
+
+        app.priority_input = Some(PriorityInput {
+            field: PriorityField::Io,
+            text: "3".to_string(),
+            class: IoClass::Idle,
+        });
+        app.apply_priority();
+        let pending = app.pending_action.as_ref().unwrap();
+        assert_eq!(
+            pending.action,
+            ProcessAction::SetIoPriority(IoPriority { class: IoClass::Idle, level: 3 })
+        );
+    }
+
+    #[test]
+    fn out_of_range_priorities_are_rejected() {
+        let mut app = app_with_process();
+        app.priority_input = Some(PriorityInput {
+            field: PriorityField::Nice,
+            text: "25".to_string(),
+            class: IoClass::BestEffort,
+        });
+        app.apply_priority();
+
+        assert!(app.pending_action.is_none());
+        // The overlay stays open to fix the value
+        assert!(app.priority_input.is_some());
+    }
+
+    /// This test process and init, busiest first, with nothing read yet
+    fn app_with_live_processes() -> App {
+        let snapshot = Snapshot {
+            processes: vec![
+                ProcessInfo {
+                    cpu_usage: 50.0,
+                    ..ProcessInfo::fixture(std::process::id(), None, "self")
+                },
+                ProcessInfo {
+                    cpu_usage: 1.0,
+                    ..ProcessInfo::fixture(1, None, "init")
+                },
+            ],
+            ..Default::default()
+        };
+        App::new(Arc::new(snapshot), Config::default(), None)
+    }
+
+    fn nice_values(app: &App) -> Vec<Option<i64>> {
+        app.snapshot.processes.iter().map(|process| process.nice).collect()
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn reads_priorities_of_shown_rows_only() {
+        let mut app = app_with_live_processes();
+        app.read_priorities();
+        assert_eq!(nice_values(&app), [None, None]);
+
+        app.process_rows.set(1);
+        app.read_priorities();
+        assert!(app.snapshot.processes[0].nice.is_some());
+        assert!(app.snapshot.processes[0].priority.is_some());
+        assert_eq!(app.snapshot.processes[1].nice, None);
+
+        // The selected row is read even when scrolled out of view
+        app.process_rows.set(0);
+        app.selected_process = Some(1);
+        app.read_priorities();
+        assert!(app.snapshot.processes[1].nice.is_some());
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn reads_every_priority_when_sorting_by_one() {
+        let mut app = app_with_live_processes();
+        app.sort_by(Column::Nice);
+        app.read_priorities();
+        assert!(nice_values(&app).iter().all(Option::is_some));
+    }
+
+    #[test]
+    fn keeps_recorded_priorities_during_replay() {
+        let mut app = app_with_live_processes();
+        app.replay = Some(Replay::from_frames(vec![Arc::clone(&app.snapshot)]));
+        app.process_rows.set(2);
+        app.read_priorities();
+        assert_eq!(nice_values(&app), [None, None]);
+    }
+
+    #[test]
+    fn scrolls_just_enough_to_show_the_selection() {
+        let mut app = app_with_live_processes();
+        assert_eq!(app.process_scroll(10), 0);
+        app.selected_process = Some(14);
+        assert_eq!(app.process_scroll(10), 5);
+        app.scroll_offset = 8;
+        app.selected_process = Some(3);
+        assert_eq!(app.process_scroll(10), 3);
+        app.selected_process = Some(12);
+        assert_eq!(app.process_scroll(10), 8);
+    }
 }
@@ -0,0 +1,219 @@
+use crate::app::App;
+use crate::column::Column;
+use crate::snapshot::Snapshot;
+use crate::ui::{format_bytes, processes};
+use anyhow::Result;
+use chrono::Local;
+use std::{
+    io::{self, IsTerminal, Write},
+    sync::Arc,
+};
+use tokio::{runtime::Runtime, sync::watch};
+
+/// Table width when stdout isn't a terminal (CI logs, pipes)
+const DEFAULT_WIDTH: usize = 160;
+
+/// Print `iterations` snapshots (0 for no limit) as plain text, one per
+/// refresh interval.
+///
+/// Each printed sample waits for a fresh snapshot from the collector, so CPU
+/// usage covers a full interval rather than the instant the tool started.
+/// Processes go through the same filters, sort and columns as the UI.
+pub fn run(
+    runtime: &Runtime,
+    app: &mut App,
+    snapshots: &mut watch::Receiver<Arc<Snapshot>>,
+    iterations: u64,
+    top: usize,
+) -> Result<()> {
+    let width = table_width(io::stdout().is_terminal());
+    let mut out = io::stdout().lock();
+    // Only the printed rows need their nice values read
+    app.process_rows.set(if top == 0 { usize::MAX } else { top });
+
+    let mut sample = 0;
+    while iterations == 0 || sample < iterations {
+        sample += 1;
+        runtime.block_on(snapshots.changed())?;
+        let snapshot = snapshots.borrow_and_update().clone();
+        app.update(snapshot);
+        app.read_priorities();
+
+        match print_sample(&mut out, app, sample, iterations, top, width) {
+            // Output piped into `head` and friends; stop quietly
+            Err(err) if err.kind() == io::ErrorKind::BrokenPipe => return Ok(()),
+            result => result?,
+        }
+    }
+
+    Ok(())
+}
+
+/// Width to lay the table out in. crossterm asks the controlling terminal,
+/// which is still there when stdout is piped, so check stdout first.
+fn table_width(stdout_is_terminal: bool) -> usize {
+    if !stdout_is_terminal {
+        return DEFAULT_WIDTH;
+    }
+    crossterm::terminal::size()
+        .map(|(cols, _)| cols as usize)
+        .unwrap_or(DEFAULT_WIDTH)
+}
+
+/// Write the summary lines and process table for one snapshot
+fn print_sample(
+    out: &mut impl Write,
+    app: &App,
+    sample: u64,
+    iterations: u64,
+    top: usize,
+    width: usize,
+) -> io::Result<()> {
+    let snapshot = &app.snapshot;
+    let memory = &snapshot.memory;
+    let rows = app.get_process_rows();
+
+    // Blank line between samples
+    if sample > 1 {
+        writeln!(out)?;
+    }
+    let progress = if iterations == 0 {
+        sample.to_string()
+    } else {
+        format!("{}/{}", sample, iterations)
+    };
+    writeln!(
+        out,
+        "rtui - {}  sample {}",
+        Local::now().format("%Y-%m-%d %H:%M:%S"),
+        progress
+    )?;
+    writeln!(
+        out,
+        "CPU:  {:5.1}%  ({} cores)",
+        snapshot.global_cpu_usage,
+        snapshot.cpus.len()
+    )?;
+    writeln!(
+        out,
+        "Mem:  {:5.1}%  ({} / {})  Swap: {:.1}%  ({} / {})",
+        snapshot.memory_usage(),
+        format_bytes(memory.used),
+        format_bytes(memory.total),
+        snapshot.swap_usage(),
+        format_bytes(memory.swap_used),
+        format_bytes(memory.swap_total)
+    )?;
+    writeln!(out, "Tasks: {} total, {} shown", snapshot.processes.len(), rows.len())?;
+    writeln!(out)?;
+
+    write_table(out, app, top, width)?;
+
+    out.flush()
+}
+
+/// Write the process table header and the first `top` rows (0 for all)
+fn write_table(out: &mut impl Write, app: &App, top: usize, width: usize) -> io::Result<()> {
+    let rows = app.get_process_rows();
+    let columns = processes::layout_columns(&app.visible_columns(), width);
+    let line = |cells: Vec<String>| cells.join(" ").trim_end().to_string();
+
+    let header = columns
+        .iter()
+        .map(|&(column, width)| processes::pad(column, &header_text(column), width))
+        .collect();
+    writeln!(out, "{}", line(header))?;
+
+    let count = if top == 0 { rows.len() } else { top };
+    for row in rows.iter().take(count) {
+        let cells = columns
+            .iter()
+            .map(|&(column, width)| processes::pad(column, &processes::cell_text(column, row), width))
+            .collect();
+        writeln!(out, "{}", line(cells))?;
+    }
+
+    Ok(())
+}
+
+/// Column header in plain ASCII for logs and pipes: no sort arrow, and the
+/// subtree columns spelled out instead of using `Σ`
+fn header_text(column: Column) -> String {
+    column.header().replace('Σ', "Sub ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use crate::snapshot::ProcessInfo;
+
+    /// App showing PID, name and CPU, sorted by CPU, over `count` processes
+    fn app(count: u32) -> App {
+        let mut config = Config::default();
+        config.processes.columns = vec![Column::Pid, Column::Name, Column::Cpu];
+        config.processes.sort = Column::Cpu;
+        let snapshot = Snapshot {
+            processes: (1..=count)
+                .map(|pid| ProcessInfo {
+                    cpu_usage: pid as f32,
+                    ..ProcessInfo::fixture(pid, None, &format!("proc{}", pid))
+                })
+                .collect(),
+            ..Default::default()
+        };
+        App::new(Arc::new(snapshot), config, None)
+    }
+
+    fn table(app: &App, top: usize, width: usize) -> Vec<String> {
+        let mut out = Vec::new();
+        write_table(&mut out, app, top, width).unwrap();
+        String::from_utf8(out).unwrap().lines().map(str::to_string).collect()
+    }
+
+    #[test]
+    fn lines_columns_up_under_an_ascii_header() {
+        let lines = table(&app(3), 0, DEFAULT_WIDTH);
+        let name_width = DEFAULT_WIDTH - 7 - 7 - 2;
+
+        assert_eq!(
+            lines[0],
+            format!("{:<7} {:<name_width$} {:>7}", "PID", "Process Name", "CPU")
+        );
+        assert_eq!(lines[1], format!("{:<7} {:<name_width$} {:>7}", 3, "proc3", "3.0%"));
+        assert_eq!(lines[3], format!("{:<7} {:<name_width$} {:>7}", 1, "proc1", "1.0%"));
+        assert!(lines.iter().all(|line| line.is_ascii()));
+    }
+
+    #[test]
+    fn spells_out_subtree_headers() {
+        let mut app = app(1);
+        app.tree_view = true;
+        let header = &table(&app, 0, DEFAULT_WIDTH)[0];
+
+        assert!(header.contains("Sub CPU"));
+        assert!(header.contains("Sub Memory"));
+        assert!(!header.contains('Σ'));
+    }
+
+    #[test]
+    fn lists_at_most_top_processes() {
+        let app = app(5);
+        assert_eq!(table(&app, 2, DEFAULT_WIDTH).len(), 3);
+        assert_eq!(table(&app, 0, DEFAULT_WIDTH).len(), 6);
+        assert_eq!(table(&app, 10, DEFAULT_WIDTH).len(), 6);
+    }
+
+    #[test]
+    fn drops_columns_that_do_not_fit() {
+        // PID and name fit in 24 columns; CPU doesn't
+        let lines = table(&app(1), 0, 24);
+        assert_eq!(lines[0], format!("{:<7} {}", "PID", "Process Name"));
+        assert!(lines.iter().all(|line| line.len() <= 24));
+    }
+
+    #[test]
+    fn uses_the_default_width_when_stdout_is_not_a_terminal() {
+        assert_eq!(table_width(false), DEFAULT_WIDTH);
+    }
+}
@@ -0,0 +1,146 @@
+use crate::app::{App, Panel};
+use crate::column::Column;
+use crate::config::Config;
+use crate::export::Format;
+use clap::{ArgGroup, Parser};
+use serde::de::{value::StrDeserializer, Deserialize, IntoDeserializer};
+#[cfg(feature = "exporter")]
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use sysinfo::Pid;
+
+/// Command-line options; anything given here overrides the config file
+#[derive(Debug, Parser)]
+#[command(name = "rtui", version, about)]
+#[command(group(ArgGroup::new("headless").args(["batch", "export"])))]
+pub struct Cli {
+    /// Refresh interval in milliseconds
+    #[arg(short, long, value_name = "MS", value_parser = clap::value_parser!(u64).range(100..=60_000))]
+    pub refresh: Option<u64>,
+
+    /// Number of samples kept for history graphs
+    #[arg(long, value_name = "SAMPLES", value_parser = clap::value_parser!(u64).range(10..=10_000))]
+    pub history: Option<u64>,
+
+    /// Column to sort processes by (e.g. cpu, memory, pid, run_time)
+    #[arg(short, long, value_name = "COLUMN", value_parser = parse_name::<Column>)]
+    pub sort: Option<Column>,
+
+    /// Sort smallest first instead of the column's default direction
+    #[arg(long)]
+    pub ascending: bool,
+
+    /// Initial process search filter (name or PID)
+    #[arg(short, long, value_name = "QUERY")]
+    pub filter: Option<String>,
+
+    /// Only show these PIDs (comma-separated)
+    #[arg(short, long, value_name = "PID", value_delimiter = ',')]
+    pub pid: Vec<u32>,
+
+    /// Only show processes owned by this user
+    #[arg(short, long, value_name = "USER")]
+    pub user: Option<String>,
+
+    /// Config file to read and save instead of the default location
+    #[arg(short, long, value_name = "PATH")]
+    pub config: Option<PathBuf>,
+
+    /// Panels to show below the CPU overview, in order (comma-separated, or "none")
+    #[arg(long, value_name = "PANELS", value_delimiter = ',', value_parser = parse_panel)]
+    pub panels: Option<Vec<Option<Panel>>>,
+
+    /// Print snapshots as plain text instead of starting the UI
+    #[arg(short, long)]
+    pub batch: bool,
+
+    /// Write snapshots to a file ("-" for stdout) instead of starting the UI
+    #[arg(short, long, value_name = "PATH")]
+    pub export: Option<PathBuf>,
+
+    /// Export format: json, ndjson or csv (default: from the file extension)
+    #[arg(long, value_name = "FORMAT", value_parser = parse_name::<Format>, requires = "export")]
+    pub format: Option<Format>,
+
+    /// Append every snapshot to a recording file for `--replay`
+    #[arg(long, value_name = "PATH", conflicts_with = "export")]
+    pub record: Option<PathBuf>,
+
+    /// Play back a recording (or NDJSON export) instead of the live system
+    #[arg(long, value_name = "PATH", conflicts_with_all = ["headless", "record"])]
+    pub replay: Option<PathBuf>,
+
+    /// Serve Prometheus/OpenMetrics on http://ADDR/metrics alongside the UI
+    #[cfg(feature = "exporter")]
+    #[arg(long, value_name = "ADDR", conflicts_with = "replay")]
+    pub listen: Option<SocketAddr>,
+
+    /// Only serve /metrics, without the UI (on --listen or 127.0.0.1:9184)
+    #[cfg(feature = "exporter")]
+    #[arg(long, conflicts_with_all = ["headless", "replay"])]
+    pub exporter: bool,
+
+    /// Processes exported as metrics: the top N by CPU and the top N by memory
+    #[cfg(feature = "exporter")]
+    #[arg(long, value_name = "COUNT", default_value_t = 10)]
+    pub metrics_top: usize,
+
+    /// Number of snapshots to print or export (0 to run until interrupted)
+    #[arg(short = 'n', long, value_name = "COUNT", default_value_t = 1, requires = "headless")]
+    pub iterations: u64,
+
+    /// Processes listed per snapshot in batch mode (0 for all)
+    #[arg(long, value_name = "COUNT", default_value_t = 20, requires = "batch")]
+    pub top: usize,
+}
+
+impl Cli {
+    /// Where the config lives: `--config`, or the XDG default
+    pub fn config_path(&self) -> Option<PathBuf> {
+        self.config.clone().or_else(Config::default_path)
+    }
+
+    /// Override config values with the ones given on the command line
+    pub fn apply(&self, config: &mut Config) -> Result<(), String> {
+        if let Some(refresh) = self.refresh {
+            config.refresh_rate_ms = refresh;
+        }
+        if let Some(history) = self.history {
+            config.history_size = history as usize;
+        }
+        if let Some(sort) = self.sort {
+            config.processes.sort = sort;
+            config.processes.sort_descending = None;
+        }
+        if self.ascending {
+            config.processes.sort_descending = Some(false);
+        }
+        if let Some(ref panels) = self.panels {
+            config.panels = panels.iter().flatten().copied().collect();
+        }
+
+        config.validate()
+    }
+
+    /// Set the process filters given on the command line
+    pub fn apply_filters(&self, app: &mut App) {
+        app.search_query = self.filter.clone().unwrap_or_default();
+        app.pid_filter = self.pid.iter().copied().map(Pid::from_u32).collect();
+        app.user_filter = self.user.clone();
+    }
+}
+
+/// Parse a value by its config file name, so the CLI and config agree
+fn parse_name<T: for<'de> Deserialize<'de>>(name: &str) -> Result<T, String> {
+    let deserializer: StrDeserializer<serde::de::value::Error> = name.into_deserializer();
+    T::deserialize(deserializer).map_err(|err| err.to_string())
+}
+
+/// Panel name, or "none" to hide them all
+fn parse_panel(name: &str) -> Result<Option<Panel>, String> {
+    if name == "none" {
+        Ok(None)
+    } else {
+        parse_name(name).map(Some)
+    }
+}
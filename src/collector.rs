@@ -0,0 +1,62 @@
+use crate::snapshot::Snapshot;
+use crate::source::MetricSource;
+use std::{sync::Arc, time::Duration};
+use tokio::{runtime::Runtime, sync::watch};
+
+/// Spawn the background collector task.
+///
+/// The first snapshot is taken synchronously so the UI has data to draw
+/// immediately. After that the task samples every `refresh_rate` and
+/// publishes the latest snapshot; `/proc` scans run on the blocking pool so
+/// they never stall input handling or drawing.
+pub fn spawn(
+    runtime: &Runtime,
+    mut source: Box<dyn MetricSource>,
+    refresh_rate: watch::Receiver<Duration>,
+) -> watch::Receiver<Arc<Snapshot>> {
+    let (tx, rx) = watch::channel(Arc::new(source.snapshot()));
+
+    runtime.spawn(run(source, tx, refresh_rate));
+
+    rx
+}
+
+/// Collector loop: sleep, sample, publish
+async fn run(
+    mut source: Box<dyn MetricSource>,
+    tx: watch::Sender<Arc<Snapshot>>,
+    mut refresh_rate: watch::Receiver<Duration>,
+) {
+    loop {
+        let delay = *refresh_rate.borrow_and_update();
+
+        tokio::select! {
+            _ = tokio::time::sleep(delay) => {}
+            changed = refresh_rate.changed() => {
+                // Restart the wait with the new rate, or stop if the UI is gone
+                if changed.is_err() {
+                    break;
+                }
+                continue;
+            }
+        }
+
+        let sampled = tokio::task::spawn_blocking(move || {
+            let snapshot = source.snapshot();
+            (source, snapshot)
+        })
+        .await;
+
+        let snapshot = match sampled {
+            Ok((returned, snapshot)) => {
+                source = returned;
+                snapshot
+            }
+            Err(_) => break,
+        };
+
+        if tx.send(Arc::new(snapshot)).is_err() {
+            break;
+        }
+    }
+}
@@ -0,0 +1,183 @@
+use crate::snapshot::ProcessInfo;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+
+/// A column of the process table
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Column {
+    Pid,
+    User,
+    Name,
+    State,
+    Threads,
+    Nice,
+    Priority,
+    Cpu,
+    Memory,
+    VirtualMemory,
+    StartTime,
+    RunTime,
+    DiskRead,
+    DiskWrite,
+    GpuUsage,
+    GpuMemory,
+    Command,
+    /// CPU of the process and its descendants (tree view only)
+    SubtreeCpu,
+    /// Resident memory of the process and its descendants (tree view only)
+    SubtreeMemory,
+}
+
+impl Column {
+    /// Columns offered in the column editor, in their default order
+    pub const ALL: [Column; 17] = [
+        Column::Pid,
+        Column::User,
+        Column::Name,
+        Column::State,
+        Column::Threads,
+        Column::Nice,
+        Column::Priority,
+        Column::Cpu,
+        Column::Memory,
+        Column::VirtualMemory,
+        Column::StartTime,
+        Column::RunTime,
+        Column::DiskRead,
+        Column::DiskWrite,
+        Column::GpuUsage,
+        Column::GpuMemory,
+        Column::Command,
+    ];
+
+    /// Columns shown out of the box
+    pub const DEFAULT: [Column; 4] = [Column::Pid, Column::Name, Column::Cpu, Column::Memory];
+
+    /// Short header shown above the column
+    pub fn header(self) -> &'static str {
+        match self {
+            Column::Pid => "PID",
+            Column::User => "USER",
+            Column::Name => "Process Name",
+            Column::State => "STATE",
+            Column::Threads => "THR",
+            Column::Nice => "NI",
+            Column::Priority => "PRI",
+            Column::Cpu => "CPU",
+            Column::Memory => "Memory",
+            Column::VirtualMemory => "VIRT",
+            Column::StartTime => "START",
+            Column::RunTime => "TIME",
+            Column::DiskRead => "READ",
+            Column::DiskWrite => "WRITE",
+            Column::GpuUsage => "GPU",
+            Column::GpuMemory => "GMEM",
+            Column::Command => "Command",
+            Column::SubtreeCpu => "ΣCPU",
+            Column::SubtreeMemory => "ΣMemory",
+        }
+    }
+
+    /// Longer description used by the column editor
+    pub fn label(self) -> &'static str {
+        match self {
+            Column::Pid => "Process ID",
+            Column::User => "User",
+            Column::Name => "Process name",
+            Column::State => "State",
+            Column::Threads => "Threads",
+            Column::Nice => "Nice value",
+            Column::Priority => "Priority",
+            Column::Cpu => "CPU usage",
+            Column::Memory => "Resident memory (RSS)",
+            Column::VirtualMemory => "Virtual memory",
+            Column::StartTime => "Start time",
+            Column::RunTime => "Run time",
+            Column::DiskRead => "Disk read rate",
+            Column::DiskWrite => "Disk write rate",
+            Column::GpuUsage => "GPU usage",
+            Column::GpuMemory => "GPU memory",
+            Column::Command => "Full command line",
+            Column::SubtreeCpu => "Subtree CPU usage",
+            Column::SubtreeMemory => "Subtree memory",
+        }
+    }
+
+    /// Width in characters; the minimum for name and command, which grow
+    /// to fill spare space
+    pub fn width(self) -> usize {
+        match self {
+            Column::Pid => 7,
+            Column::User => 9,
+            Column::Name => 16,
+            Column::State => 8,
+            Column::Threads => 4,
+            Column::Nice => 4,
+            Column::Priority => 4,
+            Column::Cpu | Column::SubtreeCpu | Column::GpuUsage => 7,
+            Column::Memory | Column::VirtualMemory | Column::SubtreeMemory | Column::GpuMemory => 10,
+            Column::StartTime => 8,
+            Column::RunTime => 9,
+            Column::DiskRead | Column::DiskWrite => 11,
+            Column::Command => 20,
+        }
+    }
+
+    /// Whether values are right-aligned (numbers) or left-aligned (text)
+    pub fn is_numeric(self) -> bool {
+        !matches!(
+            self,
+            Column::Pid | Column::User | Column::Name | Column::State | Column::Command
+        )
+    }
+
+    /// Whether the process list can be sorted by this column
+    pub fn is_sortable(self) -> bool {
+        !matches!(self, Column::SubtreeCpu | Column::SubtreeMemory)
+    }
+
+    /// Sort direction picked when switching to this column: biggest first for
+    /// resource usage, natural order for identifiers and text
+    pub fn default_descending(self) -> bool {
+        matches!(
+            self,
+            Column::Threads
+                | Column::Cpu
+                | Column::Memory
+                | Column::VirtualMemory
+                | Column::RunTime
+                | Column::DiskRead
+                | Column::DiskWrite
+                | Column::GpuUsage
+                | Column::GpuMemory
+        )
+    }
+
+    /// Compare two processes by this column in ascending order
+    pub fn compare(self, a: &ProcessInfo, b: &ProcessInfo) -> Ordering {
+        match self {
+            Column::Pid => a.pid.as_u32().cmp(&b.pid.as_u32()),
+            Column::User => a.user.cmp(&b.user),
+            Column::Name => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
+            Column::State => a.status.cmp(&b.status),
+            Column::Threads => a.threads.cmp(&b.threads),
+            Column::Nice => a.nice.cmp(&b.nice),
+            Column::Priority => a.priority.cmp(&b.priority),
+            Column::Cpu | Column::SubtreeCpu => a.cpu_usage.total_cmp(&b.cpu_usage),
+            Column::Memory | Column::SubtreeMemory => a.memory.cmp(&b.memory),
+            Column::VirtualMemory => a.virtual_memory.cmp(&b.virtual_memory),
+            Column::StartTime => a.start_time.cmp(&b.start_time),
+            Column::RunTime => a.run_time.cmp(&b.run_time),
+            Column::DiskRead => a.disk_read_rate.total_cmp(&b.disk_read_rate),
+            Column::DiskWrite => a.disk_write_rate.total_cmp(&b.disk_write_rate),
+            // Processes without a GPU context sort below idle ones
+            Column::GpuUsage => match (a.gpu_usage, b.gpu_usage) {
+                (Some(a), Some(b)) => a.total_cmp(&b),
+                (a, b) => a.is_some().cmp(&b.is_some()),
+            },
+            Column::GpuMemory => a.gpu_memory.cmp(&b.gpu_memory),
+            Column::Command => a.command.cmp(&b.command),
+        }
+    }
+}
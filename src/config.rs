@@ -0,0 +1,313 @@
+use crate::alert::AlertRule;
+use crate::app::Panel;
+use crate::column::Column;
+use crate::export::Format;
+use crate::keymap::{KeyBindings, Keymap};
+use crate::theme::Theme;
+use anyhow::{anyhow, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// Settings read from `config.toml`; every field is optional in the file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// Refresh rate in milliseconds
+    pub refresh_rate_ms: u64,
+    /// Samples kept for history graphs
+    pub history_size: usize,
+    /// Write runtime changes (refresh rate, sort, columns...) back on quit
+    pub save_on_exit: bool,
+    /// Panels shown below the CPU overview, in order
+    pub panels: Vec<Panel>,
+    pub processes: ProcessConfig,
+    pub export: ExportConfig,
+    pub theme: Theme,
+    /// Overrides for normal-mode key bindings
+    pub keys: KeyBindings,
+    /// Alert rules, checked on every refresh
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub alerts: Vec<AlertRule>,
+}
+
+/// Process list settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ProcessConfig {
+    /// Column to sort by
+    pub sort: Column,
+    /// Largest first; defaults to the natural direction of the column
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sort_descending: Option<bool>,
+    /// Visible columns, in order
+    pub columns: Vec<Column>,
+    /// Start in tree view
+    pub tree_view: bool,
+    /// Seconds a terminated process gets to exit before it is sent SIGKILL;
+    /// 0 never escalates
+    pub terminate_grace_secs: u64,
+}
+
+/// Snapshot export settings
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ExportConfig {
+    /// Format written by the export key
+    pub format: Format,
+    /// Directory exports are written to; the working directory when unset
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub directory: Option<PathBuf>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            refresh_rate_ms: 500,
+            history_size: 60,
+            save_on_exit: false,
+            panels: Panel::DEFAULT.to_vec(),
+            processes: ProcessConfig::default(),
+            export: ExportConfig::default(),
+            theme: Theme::default(),
+            keys: KeyBindings::new(),
+            alerts: Vec::new(),
+        }
+    }
+}
+
+impl Default for ProcessConfig {
+    fn default() -> Self {
+        Self {
+            sort: Column::Cpu,
+            sort_descending: None,
+            columns: Column::DEFAULT.to_vec(),
+            tree_view: false,
+            terminate_grace_secs: 5,
+        }
+    }
+}
+
+impl Config {
+    /// `$XDG_CONFIG_HOME/resmon-tui/config.toml` (or the platform equivalent)
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::config_dir().map(|dir| dir.join("resmon-tui").join("config.toml"))
+    }
+
+    /// Load and validate a config file; a missing file gives the defaults
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = match fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(err) => return Err(err).with_context(|| format!("failed to read {}", path.display())),
+        };
+
+        let config: Self = toml::from_str(&content)
+            .with_context(|| format!("failed to parse {}", path.display()))?;
+        config
+            .validate()
+            .map_err(|err| anyhow!("invalid config {}: {}", path.display(), err))?;
+
+        Ok(config)
+    }
+
+    /// Write the config, creating its directory if needed
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).with_context(|| format!("failed to create {}", dir.display()))?;
+        }
+        let content = toml::to_string_pretty(self).context("failed to serialize config")?;
+        fs::write(path, content).with_context(|| format!("failed to write {}", path.display()))
+    }
+
+    /// Check values that parse fine but make no sense
+    pub fn validate(&self) -> Result<(), String> {
+        if !(100..=60_000).contains(&self.refresh_rate_ms) {
+            return Err(format!(
+                "refresh_rate_ms must be between 100 and 60000 (got {})",
+                self.refresh_rate_ms
+            ));
+        }
+        if !(10..=10_000).contains(&self.history_size) {
+            return Err(format!(
+                "history_size must be between 10 and 10000 (got {})",
+                self.history_size
+            ));
+        }
+
+        if let Some(panel) = first_duplicate(&self.panels) {
+            return Err(format!("panels: \"{}\" is listed twice", name_of(panel)));
+        }
+
+        let columns = &self.processes.columns;
+        if columns.is_empty() {
+            return Err("processes.columns must list at least one column".to_string());
+        }
+        if let Some(column) = first_duplicate(columns) {
+            return Err(format!("processes.columns: \"{}\" is listed twice", name_of(column)));
+        }
+        if let Some(column) = columns.iter().chain([&self.processes.sort]).find(|c| !Column::ALL.contains(c)) {
+            return Err(format!("\"{}\" is only available in tree view", name_of(column)));
+        }
+
+        for (i, rule) in self.alerts.iter().enumerate() {
+            rule.validate().map_err(|err| format!("alerts[{}]: {}", i, err))?;
+        }
+
+        self.theme.validate()?;
+        Keymap::new(&self.keys)?;
+
+        Ok(())
+    }
+}
+
+/// Name of an enum value as written in the config file
+pub fn name_of<T: Serialize + std::fmt::Debug>(value: &T) -> String {
+    toml::Value::try_from(value)
+        .ok()
+        .and_then(|value| value.as_str().map(str::to_string))
+        .unwrap_or_else(|| format!("{:?}", value))
+}
+
+fn first_duplicate<T: PartialEq>(items: &[T]) -> Option<&T> {
+    items
+        .iter()
+        .enumerate()
+        .find(|(i, item)| items[..*i].contains(item))
+        .map(|(_, item)| item)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::App;
+    use crate::keymap::Action;
+    use crate::snapshot::Snapshot;
+    use ratatui::style::Color;
+    use std::sync::Arc;
+    use tempfile::TempDir;
+
+    /// Parse and validate `content` the way `load` does
+    fn load_str(content: &str) -> Result<Config> {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("config.toml");
+        fs::write(&path, content).unwrap();
+        Config::load(&path)
+    }
+
+    fn error(content: &str) -> String {
+        format!("{:#}", load_str(content).unwrap_err())
+    }
+
+    #[test]
+    fn missing_file_gives_the_defaults() {
+        let dir = TempDir::new().unwrap();
+        let config = Config::load(&dir.path().join("config.toml")).unwrap();
+        assert_eq!(config.refresh_rate_ms, 500);
+        assert_eq!(config.panels, Panel::DEFAULT);
+    }
+
+    #[test]
+    fn saved_config_loads_back_unchanged() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("nested/config.toml");
+        let config = load_str(
+            r##"
+            refresh_rate_ms = 1000
+            panels = ["memory", "alerts"]
+
+            [processes]
+            sort = "memory"
+            sort_descending = false
+            columns = ["pid", "name", "virtual_memory"]
+
+            [export]
+            format = "csv"
+            directory = "/tmp/exports"
+
+            [theme]
+            usage_warning = 50
+            ok = "#00ff88"
+            critical = "light red"
+
+            [keys]
+            quit = ["x"]
+
+            [[alerts]]
+            metric = "memory"
+            above = 90
+            for_secs = 10
+            "##,
+        )
+        .unwrap();
+
+        config.save(&path).unwrap();
+        let loaded = Config::load(&path).unwrap();
+
+        assert_eq!(loaded.refresh_rate_ms, 1000);
+        assert_eq!(loaded.panels, [Panel::Memory, Panel::Alerts]);
+        assert_eq!(loaded.processes.columns, [Column::Pid, Column::Name, Column::VirtualMemory]);
+        assert_eq!(loaded.processes.sort_descending, Some(false));
+        assert_eq!(loaded.export.directory, Some(PathBuf::from("/tmp/exports")));
+        assert_eq!(loaded.theme.ok, Color::Rgb(0x00, 0xff, 0x88));
+        assert_eq!(loaded.theme.critical, Color::LightRed);
+        assert_eq!(loaded.keys[&Action::Quit], ["x"]);
+        assert_eq!(loaded.alerts[0].above, Some(90.0));
+        assert_eq!(
+            toml::to_string_pretty(&loaded).unwrap(),
+            toml::to_string_pretty(&config).unwrap()
+        );
+    }
+
+    #[test]
+    fn saving_from_the_app_leaves_command_line_overrides_out() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("config.toml");
+        let saved = load_str("history_size = 120\n[processes]\ntree_view = true\n").unwrap();
+
+        // As if started with `--refresh 2000 --sort pid`
+        let mut config = saved.clone();
+        config.refresh_rate_ms = 2000;
+        config.processes.sort = Column::Pid;
+        let mut app = App::new(Arc::new(Snapshot::default()), config, Some(path.clone()));
+        app.set_saved_config(saved);
+        app.save_config().unwrap();
+
+        let loaded = Config::load(&path).unwrap();
+        assert_eq!(loaded.refresh_rate_ms, Config::default().refresh_rate_ms);
+        assert_eq!(loaded.processes.sort, Column::Cpu);
+        assert_eq!(loaded.history_size, 120);
+        assert!(loaded.processes.tree_view);
+    }
+
+    #[test]
+    fn rejects_unknown_keys() {
+        assert!(error("refresh_rate = 500").contains("unknown field `refresh_rate`"));
+        assert!(error("[processes]\nsort_by = \"cpu\"").contains("unknown field `sort_by`"));
+    }
+
+    #[test]
+    fn rejects_out_of_range_values() {
+        assert!(error("refresh_rate_ms = 50").contains("refresh_rate_ms must be between 100 and 60000"));
+        assert!(error("history_size = 5").contains("history_size must be between 10 and 10000"));
+        assert!(error("[theme]\nusage_warning = 90\nusage_critical = 80")
+            .contains("theme.usage_warning (90) must be below theme.usage_critical (80)"));
+    }
+
+    #[test]
+    fn rejects_bad_lists() {
+        assert!(error("panels = [\"gpu\", \"gpu\"]").contains("panels: \"gpu\" is listed twice"));
+        assert!(error("[processes]\ncolumns = []").contains("at least one column"));
+        assert!(error("[processes]\ncolumns = [\"pid\", \"subtree_cpu\"]")
+            .contains("\"subtree_cpu\" is only available in tree view"));
+    }
+
+    #[test]
+    fn rejects_bad_rules_colors_and_keys() {
+        assert!(error("[[alerts]]\nmetric = \"cpu\"").starts_with("invalid config"));
+        assert!(error("[theme]\nok = \"mauve-ish\"").contains("unknown color \"mauve-ish\""));
+        assert!(error("[keys]\nquit = [\"?\"]").starts_with("invalid config"));
+    }
+}
@@ -0,0 +1,320 @@
+use crate::snapshot::Snapshot;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use tokio::{runtime::Runtime, sync::watch};
+
+/// File format for exported snapshots
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Format {
+    /// Pretty-printed array of snapshots
+    #[default]
+    Json,
+    /// One compact snapshot per line, written as each one arrives
+    Ndjson,
+    /// One `timestamp,metric,id,name,value` row per reading
+    Csv,
+}
+
+impl Format {
+    /// File extension, without the dot
+    pub fn extension(self) -> &'static str {
+        match self {
+            Format::Json => "json",
+            Format::Ndjson => "ndjson",
+            Format::Csv => "csv",
+        }
+    }
+
+    /// Guess the format from a file extension
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()?.to_lowercase().as_str() {
+            "json" => Some(Format::Json),
+            "ndjson" | "jsonl" => Some(Format::Ndjson),
+            "csv" => Some(Format::Csv),
+            _ => None,
+        }
+    }
+}
+
+/// One reading in the CSV export
+#[derive(Serialize)]
+struct CsvRow<'a> {
+    timestamp: u64,
+    metric: &'a str,
+    /// Core index, GPU index or PID; empty for system-wide readings
+    id: String,
+    /// GPU or process name
+    name: &'a str,
+    value: f64,
+}
+
+/// Writes a stream of snapshots in one format
+pub struct Exporter<W: Write> {
+    writer: W,
+    format: Format,
+    count: usize,
+}
+
+impl<W: Write> Exporter<W> {
+    pub fn new(writer: W, format: Format) -> Self {
+        Self {
+            writer,
+            format,
+            count: 0,
+        }
+    }
+
+    /// Append a snapshot and flush it, so streams can be tailed
+    pub fn write(&mut self, snapshot: &Snapshot) -> io::Result<()> {
+        match self.format {
+            Format::Json => {
+                self.writer.write_all(if self.count == 0 { b"[\n" } else { b",\n" })?;
+                serde_json::to_writer_pretty(&mut self.writer, snapshot)?;
+            }
+            Format::Ndjson => {
+                serde_json::to_writer(&mut self.writer, snapshot)?;
+                self.writer.write_all(b"\n")?;
+            }
+            Format::Csv => {
+                // Rows are buffered so write errors keep their io::ErrorKind;
+                // the header only goes in front of the first snapshot
+                let mut csv = csv::WriterBuilder::new()
+                    .has_headers(self.count == 0)
+                    .from_writer(Vec::new());
+                for row in csv_rows(snapshot) {
+                    csv.serialize(row)?;
+                }
+                let rows = csv.into_inner().map_err(|err| err.into_error())?;
+                self.writer.write_all(&rows)?;
+            }
+        }
+
+        self.count += 1;
+        self.writer.flush()
+    }
+
+    /// Close the JSON array and flush
+    pub fn finish(mut self) -> io::Result<W> {
+        if self.format == Format::Json {
+            let end: &[u8] = if self.count == 0 { b"[]\n" } else { b"\n]\n" };
+            self.writer.write_all(end)?;
+        }
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+/// Flatten a snapshot into CSV readings
+fn csv_rows(snapshot: &Snapshot) -> Vec<CsvRow<'_>> {
+    let mut rows = Vec::new();
+    let mut push = |metric, id: String, name, value: f64| {
+        rows.push(CsvRow {
+            timestamp: snapshot.timestamp,
+            metric,
+            id,
+            name,
+            value,
+        });
+    };
+
+    push("cpu_usage", String::new(), "", snapshot.global_cpu_usage as f64);
+    for (i, cpu) in snapshot.cpus.iter().enumerate() {
+        push("core_usage", i.to_string(), "", cpu.usage as f64);
+        push("core_frequency_mhz", i.to_string(), "", cpu.frequency as f64);
+    }
+
+    let memory = &snapshot.memory;
+    push("memory_total", String::new(), "", memory.total as f64);
+    push("memory_used", String::new(), "", memory.used as f64);
+    push("memory_available", String::new(), "", memory.available as f64);
+    push("swap_total", String::new(), "", memory.swap_total as f64);
+    push("swap_used", String::new(), "", memory.swap_used as f64);
+
+    for gpu in &snapshot.gpus {
+        let index = gpu.index.to_string();
+        let name = gpu.name.as_str();
+        push("gpu_usage", index.clone(), name, gpu.usage as f64);
+        push("gpu_memory_used", index.clone(), name, gpu.memory_used as f64);
+        push("gpu_memory_total", index.clone(), name, gpu.memory_total as f64);
+        push("gpu_temperature", index.clone(), name, gpu.temperature as f64);
+
+        let readings = [
+            ("gpu_power_draw_watts", gpu.power_draw.map(f64::from)),
+            ("gpu_power_limit_watts", gpu.power_limit.map(f64::from)),
+            ("gpu_sm_clock_mhz", gpu.sm_clock.map(f64::from)),
+            ("gpu_memory_clock_mhz", gpu.memory_clock.map(f64::from)),
+            ("gpu_fan_speed", gpu.fan_speed.map(f64::from)),
+            ("gpu_encoder_usage", gpu.encoder_usage.map(f64::from)),
+            ("gpu_decoder_usage", gpu.decoder_usage.map(f64::from)),
+            ("gpu_pcie_rx_rate", gpu.pcie_rx_rate),
+            ("gpu_pcie_tx_rate", gpu.pcie_tx_rate),
+        ];
+        for (metric, value) in readings {
+            if let Some(value) = value {
+                push(metric, index.clone(), name, value);
+            }
+        }
+    }
+
+    for process in &snapshot.processes {
+        let pid = process.pid.to_string();
+        let name = process.name.as_str();
+        push("process_cpu_usage", pid.clone(), name, process.cpu_usage as f64);
+        push("process_memory", pid.clone(), name, process.memory as f64);
+        push("process_virtual_memory", pid.clone(), name, process.virtual_memory as f64);
+        push("process_disk_read_rate", pid.clone(), name, process.disk_read_rate);
+        push("process_disk_write_rate", pid.clone(), name, process.disk_write_rate);
+        if let Some(usage) = process.gpu_usage {
+            push("process_gpu_usage", pid.clone(), name, usage as f64);
+        }
+        if let Some(memory) = process.gpu_memory {
+            push("process_gpu_memory", pid, name, memory as f64);
+        }
+    }
+
+    rows
+}
+
+/// Write a single snapshot to `path`
+pub fn write_file(path: &Path, format: Format, snapshot: &Snapshot) -> Result<()> {
+    let file = File::create(path).with_context(|| format!("failed to create {}", path.display()))?;
+    let mut exporter = Exporter::new(BufWriter::new(file), format);
+    exporter
+        .write(snapshot)
+        .and_then(|()| exporter.finish())
+        .with_context(|| format!("failed to write {}", path.display()))?;
+    Ok(())
+}
+
+/// Stream `iterations` snapshots (0 for no limit) to `path`, or stdout for "-",
+/// until the limit or Ctrl-C
+pub fn run(
+    runtime: &Runtime,
+    snapshots: &mut watch::Receiver<Arc<Snapshot>>,
+    path: &Path,
+    format: Format,
+    iterations: u64,
+) -> Result<()> {
+    let writer: Box<dyn Write> = if path == Path::new("-") {
+        Box::new(io::stdout().lock())
+    } else {
+        let file = File::create(path).with_context(|| format!("failed to create {}", path.display()))?;
+        Box::new(BufWriter::new(file))
+    };
+    let mut exporter = Exporter::new(writer, format);
+
+    // Ctrl-C ends the export cleanly, so the JSON array still gets closed
+    let mut interrupted = std::pin::pin!(tokio::signal::ctrl_c());
+
+    let mut sample = 0;
+    while iterations == 0 || sample < iterations {
+        // Wait a full interval so CPU usage covers it
+        let changed = runtime.block_on(async {
+            tokio::select! {
+                changed = snapshots.changed() => Some(changed),
+                _ = &mut interrupted => None,
+            }
+        });
+        let Some(changed) = changed else {
+            break;
+        };
+        changed?;
+        let snapshot = snapshots.borrow_and_update().clone();
+
+        match exporter.write(&snapshot) {
+            // Output piped into `head` and friends; stop quietly
+            Err(err) if err.kind() == io::ErrorKind::BrokenPipe => return Ok(()),
+            result => result.with_context(|| format!("failed to write {}", path.display()))?,
+        }
+        sample += 1;
+    }
+
+    exporter
+        .finish()
+        .with_context(|| format!("failed to write {}", path.display()))?;
+    Ok(())
+}
+
+/// Name for an export triggered from the UI, unique per second
+pub fn file_name(format: Format) -> PathBuf {
+    let stamp = chrono::Local::now().format("%Y%m%d-%H%M%S");
+    PathBuf::from(format!("rtui-{}.{}", stamp, format.extension()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::snapshot::{CpuInfo, Snapshot};
+
+    fn snapshot(timestamp: u64) -> Snapshot {
+        Snapshot {
+            timestamp,
+            cpus: vec![CpuInfo::default(); 2],
+            global_cpu_usage: 12.5,
+            ..Default::default()
+        }
+    }
+
+    fn export(format: Format, count: u64) -> String {
+        let mut exporter = Exporter::new(Vec::new(), format);
+        for i in 0..count {
+            exporter.write(&snapshot(1000 + i)).unwrap();
+        }
+        String::from_utf8(exporter.finish().unwrap()).unwrap()
+    }
+
+    #[test]
+    fn json_without_snapshots_is_an_empty_array() {
+        assert_eq!(export(Format::Json, 0), "[]\n");
+    }
+
+    #[test]
+    fn json_is_one_array() {
+        let output = export(Format::Json, 3);
+        assert!(output.starts_with("[\n{"));
+        assert!(output.ends_with("}\n]\n"));
+        assert_eq!(output.matches("},\n{").count(), 2);
+
+        let parsed: Vec<Snapshot> = serde_json::from_str(&output).unwrap();
+        let timestamps: Vec<u64> = parsed.iter().map(|s| s.timestamp).collect();
+        assert_eq!(timestamps, [1000, 1001, 1002]);
+    }
+
+    #[test]
+    fn ndjson_is_one_snapshot_per_line() {
+        let output = export(Format::Ndjson, 2);
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines.len(), 2);
+        for (line, timestamp) in lines.iter().zip([1000, 1001]) {
+            assert_eq!(serde_json::from_str::<Snapshot>(line).unwrap().timestamp, timestamp);
+        }
+    }
+
+    #[test]
+    fn csv_writes_the_header_once() {
+        let output = export(Format::Csv, 2);
+        let lines: Vec<&str> = output.lines().collect();
+
+        assert_eq!(lines[0], "timestamp,metric,id,name,value");
+        assert_eq!(output.matches("timestamp,metric").count(), 1);
+        assert_eq!(lines[1], "1000,cpu_usage,,,12.5");
+        assert_eq!(lines[2], "1000,core_usage,0,,0.0");
+        assert!(lines.contains(&"1001,cpu_usage,,,12.5"));
+    }
+
+    #[test]
+    fn guesses_format_from_extension() {
+        assert_eq!(Format::from_path(Path::new("out.JSON")), Some(Format::Json));
+        assert_eq!(Format::from_path(Path::new("out.jsonl")), Some(Format::Ndjson));
+        assert_eq!(Format::from_path(Path::new("out.csv")), Some(Format::Csv));
+        assert_eq!(Format::from_path(Path::new("out.txt")), None);
+        assert_eq!(Format::from_path(Path::new("-")), None);
+    }
+}
@@ -0,0 +1,626 @@
+use super::{GpuBackend, ProcessGpu};
+use crate::hwmon::{read_number, read_string};
+use crate::snapshot::GpuInfo;
+use std::{
+    collections::HashMap,
+    ffi::OsString,
+    fs,
+    path::{Path, PathBuf},
+    time::Instant,
+};
+
+/// Default location of DRM cards on Linux
+pub const DRM_ROOT: &str = "/sys/class/drm";
+
+/// Default location of per-process information on Linux
+pub const PROC_ROOT: &str = "/proc";
+
+/// Scans between full walks of every process's file descriptors; in
+/// between, only processes not seen before are walked
+const FD_RESCAN_INTERVAL: u32 = 10;
+
+/// Kernel drivers with counters this backend understands
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Driver {
+    Amdgpu,
+    I915,
+    Xe,
+}
+
+impl Driver {
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "amdgpu" => Some(Driver::Amdgpu),
+            "i915" => Some(Driver::I915),
+            "xe" => Some(Driver::Xe),
+            _ => None,
+        }
+    }
+
+    fn vendor(self) -> &'static str {
+        match self {
+            Driver::Amdgpu => "AMD",
+            Driver::I915 | Driver::Xe => "Intel",
+        }
+    }
+}
+
+/// Cumulative idle time of one Intel GT, in milliseconds
+struct IdleCounter {
+    path: PathBuf,
+    /// Previous reading and when it was taken
+    last: Option<(u64, Instant)>,
+}
+
+impl IdleCounter {
+    fn new(path: PathBuf) -> Self {
+        Self { path, last: None }
+    }
+
+    /// Busy percentage since the previous read; `None` on the first read
+    fn busy(&mut self) -> Option<f32> {
+        let idle_ms = read_number(&self.path)? as u64;
+        let now = Instant::now();
+        let (last_ms, last_read) = self.last.replace((idle_ms, now))?;
+
+        let wall_ms = now.duration_since(last_read).as_secs_f64() * 1000.0;
+        if wall_ms <= 0.0 {
+            return None;
+        }
+        let idle = idle_ms.saturating_sub(last_ms) as f64 / wall_ms;
+        Some(((1.0 - idle) * 100.0).clamp(0.0, 100.0) as f32)
+    }
+}
+
+/// One DRM card, such as `/sys/class/drm/card0`
+struct Card {
+    name: String,
+    /// The card's PCI device directory
+    device: PathBuf,
+    driver: Driver,
+    /// Intel only; amdgpu reports a busy percentage directly
+    idle: Vec<IdleCounter>,
+}
+
+impl Card {
+    fn read(&mut self) -> GpuInfo {
+        let device = &self.device;
+        let usage = match self.driver {
+            Driver::Amdgpu => read_number(&device.join("gpu_busy_percent")).unwrap_or(0.0) as f32,
+            // The busiest GT; integrated parts have one, discrete ones a media GT too
+            Driver::I915 | Driver::Xe => self
+                .idle
+                .iter_mut()
+                .filter_map(IdleCounter::busy)
+                .reduce(f32::max)
+                .unwrap_or(0.0),
+        };
+
+        // Intel integrated graphics share system memory and report none
+        let vram = |file: &str| read_number(&device.join(file)).unwrap_or(0.0) as u64;
+        let temperature = subdirs(&device.join("hwmon"), "hwmon")
+            .first()
+            .and_then(|hwmon| read_number(&hwmon.join("temp1_input")))
+            .map(|millidegrees| (millidegrees / 1000.0) as u32)
+            .unwrap_or(0);
+
+        GpuInfo {
+            index: 0,
+            name: read_string(&device.join("product_name"))
+                .unwrap_or_else(|| format!("{} GPU ({})", self.driver.vendor(), self.name)),
+            usage,
+            memory_used: vram("mem_info_vram_used"),
+            memory_total: vram("mem_info_vram_total"),
+            temperature,
+            ..Default::default()
+        }
+    }
+}
+
+/// One open DRM client, parsed from a `/proc/<pid>/fdinfo/<fd>` file
+struct Client {
+    id: u64,
+    /// Cumulative busy time per engine, in nanoseconds
+    engines: HashMap<String, u64>,
+    /// Dedicated memory in bytes
+    memory: u64,
+}
+
+/// AMD and Intel GPUs read from sysfs.
+///
+/// amdgpu exposes `gpu_busy_percent` and VRAM usage on the PCI device. i915
+/// and xe have no utilization file, so usage is derived from how fast each
+/// GT's idle residency counter grows between reads. Temperatures come from
+/// the card's hwmon directory when it has one.
+///
+/// Per-process usage comes from the DRM fdinfo of every open render or card
+/// node, so only processes we're allowed to inspect show up. Walking every
+/// descriptor of every process is costly, so the DRM descriptors found are
+/// remembered per PID; a process that opens the GPU later shows up at the
+/// next full walk.
+pub struct DrmBackend {
+    cards: Vec<Card>,
+    proc_root: PathBuf,
+    /// Engine busy times from the previous scan, by PID and client ID
+    clients: HashMap<(u32, u64), HashMap<String, u64>>,
+    last_scan: Option<Instant>,
+    /// DRM descriptors of each process as of its last fd walk, by PID
+    drm_fds: HashMap<u32, Vec<OsString>>,
+    /// Scans left before the next full fd walk
+    scans_until_rescan: u32,
+}
+
+impl DrmBackend {
+    /// Find the amdgpu, i915 and xe cards under `root`; processes are
+    /// looked up under `proc_root`
+    pub fn new(root: &Path, proc_root: &Path) -> Self {
+        let mut backend = Self {
+            cards: Vec::new(),
+            proc_root: proc_root.to_path_buf(),
+            clients: HashMap::new(),
+            last_scan: None,
+            drm_fds: HashMap::new(),
+            scans_until_rescan: 0,
+        };
+        let Ok(entries) = fs::read_dir(root) else {
+            return backend;
+        };
+
+        // Skip connectors such as card0-DP-1 and render nodes
+        let mut numbered: Vec<(u32, String)> = entries
+            .flatten()
+            .filter_map(|entry| {
+                let name = entry.file_name().to_string_lossy().to_string();
+                let number = name.strip_prefix("card")?.parse().ok()?;
+                Some((number, name))
+            })
+            .collect();
+        numbered.sort_unstable();
+
+        backend.cards = numbered
+            .into_iter()
+            .filter_map(|(_, name)| {
+                let card = root.join(&name);
+                let device = card.join("device");
+                let driver = Driver::from_name(&driver_name(&device)?)?;
+
+                let idle = match driver {
+                    Driver::Amdgpu => Vec::new(),
+                    Driver::I915 => {
+                        let per_gt: Vec<PathBuf> = subdirs(&card.join("gt"), "gt")
+                            .into_iter()
+                            .map(|gt| gt.join("rc6_residency_ms"))
+                            .filter(|path| path.exists())
+                            .collect();
+                        // Kernels before 5.12 have a single card-wide counter
+                        if per_gt.is_empty() {
+                            vec![card.join("power/rc6_residency_ms")]
+                        } else {
+                            per_gt
+                        }
+                    }
+                    Driver::Xe => subdirs(&device, "tile")
+                        .iter()
+                        .flat_map(|tile| subdirs(tile, "gt"))
+                        .map(|gt| gt.join("gtidle/idle_residency_ms"))
+                        .collect(),
+                };
+
+                Some(Card {
+                    name,
+                    device,
+                    driver,
+                    idle: idle.into_iter().map(IdleCounter::new).collect(),
+                })
+            })
+            .collect();
+
+        backend
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cards.is_empty()
+    }
+}
+
+impl GpuBackend for DrmBackend {
+    fn gpus(&mut self) -> Vec<GpuInfo> {
+        self.cards.iter_mut().map(Card::read).collect()
+    }
+
+    fn processes(&mut self) -> HashMap<u32, ProcessGpu> {
+        let now = Instant::now();
+        let wall_ns = self
+            .last_scan
+            .replace(now)
+            .map(|last| now.duration_since(last).as_nanos() as f64)
+            .filter(|&wall_ns| wall_ns > 0.0);
+
+        let mut clients = HashMap::new();
+        let mut processes: HashMap<u32, ProcessGpu> = HashMap::new();
+        for (pid, client) in self.drm_clients() {
+            // Duplicated descriptors share a client; count it once
+            if clients.contains_key(&(pid, client.id)) {
+                continue;
+            }
+
+            // Usage of the busiest engine since the previous scan
+            let usage = wall_ns.zip(self.clients.get(&(pid, client.id))).map(|(wall_ns, previous)| {
+                client
+                    .engines
+                    .iter()
+                    .map(|(engine, &busy_ns)| {
+                        let delta = busy_ns.saturating_sub(previous.get(engine).copied().unwrap_or(busy_ns));
+                        (delta as f64 / wall_ns * 100.0).min(100.0) as f32
+                    })
+                    .fold(0.0, f32::max)
+            });
+
+            processes.entry(pid).or_default().add(ProcessGpu {
+                usage,
+                memory: client.memory,
+            });
+            clients.insert((pid, client.id), client.engines);
+        }
+
+        self.clients = clients;
+        processes
+    }
+}
+
+impl DrmBackend {
+    /// Every amdgpu, i915 and xe client open by any process under `proc_root`
+    fn drm_clients(&mut self) -> Vec<(u32, Client)> {
+        let Ok(entries) = fs::read_dir(&self.proc_root) else {
+            return Vec::new();
+        };
+
+        let rescan = self.scans_until_rescan == 0;
+        self.scans_until_rescan = if rescan {
+            FD_RESCAN_INTERVAL - 1
+        } else {
+            self.scans_until_rescan - 1
+        };
+
+        // Rebuilt every scan so exited processes drop out
+        let mut drm_fds = HashMap::new();
+        let mut clients = Vec::new();
+        for entry in entries.flatten() {
+            let Ok(pid) = entry.file_name().to_string_lossy().parse::<u32>() else {
+                continue;
+            };
+            let fds = match self.drm_fds.remove(&pid) {
+                Some(fds) if !rescan => fds,
+                _ => find_drm_fds(&entry.path()),
+            };
+
+            for fd in &fds {
+                let fdinfo = entry.path().join("fdinfo").join(fd);
+                if let Some(client) = fs::read_to_string(fdinfo).ok().as_deref().and_then(parse_fdinfo) {
+                    clients.push((pid, client));
+                }
+            }
+            drm_fds.insert(pid, fds);
+        }
+
+        self.drm_fds = drm_fds;
+        clients
+    }
+}
+
+/// Descriptors of a process that point at DRM nodes; none for processes we
+/// can't inspect
+fn find_drm_fds(process: &Path) -> Vec<OsString> {
+    // Fails for other users' processes unless we're root
+    let Ok(fds) = fs::read_dir(process.join("fd")) else {
+        return Vec::new();
+    };
+
+    fds.flatten()
+        .filter(|fd| fs::read_link(fd.path()).is_ok_and(|target| target.starts_with("/dev/dri")))
+        .map(|fd| fd.file_name())
+        .collect()
+}
+
+/// Parse a DRM fdinfo file; `None` for drivers this backend doesn't handle
+fn parse_fdinfo(content: &str) -> Option<Client> {
+    let mut driver = None;
+    let mut id = None;
+    let mut engines = HashMap::new();
+    let mut legacy_vram = 0;
+    let mut resident = None;
+
+    for line in content.lines() {
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = value.trim();
+
+        if key == "drm-driver" {
+            driver = Driver::from_name(value);
+        } else if key == "drm-client-id" {
+            id = value.parse().ok();
+        } else if key == "drm-memory-vram" {
+            legacy_vram = parse_size(value)?;
+        } else if let Some(region) = key.strip_prefix("drm-resident-") {
+            // Dedicated memory only; system memory is already in the RSS
+            if region.starts_with("vram") || region.starts_with("local") {
+                *resident.get_or_insert(0) += parse_size(value)?;
+            }
+        } else if let Some(engine) = key.strip_prefix("drm-engine-") {
+            if !engine.starts_with("capacity-") {
+                let busy_ns = value.split_whitespace().next()?.parse().ok()?;
+                engines.insert(engine.to_string(), busy_ns);
+            }
+        }
+    }
+
+    driver?;
+    Some(Client {
+        id: id?,
+        engines,
+        // Newer kernels report both; the legacy key is kept for older ones
+        memory: resident.unwrap_or(legacy_vram),
+    })
+}
+
+/// Parse an fdinfo size such as "1024 KiB"
+fn parse_size(value: &str) -> Option<u64> {
+    let mut parts = value.split_whitespace();
+    let number: u64 = parts.next()?.parse().ok()?;
+    let scale = match parts.next() {
+        Some("KiB") => 1 << 10,
+        Some("MiB") => 1 << 20,
+        Some("GiB") => 1 << 30,
+        _ => 1,
+    };
+    Some(number * scale)
+}
+
+/// Kernel driver bound to a PCI device, from its `uevent` file
+fn driver_name(device: &Path) -> Option<String> {
+    let uevent = fs::read_to_string(device.join("uevent")).ok()?;
+    uevent
+        .lines()
+        .find_map(|line| line.strip_prefix("DRIVER="))
+        .map(|driver| driver.trim().to_string())
+}
+
+/// Subdirectories of `dir` named `<prefix>N`, in numeric order
+fn subdirs(dir: &Path, prefix: &str) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+
+    let mut numbered: Vec<(u32, PathBuf)> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let number = entry.file_name().to_string_lossy().strip_prefix(prefix)?.parse().ok()?;
+            Some((number, entry.path()))
+        })
+        .collect();
+    numbered.sort_unstable();
+    numbered.into_iter().map(|(_, path)| path).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{thread, time::Duration};
+    use tempfile::TempDir;
+
+    fn write(path: &Path, content: &str) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    fn card(root: &Path, name: &str, driver: &str) -> PathBuf {
+        let card = root.join(name);
+        write(&card.join("device/uevent"), &format!("DRIVER={}\nPCI_ID=1002:7480\n", driver));
+        card
+    }
+
+    fn backend(root: &TempDir) -> DrmBackend {
+        DrmBackend::new(&root.path().join("drm"), &root.path().join("proc"))
+    }
+
+    #[test]
+    fn reads_amdgpu_card() {
+        let root = TempDir::new().unwrap();
+        let device = card(&root.path().join("drm"), "card0", "amdgpu").join("device");
+        write(&device.join("gpu_busy_percent"), "42\n");
+        write(&device.join("mem_info_vram_used"), "1073741824\n");
+        write(&device.join("mem_info_vram_total"), "8589934592\n");
+        write(&device.join("hwmon/hwmon0/temp1_input"), "61000\n");
+        // Connectors and unknown drivers are skipped
+        write(&root.path().join("drm/card0-DP-1/status"), "connected\n");
+        card(&root.path().join("drm"), "card1", "nouveau");
+
+        let gpus = backend(&root).gpus();
+        assert_eq!(gpus.len(), 1);
+        let gpu = &gpus[0];
+        assert_eq!(gpu.name, "AMD GPU (card0)");
+        assert_eq!(gpu.usage, 42.0);
+        assert_eq!(gpu.memory_used, 1 << 30);
+        assert_eq!(gpu.memory_total, 8 << 30);
+        assert_eq!(gpu.temperature, 61);
+    }
+
+    #[test]
+    fn prefers_product_name() {
+        let root = TempDir::new().unwrap();
+        let card = card(&root.path().join("drm"), "card0", "amdgpu");
+        write(&card.join("device/product_name"), "Radeon RX 7600\n");
+
+        assert_eq!(backend(&root).gpus()[0].name, "Radeon RX 7600");
+    }
+
+    #[test]
+    fn derives_i915_usage_from_rc6_residency() {
+        let root = TempDir::new().unwrap();
+        let card = card(&root.path().join("drm"), "card0", "i915");
+        let rc6 = card.join("gt/gt0/rc6_residency_ms");
+        write(&rc6, "1000\n");
+
+        let mut backend = backend(&root);
+        // No rate until there's a previous reading
+        assert_eq!(backend.gpus()[0].usage, 0.0);
+
+        // Never idle between the reads
+        thread::sleep(Duration::from_millis(20));
+        let gpu = &backend.gpus()[0];
+        assert_eq!(gpu.usage, 100.0);
+        assert_eq!(gpu.name, "Intel GPU (card0)");
+        assert_eq!(gpu.memory_total, 0);
+    }
+
+    #[test]
+    fn falls_back_to_card_wide_rc6_counter() {
+        let root = TempDir::new().unwrap();
+        let card = card(&root.path().join("drm"), "card0", "i915");
+        write(&card.join("power/rc6_residency_ms"), "0\n");
+
+        let backend = backend(&root);
+        assert_eq!(backend.cards[0].idle.len(), 1);
+        assert_eq!(backend.cards[0].idle[0].path, card.join("power/rc6_residency_ms"));
+    }
+
+    #[test]
+    fn derives_xe_usage_from_gtidle() {
+        let root = TempDir::new().unwrap();
+        let card = card(&root.path().join("drm"), "card0", "xe");
+        let idle = card.join("device/tile0/gt0/gtidle/idle_residency_ms");
+        write(&idle, "0\n");
+        write(&card.join("device/tile0/gt1/gtidle/idle_residency_ms"), "0\n");
+
+        let mut backend = backend(&root);
+        assert_eq!(backend.cards[0].idle.len(), 2);
+        backend.gpus();
+
+        // Idle for far longer than the wall time between reads
+        thread::sleep(Duration::from_millis(20));
+        write(&idle, "1000000\n");
+        write(&card.join("device/tile0/gt1/gtidle/idle_residency_ms"), "1000000\n");
+        assert_eq!(backend.gpus()[0].usage, 0.0);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn reads_process_usage_from_fdinfo() {
+        let root = TempDir::new().unwrap();
+        let process = root.path().join("proc/4242");
+        let fdinfo = process.join("fdinfo/5");
+        write(
+            &fdinfo,
+            "drm-driver:\tamdgpu\ndrm-client-id:\t7\ndrm-engine-gfx:\t0 ns\ndrm-memory-vram:\t2048 KiB\n",
+        );
+        fs::create_dir_all(process.join("fd")).unwrap();
+        std::os::unix::fs::symlink("/dev/dri/renderD128", process.join("fd/5")).unwrap();
+        // Not a DRM node, so its fdinfo is never read
+        std::os::unix::fs::symlink("/dev/null", process.join("fd/6")).unwrap();
+        write(&process.join("fdinfo/6"), "drm-driver:\tamdgpu\ndrm-client-id:\t8\n");
+
+        let mut backend = backend(&root);
+        let first = backend.processes();
+        assert_eq!(first[&4242].usage, None);
+        assert_eq!(first[&4242].memory, 2048 << 10);
+
+        // Far more busy time than wall time caps at 100%
+        write(
+            &fdinfo,
+            "drm-driver:\tamdgpu\ndrm-client-id:\t7\ndrm-engine-gfx:\t1000000000000 ns\ndrm-memory-vram:\t2048 KiB\n",
+        );
+        assert_eq!(backend.processes()[&4242].usage, Some(100.0));
+    }
+
+    /// Give `pid` a DRM descriptor `fd` for amdgpu client `id`
+    fn open_drm(root: &TempDir, pid: u32, fd: u32, id: u64) {
+        let process = root.path().join(format!("proc/{}", pid));
+        write(
+            &process.join(format!("fdinfo/{}", fd)),
+            &format!("drm-driver:\tamdgpu\ndrm-client-id:\t{}\ndrm-memory-vram:\t1 KiB\n", id),
+        );
+        fs::create_dir_all(process.join("fd")).unwrap();
+        std::os::unix::fs::symlink("/dev/dri/renderD128", process.join(format!("fd/{}", fd))).unwrap();
+    }
+
+    #[test]
+    fn walks_descriptors_of_known_processes_only_on_rescans() {
+        let root = TempDir::new().unwrap();
+        open_drm(&root, 100, 5, 1);
+        fs::create_dir_all(root.path().join("proc/200/fd")).unwrap();
+
+        let mut backend = backend(&root);
+        assert_eq!(backend.processes().len(), 1);
+
+        // New processes are walked right away
+        open_drm(&root, 300, 3, 2);
+        // Known ones keep their descriptors until the next full walk
+        open_drm(&root, 200, 4, 3);
+        open_drm(&root, 100, 6, 4);
+        let processes = backend.processes();
+        assert!(processes.contains_key(&300));
+        assert!(!processes.contains_key(&200));
+        assert_eq!(processes[&100].memory, 1 << 10);
+
+        for _ in 2..FD_RESCAN_INTERVAL {
+            assert!(!backend.processes().contains_key(&200));
+        }
+        let processes = backend.processes();
+        assert!(processes.contains_key(&200));
+        assert_eq!(processes[&100].memory, 2 << 10);
+
+        // Exited processes are forgotten
+        fs::remove_dir_all(root.path().join("proc/300")).unwrap();
+        assert!(!backend.processes().contains_key(&300));
+        assert!(!backend.drm_fds.contains_key(&300));
+    }
+
+    #[test]
+    fn parses_fdinfo() {
+        let client = parse_fdinfo(
+            "pos:\t0\n\
+             drm-driver:\ti915\n\
+             drm-client-id:\t12\n\
+             drm-engine-render:\t5000 ns\n\
+             drm-engine-video:\t300 ns\n\
+             drm-engine-capacity-video:\t2\n\
+             drm-resident-local0:\t4 MiB\n\
+             drm-resident-system0:\t100 MiB\n",
+        )
+        .unwrap();
+
+        assert_eq!(client.id, 12);
+        assert_eq!(client.engines.len(), 2);
+        assert_eq!(client.engines["render"], 5000);
+        assert_eq!(client.engines["video"], 300);
+        // System memory is already counted in the RSS
+        assert_eq!(client.memory, 4 << 20);
+    }
+
+    #[test]
+    fn resident_memory_overrides_legacy_vram() {
+        let client = parse_fdinfo(
+            "drm-driver:\tamdgpu\ndrm-client-id:\t1\ndrm-memory-vram:\t1 MiB\ndrm-resident-vram:\t3 MiB\n",
+        )
+        .unwrap();
+        assert_eq!(client.memory, 3 << 20);
+    }
+
+    #[test]
+    fn rejects_other_drivers_and_missing_client_id() {
+        assert!(parse_fdinfo("drm-driver:\tnouveau\ndrm-client-id:\t1\n").is_none());
+        assert!(parse_fdinfo("drm-driver:\tamdgpu\n").is_none());
+        assert!(parse_fdinfo("").is_none());
+    }
+
+    #[test]
+    fn parses_sizes() {
+        assert_eq!(parse_size("512"), Some(512));
+        assert_eq!(parse_size("4 KiB"), Some(4 << 10));
+        assert_eq!(parse_size("3 MiB"), Some(3 << 20));
+        assert_eq!(parse_size("2 GiB"), Some(2 << 30));
+        assert_eq!(parse_size("KiB"), None);
+        assert_eq!(parse_size(""), None);
+    }
+}
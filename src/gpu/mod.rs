@@ -0,0 +1,59 @@
+pub mod drm;
+#[cfg(feature = "gpu-nvidia")]
+pub mod nvidia;
+
+use crate::snapshot::GpuInfo;
+use std::{collections::HashMap, path::Path};
+
+/// What one process uses across the GPUs of a backend
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ProcessGpu {
+    /// Utilization percentage, if the driver reports it
+    pub usage: Option<f32>,
+    /// Dedicated GPU memory in bytes
+    pub memory: u64,
+}
+
+impl ProcessGpu {
+    /// Combine readings for the same process from another GPU or backend
+    pub fn add(&mut self, other: ProcessGpu) {
+        self.usage = match (self.usage, other.usage) {
+            (Some(a), Some(b)) => Some(a + b),
+            (a, b) => a.or(b),
+        };
+        self.memory += other.memory;
+    }
+}
+
+/// Reads the GPUs of one vendor or driver family.
+///
+/// Backends are polled once per refresh; ones that need two readings to
+/// compute a rate (such as idle residency counters) keep the previous
+/// reading themselves.
+pub trait GpuBackend: Send {
+    /// Current readings for every GPU this backend handles
+    fn gpus(&mut self) -> Vec<GpuInfo>;
+
+    /// GPU usage of every process with a context on these GPUs, by PID
+    fn processes(&mut self) -> HashMap<u32, ProcessGpu> {
+        HashMap::new()
+    }
+}
+
+/// Every backend usable on this machine: NVML first when compiled in and a
+/// driver is loaded, then AMD and Intel cards found through sysfs
+pub fn backends() -> Vec<Box<dyn GpuBackend>> {
+    let mut backends: Vec<Box<dyn GpuBackend>> = Vec::new();
+
+    #[cfg(feature = "gpu-nvidia")]
+    if let Some(nvidia) = nvidia::NvidiaBackend::new() {
+        backends.push(Box::new(nvidia));
+    }
+
+    let drm = drm::DrmBackend::new(Path::new(drm::DRM_ROOT), Path::new(drm::PROC_ROOT));
+    if !drm.is_empty() {
+        backends.push(Box::new(drm));
+    }
+
+    backends
+}
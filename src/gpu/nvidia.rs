@@ -0,0 +1,127 @@
+use super::{GpuBackend, ProcessGpu};
+use crate::snapshot::GpuInfo;
+use nvml_wrapper::{
+    bitmasks::device::ThrottleReasons,
+    enum_wrappers::device::{Clock, PcieUtilCounter, TemperatureSensor},
+    enums::device::UsedGpuMemory,
+    Nvml,
+};
+use std::collections::HashMap;
+
+/// NVIDIA GPUs through NVML
+pub struct NvidiaBackend {
+    nvml: Nvml,
+    /// Timestamp (µs) of the newest process utilization sample seen, per device
+    last_samples: Vec<u64>,
+}
+
+impl NvidiaBackend {
+    /// Load NVML; `None` when the driver isn't installed
+    pub fn new() -> Option<Self> {
+        Nvml::init().ok().map(|nvml| Self {
+            nvml,
+            last_samples: Vec::new(),
+        })
+    }
+}
+
+impl GpuBackend for NvidiaBackend {
+    fn gpus(&mut self) -> Vec<GpuInfo> {
+        let count = self.nvml.device_count().unwrap_or(0);
+
+        (0..count)
+            .filter_map(|index| {
+                let device = self.nvml.device_by_index(index).ok()?;
+                let memory = device.memory_info().ok();
+                // Readings a GPU doesn't support come back as errors
+                let watts = |milliwatts: u32| milliwatts as f32 / 1000.0;
+                let pcie = |counter| device.pcie_throughput(counter).ok().map(|kb| kb as f64 * 1024.0);
+
+                Some(GpuInfo {
+                    index,
+                    name: device.name().unwrap_or_else(|_| "Unknown GPU".to_string()),
+                    usage: device
+                        .utilization_rates()
+                        .map(|u| u.gpu as f32)
+                        .unwrap_or(0.0),
+                    memory_used: memory.as_ref().map(|m| m.used).unwrap_or(0),
+                    memory_total: memory.as_ref().map(|m| m.total).unwrap_or(0),
+                    temperature: device.temperature(TemperatureSensor::Gpu).unwrap_or(0),
+                    power_draw: device.power_usage().ok().map(watts),
+                    power_limit: device.enforced_power_limit().ok().map(watts),
+                    sm_clock: device.clock_info(Clock::SM).ok(),
+                    memory_clock: device.clock_info(Clock::Memory).ok(),
+                    fan_speed: device.fan_speed(0).ok(),
+                    throttle_reasons: device
+                        .current_throttle_reasons()
+                        .map(throttle_names)
+                        .unwrap_or_default(),
+                    encoder_usage: device.encoder_utilization().ok().map(|u| u.utilization as f32),
+                    decoder_usage: device.decoder_utilization().ok().map(|u| u.utilization as f32),
+                    pcie_rx_rate: pcie(PcieUtilCounter::Receive),
+                    pcie_tx_rate: pcie(PcieUtilCounter::Send),
+                })
+            })
+            .collect()
+    }
+
+    fn processes(&mut self) -> HashMap<u32, ProcessGpu> {
+        let count = self.nvml.device_count().unwrap_or(0);
+        self.last_samples.resize(count as usize, 0);
+
+        let mut processes: HashMap<u32, ProcessGpu> = HashMap::new();
+        for index in 0..count {
+            let Ok(device) = self.nvml.device_by_index(index) else {
+                continue;
+            };
+
+            // A process can be in both lists with the same memory figure
+            let mut on_device: HashMap<u32, ProcessGpu> = HashMap::new();
+            let compute = device.running_compute_processes().unwrap_or_default();
+            let graphics = device.running_graphics_processes().unwrap_or_default();
+            for process in compute.into_iter().chain(graphics) {
+                let memory = match process.used_gpu_memory {
+                    UsedGpuMemory::Used(bytes) => bytes,
+                    UsedGpuMemory::Unavailable => 0,
+                };
+                let entry = on_device.entry(process.pid).or_default();
+                entry.memory = entry.memory.max(memory);
+            }
+
+            // Only samples newer than the last refresh; keep each process's latest.
+            // Processes without a sample were idle, unless the GPU can't sample at all.
+            let last_seen = &mut self.last_samples[index as usize];
+            let Ok(mut samples) = device.process_utilization_stats(*last_seen) else {
+                for (pid, usage) in on_device {
+                    processes.entry(pid).or_default().add(usage);
+                }
+                continue;
+            };
+            for usage in on_device.values_mut() {
+                usage.usage = Some(0.0);
+            }
+            samples.sort_by_key(|sample| sample.timestamp);
+            for sample in samples {
+                *last_seen = (*last_seen).max(sample.timestamp);
+                on_device.entry(sample.pid).or_default().usage = Some(sample.sm_util as f32);
+            }
+
+            for (pid, usage) in on_device {
+                processes.entry(pid).or_default().add(usage);
+            }
+        }
+
+        processes
+    }
+}
+
+/// Names of the active throttle reasons, such as "sw_power_cap".
+///
+/// An idle GPU isn't being held back, so `GPU_IDLE` is left out.
+fn throttle_names(reasons: ThrottleReasons) -> Vec<String> {
+    reasons
+        .difference(ThrottleReasons::GPU_IDLE)
+        .iter_names()
+        .map(|(name, _)| name.to_lowercase())
+        .collect()
+}
@@ -0,0 +1,172 @@
+use crate::snapshot::{SensorInfo, SensorKind};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// Default location of hwmon chips on Linux
+pub const HWMON_ROOT: &str = "/sys/class/hwmon";
+
+/// Read fan and voltage sensors from every chip under `root`.
+///
+/// Temperatures are left to `sysinfo::Components`, which already reads them
+/// from hwmon along with their critical thresholds.
+pub fn read_sensors(root: &Path) -> Vec<SensorInfo> {
+    let Ok(entries) = fs::read_dir(root) else {
+        return Vec::new();
+    };
+
+    let mut chips: Vec<PathBuf> = entries.flatten().map(|entry| entry.path()).collect();
+    chips.sort();
+
+    let mut sensors = Vec::new();
+    for chip in chips {
+        let chip_name = read_string(&chip.join("name")).unwrap_or_else(|| {
+            chip.file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default()
+        });
+
+        sensors.extend(read_channels(&chip, &chip_name, "fan", SensorKind::Fan));
+        sensors.extend(read_channels(&chip, &chip_name, "in", SensorKind::Voltage));
+    }
+
+    sensors
+}
+
+/// Read all `<prefix>N_input` channels of one chip
+fn read_channels(chip: &Path, chip_name: &str, prefix: &str, kind: SensorKind) -> Vec<SensorInfo> {
+    let Ok(entries) = fs::read_dir(chip) else {
+        return Vec::new();
+    };
+
+    let mut indices: Vec<u32> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let file_name = entry.file_name().to_string_lossy().to_string();
+            file_name
+                .strip_prefix(prefix)?
+                .strip_suffix("_input")?
+                .parse()
+                .ok()
+        })
+        .collect();
+    indices.sort_unstable();
+
+    indices
+        .into_iter()
+        .filter_map(|index| {
+            let channel = format!("{}{}", prefix, index);
+            let value = read_number(&chip.join(format!("{}_input", channel)))?;
+            let label = read_string(&chip.join(format!("{}_label", channel))).unwrap_or(channel.clone());
+            let max = read_number(&chip.join(format!("{}_max", channel)));
+            let critical = read_number(&chip.join(format!("{}_crit", channel)));
+
+            Some(SensorInfo {
+                label: format!("{} {}", chip_name, label),
+                kind,
+                value: scale(kind, value),
+                max: max.map(|v| scale(kind, v)),
+                critical: critical.map(|v| scale(kind, v)),
+            })
+        })
+        .collect()
+}
+
+/// Convert raw hwmon units (RPM, millivolts) to display units
+fn scale(kind: SensorKind, raw: f64) -> f32 {
+    match kind {
+        SensorKind::Voltage => (raw / 1000.0) as f32,
+        _ => raw as f32,
+    }
+}
+
+pub fn read_string(path: &Path) -> Option<String> {
+    fs::read_to_string(path)
+        .ok()
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+}
+
+pub fn read_number(path: &Path) -> Option<f64> {
+    read_string(path)?.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write(path: &Path, content: &str) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    /// Label, kind, value, max and critical reading of one sensor
+    type Reading = (String, SensorKind, f32, Option<f32>, Option<f32>);
+
+    fn read(root: &Path) -> Vec<Reading> {
+        read_sensors(root)
+            .into_iter()
+            .map(|s| (s.label, s.kind, s.value, s.max, s.critical))
+            .collect()
+    }
+
+    #[test]
+    fn reads_fans_and_voltages() {
+        let root = TempDir::new().unwrap();
+        let chip = root.path().join("hwmon0");
+        write(&chip.join("name"), "nct6798\n");
+        write(&chip.join("fan2_input"), "1200\n");
+        write(&chip.join("fan2_label"), "CPU Fan\n");
+        write(&chip.join("fan10_input"), "800\n");
+        write(&chip.join("in0_input"), "1104\n");
+        write(&chip.join("in0_max"), "1500\n");
+        write(&chip.join("in0_crit"), "1750\n");
+        // Temperatures come from sysinfo
+        write(&chip.join("temp1_input"), "45000\n");
+
+        assert_eq!(
+            read(root.path()),
+            [
+                ("nct6798 CPU Fan".to_string(), SensorKind::Fan, 1200.0, None, None),
+                ("nct6798 fan10".to_string(), SensorKind::Fan, 800.0, None, None),
+                ("nct6798 in0".to_string(), SensorKind::Voltage, 1.104, Some(1.5), Some(1.75)),
+            ]
+        );
+    }
+
+    #[test]
+    fn falls_back_to_the_directory_name_without_a_chip_name() {
+        let root = TempDir::new().unwrap();
+        write(&root.path().join("hwmon3/fan1_input"), "900\n");
+        write(&root.path().join("hwmon3/name"), "\n");
+
+        assert_eq!(read(root.path())[0].0, "hwmon3 fan1");
+    }
+
+    #[test]
+    fn skips_missing_and_unparsable_readings() {
+        let root = TempDir::new().unwrap();
+        let chip = root.path().join("hwmon0");
+        write(&chip.join("name"), "acpi\n");
+        write(&chip.join("fan1_input"), "n/a\n");
+        write(&chip.join("fan1_label"), "Broken\n");
+        write(&chip.join("fan2_label"), "No input\n");
+        write(&chip.join("in1_input"), "\n");
+        write(&chip.join("in2_input"), "3300\n");
+        write(&chip.join("in2_max"), "garbage\n");
+        write(&chip.join("intrusion0_alarm"), "0\n");
+
+        assert_eq!(
+            read(root.path()),
+            [("acpi in2".to_string(), SensorKind::Voltage, 3.3, None, None)]
+        );
+    }
+
+    #[test]
+    fn missing_root_has_no_sensors() {
+        let root = TempDir::new().unwrap();
+        assert!(read_sensors(&root.path().join("missing")).is_empty());
+    }
+}
@@ -0,0 +1,290 @@
+use crate::config::name_of;
+use crossterm::event::KeyCode;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+/// Something a key can be bound to in normal mode
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Action {
+    Quit,
+    Help,
+    Search,
+    FasterRefresh,
+    SlowerRefresh,
+    SortCpu,
+    SortMemory,
+    SortPid,
+    SortRuntime,
+    SortPrevColumn,
+    SortNextColumn,
+    ColumnEditor,
+    TreeView,
+    Pin,
+    Mark,
+    MarkMatching,
+    Collapse,
+    Expand,
+    NextInterface,
+    NextGpu,
+    Up,
+    Down,
+    Details,
+    /// Falls back to moving up when no process is selected, so the
+    /// default `k` still works as vim-style navigation
+    Kill,
+    KillTree,
+    SignalMenu,
+    Suspend,
+    Resume,
+    Renice,
+    IoPriority,
+    AlertLog,
+    Export,
+    SaveConfig,
+    ReplayPause,
+    ReplaySlower,
+    ReplayFaster,
+    ReplayBack,
+    ReplayForward,
+}
+
+impl Action {
+    /// Bindings used when the config file doesn't override them
+    fn default_keys(self) -> &'static [&'static str] {
+        match self {
+            Action::Quit => &["q", "Q"],
+            Action::Help => &["?"],
+            Action::Search => &["/"],
+            Action::FasterRefresh => &["+", "="],
+            Action::SlowerRefresh => &["-"],
+            Action::SortCpu => &["c"],
+            Action::SortMemory => &["m"],
+            Action::SortPid => &["p"],
+            Action::SortRuntime => &["T"],
+            Action::SortPrevColumn => &["<"],
+            Action::SortNextColumn => &[">"],
+            Action::ColumnEditor => &["o"],
+            Action::TreeView => &["t"],
+            Action::Pin => &["P"],
+            Action::Mark => &["v"],
+            Action::MarkMatching => &["V"],
+            Action::Collapse => &["Left"],
+            Action::Expand => &["Right"],
+            Action::NextInterface => &["i"],
+            Action::NextGpu => &["g"],
+            Action::Up => &["Up"],
+            Action::Down => &["Down", "j"],
+            Action::Details => &["Enter"],
+            Action::Kill => &["k"],
+            Action::KillTree => &["X"],
+            Action::SignalMenu => &["K"],
+            Action::Suspend => &["s"],
+            Action::Resume => &["r"],
+            Action::Renice => &["n"],
+            Action::IoPriority => &["I"],
+            Action::AlertLog => &["a"],
+            Action::Export => &["e"],
+            Action::SaveConfig => &["W"],
+            Action::ReplayPause => &["Space"],
+            Action::ReplaySlower => &["["],
+            Action::ReplayFaster => &["]"],
+            Action::ReplayBack => &[","],
+            Action::ReplayForward => &["."],
+        }
+    }
+
+    const ALL: [Action; 38] = [
+        Action::Quit,
+        Action::Help,
+        Action::Search,
+        Action::FasterRefresh,
+        Action::SlowerRefresh,
+        Action::SortCpu,
+        Action::SortMemory,
+        Action::SortPid,
+        Action::SortRuntime,
+        Action::SortPrevColumn,
+        Action::SortNextColumn,
+        Action::ColumnEditor,
+        Action::TreeView,
+        Action::Pin,
+        Action::Mark,
+        Action::MarkMatching,
+        Action::Collapse,
+        Action::Expand,
+        Action::NextInterface,
+        Action::NextGpu,
+        Action::Up,
+        Action::Down,
+        Action::Details,
+        Action::Kill,
+        Action::KillTree,
+        Action::SignalMenu,
+        Action::Suspend,
+        Action::Resume,
+        Action::Renice,
+        Action::IoPriority,
+        Action::AlertLog,
+        Action::Export,
+        Action::SaveConfig,
+        Action::ReplayPause,
+        Action::ReplaySlower,
+        Action::ReplayFaster,
+        Action::ReplayBack,
+        Action::ReplayForward,
+    ];
+}
+
+/// Key bindings as written in the config file: action name to key names
+pub type KeyBindings = BTreeMap<Action, Vec<String>>;
+
+/// Lookup from key to action for normal mode
+#[derive(Debug, Clone)]
+pub struct Keymap {
+    keys: HashMap<KeyCode, Action>,
+    /// Keys of each action in the order they were listed, for hints
+    bindings: HashMap<Action, Vec<KeyCode>>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Self::new(&KeyBindings::new()).expect("default key bindings are valid")
+    }
+}
+
+impl Keymap {
+    /// Build a keymap from config overrides on top of the defaults.
+    ///
+    /// An action listed in `overrides` replaces all its default keys. A key
+    /// bound to two actions is an error.
+    pub fn new(overrides: &KeyBindings) -> Result<Self, String> {
+        let mut keys = HashMap::new();
+        let mut bindings: HashMap<Action, Vec<KeyCode>> = HashMap::new();
+
+        for action in Action::ALL {
+            let names: Vec<&str> = match overrides.get(&action) {
+                Some(names) => names.iter().map(String::as_str).collect(),
+                None => action.default_keys().to_vec(),
+            };
+
+            for name in names {
+                let code = parse_key(name)
+                    .ok_or_else(|| format!("keys.{}: unknown key \"{}\"", name_of(&action), name))?;
+                if let Some(other) = keys.insert(code, action) {
+                    if other != action {
+                        return Err(format!(
+                            "keys.{}: \"{}\" is already bound to {}",
+                            name_of(&action),
+                            name,
+                            name_of(&other)
+                        ));
+                    }
+                }
+                let codes = bindings.entry(action).or_default();
+                if !codes.contains(&code) {
+                    codes.push(code);
+                }
+            }
+        }
+
+        Ok(Self { keys, bindings })
+    }
+
+    /// Action bound to a key, if any
+    pub fn action(&self, code: KeyCode) -> Option<Action> {
+        self.keys.get(&code).copied()
+    }
+
+    /// Display names of the keys bound to an action; empty if it's unbound
+    pub fn keys(&self, action: Action) -> Vec<String> {
+        self.bindings
+            .get(&action)
+            .map(|codes| codes.iter().map(|&code| key_name(code)).collect())
+            .unwrap_or_default()
+    }
+}
+
+/// Short display name of a key for hints, such as "↑" or "Space"
+fn key_name(code: KeyCode) -> String {
+    match code {
+        KeyCode::Char(' ') => "Space".to_string(),
+        KeyCode::Char(c) => c.to_string(),
+        KeyCode::Up => "↑".to_string(),
+        KeyCode::Down => "↓".to_string(),
+        KeyCode::Left => "←".to_string(),
+        KeyCode::Right => "→".to_string(),
+        KeyCode::PageUp => "PgUp".to_string(),
+        KeyCode::PageDown => "PgDn".to_string(),
+        KeyCode::Delete => "Del".to_string(),
+        KeyCode::F(n) => format!("F{}", n),
+        other => format!("{:?}", other),
+    }
+}
+
+/// Parse a key name: a single character, or a named key such as "Enter"
+fn parse_key(name: &str) -> Option<KeyCode> {
+    let mut chars = name.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        return Some(KeyCode::Char(c));
+    }
+
+    let code = match name.to_lowercase().as_str() {
+        "up" => KeyCode::Up,
+        "down" => KeyCode::Down,
+        "left" => KeyCode::Left,
+        "right" => KeyCode::Right,
+        "enter" => KeyCode::Enter,
+        "esc" => KeyCode::Esc,
+        "tab" => KeyCode::Tab,
+        "backspace" => KeyCode::Backspace,
+        "delete" => KeyCode::Delete,
+        "home" => KeyCode::Home,
+        "end" => KeyCode::End,
+        "pageup" => KeyCode::PageUp,
+        "pagedown" => KeyCode::PageDown,
+        "space" => KeyCode::Char(' '),
+        other => KeyCode::F(other.strip_prefix('f')?.parse().ok().filter(|n| (1..=12).contains(n))?),
+    };
+    Some(code)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_bindings() {
+        let keymap = Keymap::default();
+        assert_eq!(keymap.action(KeyCode::Char('k')), Some(Action::Kill));
+        assert_eq!(keymap.action(KeyCode::Down), Some(Action::Down));
+        assert_eq!(keymap.keys(Action::Down), ["↓", "j"]);
+        assert_eq!(keymap.keys(Action::ReplayPause), ["Space"]);
+    }
+
+    #[test]
+    fn overrides_replace_default_keys() {
+        let overrides = KeyBindings::from([(Action::Kill, vec!["x".to_string(), "F9".to_string()])]);
+        let keymap = Keymap::new(&overrides).unwrap();
+
+        assert_eq!(keymap.action(KeyCode::Char('x')), Some(Action::Kill));
+        assert_eq!(keymap.action(KeyCode::Char('k')), None);
+        assert_eq!(keymap.keys(Action::Kill), ["x", "F9"]);
+    }
+
+    #[test]
+    fn unbound_actions_have_no_keys() {
+        let overrides = KeyBindings::from([(Action::Export, Vec::new())]);
+        let keymap = Keymap::new(&overrides).unwrap();
+        assert!(keymap.keys(Action::Export).is_empty());
+    }
+
+    #[test]
+    fn rejects_conflicts_and_unknown_keys() {
+        let conflict = KeyBindings::from([(Action::Kill, vec!["q".to_string()])]);
+        assert!(Keymap::new(&conflict).unwrap_err().contains("already bound"));
+
+        let unknown = KeyBindings::from([(Action::Kill, vec!["F13".to_string()])]);
+        assert!(Keymap::new(&unknown).unwrap_err().contains("unknown key"));
+    }
+}
@@ -1,10 +1,33 @@
+mod alert;
 mod app;
+mod batch;
+mod cli;
+mod collector;
+mod column;
+mod config;
+mod export;
+mod gpu;
+mod hwmon;
+mod keymap;
+#[cfg(feature = "exporter")]
+mod metrics;
+mod priority;
+mod process_tree;
+mod procfs;
+mod replay;
+mod snapshot;
+mod source;
+mod theme;
 mod ui;
 
 use anyhow::Result;
-use app::{App, ProcessAction, SortOrder};
+use app::{App, PriorityField, ProcessAction, ToastLevel};
+use clap::Parser;
+use cli::Cli;
+use column::Column;
+use config::Config;
 use crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode},
+    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEvent},
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
@@ -12,12 +35,92 @@ use ratatui::{
     backend::CrosstermBackend,
     Terminal,
 };
-use std::{
-    io,
-    time::{Duration, Instant},
-};
+use snapshot::Snapshot;
+use keymap::Action;
+use replay::{Recorder, Replay};
+use source::SystemSource;
+use std::{io, sync::Arc, time::Duration};
+use tokio::sync::watch;
+
+/// How long to wait for input before redrawing
+const UI_TICK: Duration = Duration::from_millis(100);
 
 fn main() -> Result<()> {
+    let cli = Cli::parse();
+
+    // Load the config first so errors print on a normal terminal
+    let config_path = cli.config_path();
+    let saved_config = match config_path {
+        Some(ref path) => Config::load(path)?,
+        None => Config::default(),
+    };
+    let mut config = saved_config.clone();
+    cli.apply(&mut config)
+        .map_err(|err| anyhow::anyhow!("invalid options: {}", err))?;
+
+    // A replay feeds recorded snapshots, so there is no collector to start
+    if let Some(ref path) = cli.replay {
+        let replay = Replay::load(path)?;
+        let mut app = App::new(replay.current(), config, config_path);
+        app.set_saved_config(saved_config);
+        app.update(replay.current());
+        app.replay = Some(replay);
+        cli.apply_filters(&mut app);
+        return run_tui(&mut app, None);
+    }
+
+    // Start the background collector before touching the terminal
+    let runtime = tokio::runtime::Runtime::new()?;
+    let (refresh_tx, refresh_rx) = watch::channel(Duration::from_millis(config.refresh_rate_ms));
+    let mut snapshots = collector::spawn(&runtime, Box::new(SystemSource::new()), refresh_rx);
+
+    // Create app state
+    let mut app = App::new(snapshots.borrow_and_update().clone(), config, config_path);
+    app.set_saved_config(saved_config);
+    cli.apply_filters(&mut app);
+    if let Some(ref path) = cli.record {
+        app.recorder = Some(Recorder::open(path)?);
+    }
+
+    // Serve metrics from the same collector as the UI
+    #[cfg(feature = "exporter")]
+    if cli.listen.is_some() || cli.exporter {
+        let addr = match cli.listen {
+            Some(addr) => addr,
+            None => metrics::DEFAULT_ADDR.parse()?,
+        };
+        let addr = metrics::serve(&runtime, addr, snapshots.clone(), cli.metrics_top, cli.exporter)?;
+
+        if cli.exporter {
+            eprintln!("Serving metrics on http://{}/metrics", addr);
+            return Ok(runtime.block_on(tokio::signal::ctrl_c())?);
+        }
+    }
+
+    // Headless modes print to stdout and never touch the terminal
+    if let Some(ref path) = cli.export {
+        let format = cli.format.or_else(|| export::Format::from_path(path)).unwrap_or_default();
+        return export::run(&runtime, &mut snapshots, path, format, cli.iterations);
+    }
+    if cli.batch {
+        return batch::run(&runtime, &mut app, &mut snapshots, cli.iterations, cli.top);
+    }
+
+    let mut live = Live {
+        snapshots: &mut snapshots,
+        refresh_tx: &refresh_tx,
+    };
+    run_tui(&mut app, Some(&mut live))
+}
+
+/// Channels to the background collector
+struct Live<'a> {
+    snapshots: &'a mut watch::Receiver<Arc<Snapshot>>,
+    refresh_tx: &'a watch::Sender<Duration>,
+}
+
+/// Take over the terminal and run the UI until the user quits
+fn run_tui(app: &mut App, live: Option<&mut Live>) -> Result<()> {
     // Setup terminal
     enable_raw_mode()?;
     let mut stdout = io::stdout();
@@ -25,12 +128,8 @@ fn main() -> Result<()> {
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
-    // Create app state
-    let mut app = App::new();
-    let mut last_tick = Instant::now();
-
     // Run main loop
-    let res = run_app(&mut terminal, &mut app, &mut last_tick);
+    let res = run_app(&mut terminal, app, live);
 
     // Restore terminal
     disable_raw_mode()?;
@@ -45,154 +144,51 @@ fn main() -> Result<()> {
         println!("Error: {:?}", err);
     }
 
+    if app.save_on_exit() {
+        if let Err(err) = app.save_config() {
+            eprintln!("Error: {}", err);
+        }
+    }
+
     Ok(())
 }
 
 fn run_app<B: ratatui::backend::Backend>(
     terminal: &mut Terminal<B>,
     app: &mut App,
-    last_tick: &mut Instant,
+    mut live: Option<&mut Live>,
 ) -> Result<()> {
     loop {
+        // Pick up the latest snapshot from the collector, or the replay
+        if let Some(ref mut live) = live {
+            if live.snapshots.has_changed()? {
+                let snapshot = live.snapshots.borrow_and_update().clone();
+                app.update(snapshot);
+            }
+        } else {
+            app.tick_replay();
+        }
+        app.update_toast();
+        app.read_priorities();
+
         // Draw UI
         terminal.draw(|f| ui::render(f, app))?;
 
         // Handle input with timeout
-        let tick_rate = app.get_refresh_duration();
-        let timeout = tick_rate
-            .checked_sub(last_tick.elapsed())
-            .unwrap_or_else(|| Duration::from_secs(0));
-
-        if event::poll(timeout)? {
+        if event::poll(UI_TICK)? {
             if let Event::Key(key) = event::read()? {
-                // Handle confirmation dialog first
-                if app.pending_action.is_some() {
-                    match key.code {
-                        KeyCode::Char('y') | KeyCode::Enter => {
-                            app.execute_action();
-                        }
-                        KeyCode::Char('n') | KeyCode::Esc => {
-                            app.cancel_action();
-                        }
-                        _ => {}
-                    }
-                }
-                // Handle search mode separately
-                else if app.search_mode {
-                    match key.code {
-                        KeyCode::Char(c) => {
-                            app.search_input(c);
-                        }
-                        KeyCode::Backspace => {
-                            app.search_backspace();
-                        }
-                        KeyCode::Enter | KeyCode::Esc => {
-                            app.exit_search();
-                        }
-                        _ => {}
-                    }
-                } else {
-                    match key.code {
-                        KeyCode::Char('?') => {
-                            app.toggle_help();
-                        }
-                        KeyCode::Char('/') => {
-                            if !app.show_help {
-                                app.start_search();
-                            }
-                        }
-                        KeyCode::Char('q') | KeyCode::Char('Q') => {
-                            if !app.show_details && !app.show_help {
-                                app.quit();
-                            }
-                        }
-                        KeyCode::Char('+') | KeyCode::Char('=') => {
-                            if !app.show_help {
-                                app.increase_refresh_rate();
-                            }
-                        }
-                        KeyCode::Char('-') => {
-                            if !app.show_help {
-                                app.decrease_refresh_rate();
-                            }
-                        }
-                        KeyCode::Char('c') => {
-                            if !app.show_help {
-                                app.set_sort_order(SortOrder::Cpu);
-                            }
-                        }
-                        KeyCode::Char('m') => {
-                            if !app.show_help {
-                                app.set_sort_order(SortOrder::Memory);
-                            }
-                        }
-                        KeyCode::Char('p') => {
-                            if !app.show_help {
-                                app.set_sort_order(SortOrder::Pid);
-                            }
-                        }
-                        KeyCode::Char('t') => {
-                            if !app.show_help {
-                                app.set_sort_order(SortOrder::Runtime);
-                            }
-                        }
-                        KeyCode::Down | KeyCode::Char('j') => {
-                            if !app.show_help {
-                                app.next_process();
-                            }
-                        }
-                        KeyCode::Up => {
-                            if !app.show_help {
-                                app.previous_process();
-                            }
-                        }
-                        KeyCode::Char('k') => {
-                            if !app.show_help && !app.show_details {
-                                // 'k' for kill when in normal mode with process selected
-                                if app.selected_process.is_some() {
-                                    app.request_action(ProcessAction::Kill);
-                                } else {
-                                    // Otherwise use for navigation up
-                                    app.previous_process();
-                                }
-                            } else if !app.show_help {
-                                // In details view or help, just navigate
-                                app.previous_process();
-                            }
-                        }
-                        KeyCode::Char('s') => {
-                            if !app.show_help && app.selected_process.is_some() && !app.show_details {
-                                app.request_action(ProcessAction::Suspend);
-                            }
-                        }
-                        KeyCode::Char('r') => {
-                            if !app.show_help && app.selected_process.is_some() && !app.show_details {
-                                app.request_action(ProcessAction::Resume);
-                            }
-                        }
-                        KeyCode::Enter => {
-                            if !app.show_help && app.selected_process.is_some() {
-                                app.toggle_details();
-                            }
-                        }
-                        KeyCode::Esc => {
-                            if app.show_help {
-                                app.toggle_help();
-                            } else if app.show_details {
-                                app.toggle_details();
-                            }
-                        }
-                        _ => {}
-                    }
-                }
-            }
-        }
-
-        // Update data periodically
-        let tick_rate = app.get_refresh_duration();
-        if last_tick.elapsed() >= tick_rate {
-            app.update();
-            *last_tick = Instant::now();
+                handle_key(app, key);
+            }
+        }
+
+        // Let the collector know about refresh rate changes
+        if let Some(ref live) = live {
+            let refresh_rate = app.get_refresh_duration();
+            live.refresh_tx.send_if_modified(|current| {
+                let changed = *current != refresh_rate;
+                *current = refresh_rate;
+                changed
+            });
         }
 
         // Check if should quit
@@ -203,3 +199,251 @@ fn run_app<B: ratatui::backend::Backend>(
 
     Ok(())
 }
+
+/// Handle a single key press
+fn handle_key(app: &mut App, key: KeyEvent) {
+    // Handle confirmation dialog first
+    if app.pending_action.is_some() {
+        match key.code {
+            KeyCode::Char('y') | KeyCode::Enter => {
+                app.execute_action();
+            }
+            KeyCode::Char('n') | KeyCode::Esc => {
+                app.cancel_action();
+            }
+            _ => {}
+        }
+    }
+    // Column editor captures keys while open
+    else if app.show_column_editor {
+        match key.code {
+            KeyCode::Down | KeyCode::Char('j') => {
+                app.move_column_cursor(true);
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                app.move_column_cursor(false);
+            }
+            KeyCode::Char(' ') | KeyCode::Enter => {
+                app.toggle_column();
+            }
+            KeyCode::Char('J') => {
+                app.move_column(true);
+            }
+            KeyCode::Char('K') => {
+                app.move_column(false);
+            }
+            KeyCode::Char('o') | KeyCode::Esc => {
+                app.toggle_column_editor();
+            }
+            _ => {}
+        }
+    }
+    // Signal menu captures keys while open
+    else if app.show_signal_menu {
+        match key.code {
+            KeyCode::Down | KeyCode::Char('j') => {
+                app.move_signal_cursor(true);
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                app.move_signal_cursor(false);
+            }
+            KeyCode::Enter => {
+                app.choose_signal();
+            }
+            KeyCode::Char('t') => {
+                app.toggle_signal_tree();
+            }
+            KeyCode::Char('K') | KeyCode::Esc => {
+                app.close_signal_menu();
+            }
+            _ => {}
+        }
+    }
+    // Priority input captures keys while open
+    else if app.priority_input.is_some() {
+        match key.code {
+            KeyCode::Char(c) => {
+                app.priority_input_char(c);
+            }
+            KeyCode::Backspace => {
+                app.priority_input_backspace();
+            }
+            KeyCode::Up => {
+                app.adjust_priority(true);
+            }
+            KeyCode::Down => {
+                app.adjust_priority(false);
+            }
+            KeyCode::Left => {
+                app.cycle_io_class(false);
+            }
+            KeyCode::Right | KeyCode::Tab => {
+                app.cycle_io_class(true);
+            }
+            KeyCode::Enter => {
+                app.apply_priority();
+            }
+            KeyCode::Esc => {
+                app.close_priority_input();
+            }
+            _ => {}
+        }
+    }
+    // Handle search mode separately
+    else if app.search_mode {
+        match key.code {
+            KeyCode::Char(c) => {
+                app.search_input(c);
+            }
+            KeyCode::Backspace => {
+                app.search_backspace();
+            }
+            KeyCode::Enter | KeyCode::Esc => {
+                app.exit_search();
+            }
+            _ => {}
+        }
+    } else if key.code == KeyCode::Esc {
+        if app.show_help {
+            app.toggle_help();
+        } else if app.show_details {
+            app.toggle_details();
+        } else {
+            app.clear_marks();
+        }
+    } else if let Some(action) = app.keymap.action(key.code) {
+        // Only the help toggle works while help is open
+        if app.show_help && action != Action::Help {
+            return;
+        }
+        handle_action(app, action);
+    }
+}
+
+fn handle_action(app: &mut App, action: Action) {
+    match action {
+        Action::Help => {
+            app.toggle_help();
+        }
+        Action::Search => {
+            app.start_search();
+        }
+        Action::Quit if !app.show_details => {
+            app.quit();
+        }
+        Action::FasterRefresh => {
+            app.increase_refresh_rate();
+        }
+        Action::SlowerRefresh => {
+            app.decrease_refresh_rate();
+        }
+        Action::SortCpu => {
+            app.sort_by(Column::Cpu);
+        }
+        Action::SortMemory => {
+            app.sort_by(Column::Memory);
+        }
+        Action::SortPid => {
+            app.sort_by(Column::Pid);
+        }
+        Action::SortRuntime => {
+            app.sort_by(Column::RunTime);
+        }
+        Action::SortPrevColumn => {
+            app.cycle_sort_column(false);
+        }
+        Action::SortNextColumn => {
+            app.cycle_sort_column(true);
+        }
+        Action::ColumnEditor => {
+            app.toggle_column_editor();
+        }
+        Action::TreeView => {
+            app.toggle_tree_view();
+        }
+        Action::Pin => {
+            app.toggle_pin();
+        }
+        Action::Mark => {
+            app.toggle_mark();
+        }
+        Action::MarkMatching => {
+            app.mark_matching();
+        }
+        Action::Collapse => {
+            app.collapse_selected();
+        }
+        Action::Expand => {
+            app.expand_selected();
+        }
+        Action::NextInterface => {
+            app.next_interface();
+        }
+        Action::NextGpu => {
+            app.next_gpu();
+        }
+        Action::Down => {
+            app.next_process();
+        }
+        Action::Up => {
+            app.previous_process();
+        }
+        Action::Kill => {
+            if !app.show_details && app.has_action_targets() {
+                // 'k' for kill when in normal mode with processes to act on
+                app.request_action(ProcessAction::Kill);
+            } else {
+                // Otherwise use for navigation up
+                app.previous_process();
+            }
+        }
+        Action::KillTree if app.has_action_targets() && !app.show_details => {
+            app.request_tree_action(ProcessAction::Kill);
+        }
+        Action::SignalMenu if app.has_action_targets() && !app.show_details => {
+            app.open_signal_menu();
+        }
+        Action::Suspend if app.has_action_targets() && !app.show_details => {
+            app.request_action(ProcessAction::Suspend);
+        }
+        Action::Resume if app.has_action_targets() && !app.show_details => {
+            app.request_action(ProcessAction::Resume);
+        }
+        Action::Renice if app.has_action_targets() && !app.show_details => {
+            app.open_priority_input(PriorityField::Nice);
+        }
+        Action::IoPriority if app.has_action_targets() && !app.show_details => {
+            app.open_priority_input(PriorityField::Io);
+        }
+        Action::Details if app.selected_process.is_some() => {
+            app.toggle_details();
+        }
+        Action::ReplayPause => {
+            app.toggle_replay_pause();
+        }
+        Action::ReplaySlower => {
+            app.change_replay_speed(false);
+        }
+        Action::ReplayFaster => {
+            app.change_replay_speed(true);
+        }
+        Action::ReplayBack => {
+            app.seek_replay(false);
+        }
+        Action::ReplayForward => {
+            app.seek_replay(true);
+        }
+        Action::AlertLog => {
+            app.toggle_alerts_panel();
+        }
+        Action::Export => match app.export_snapshot() {
+            Ok(msg) => app.show_toast(msg, ToastLevel::Success),
+            Err(err) => app.show_toast(err, ToastLevel::Error),
+        },
+        Action::SaveConfig => match app.save_config() {
+            Ok(msg) => app.show_toast(msg, ToastLevel::Success),
+            Err(err) => app.show_toast(err, ToastLevel::Error),
+        },
+        _ => {}
+    }
+}
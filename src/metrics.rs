@@ -0,0 +1,407 @@
+use crate::column::Column;
+use crate::snapshot::{GpuInfo, ProcessInfo, Snapshot};
+use anyhow::{Context, Result};
+use std::{fmt::Write as _, net::SocketAddr, sync::Arc, time::Duration};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{TcpListener, TcpStream},
+    runtime::Runtime,
+    sync::watch,
+};
+
+/// Address used by `--exporter` when `--listen` isn't given
+pub const DEFAULT_ADDR: &str = "127.0.0.1:9184";
+
+const CONTENT_TYPE: &str = "application/openmetrics-text; version=1.0.0; charset=utf-8";
+
+/// Give up on clients that don't send a request line in time
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Pause after a failed accept, such as running out of file descriptors,
+/// instead of retrying in a busy loop
+const ACCEPT_BACKOFF: Duration = Duration::from_millis(100);
+
+/// Bind `addr` and serve the latest snapshot on `/metrics` in the background.
+///
+/// Binding happens before returning so a taken port is reported up front.
+/// Accept errors go to stderr when `log_errors` is set; leave it off while
+/// the UI owns the terminal.
+pub fn serve(
+    runtime: &Runtime,
+    addr: SocketAddr,
+    snapshots: watch::Receiver<Arc<Snapshot>>,
+    top: usize,
+    log_errors: bool,
+) -> Result<SocketAddr> {
+    let listener = runtime
+        .block_on(TcpListener::bind(addr))
+        .with_context(|| format!("failed to listen on {}", addr))?;
+    let local_addr = listener.local_addr()?;
+
+    runtime.spawn(async move {
+        loop {
+            let stream = match listener.accept().await {
+                Ok((stream, _)) => stream,
+                Err(err) => {
+                    if log_errors {
+                        eprintln!("Failed to accept a metrics connection: {}", err);
+                    }
+                    tokio::time::sleep(ACCEPT_BACKOFF).await;
+                    continue;
+                }
+            };
+            let snapshots = snapshots.clone();
+            tokio::spawn(async move {
+                let _ = tokio::time::timeout(REQUEST_TIMEOUT, respond(stream, &snapshots, top)).await;
+            });
+        }
+    });
+
+    Ok(local_addr)
+}
+
+/// Answer one HTTP request and close the connection
+async fn respond(
+    mut stream: TcpStream,
+    snapshots: &watch::Receiver<Arc<Snapshot>>,
+    top: usize,
+) -> std::io::Result<()> {
+    // Only the request line matters; read until the end of the headers
+    let mut request = Vec::new();
+    let mut buf = [0u8; 1024];
+    while !request.windows(4).any(|w| w == b"\r\n\r\n") && request.len() < 8192 {
+        let read = stream.read(&mut buf).await?;
+        if read == 0 {
+            break;
+        }
+        request.extend_from_slice(&buf[..read]);
+    }
+
+    let request = String::from_utf8_lossy(&request);
+    let mut parts = request.split_whitespace();
+    let (method, path) = (parts.next().unwrap_or(""), parts.next().unwrap_or(""));
+
+    let (status, content_type, body) = match (method, path.split('?').next()) {
+        ("GET", Some("/metrics")) => {
+            let snapshot = snapshots.borrow().clone();
+            ("200 OK", CONTENT_TYPE, render(&snapshot, top))
+        }
+        ("GET", _) => ("404 Not Found", "text/plain", "Not found; try /metrics\n".to_string()),
+        _ => ("405 Method Not Allowed", "text/plain", "Only GET is supported\n".to_string()),
+    };
+
+    let response = format!(
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        content_type,
+        body.len(),
+        body
+    );
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await
+}
+
+/// One metric family being written
+struct Family<'a> {
+    out: &'a mut String,
+    name: &'static str,
+}
+
+impl Family<'_> {
+    /// Add a sample with `labels` as (name, value) pairs
+    fn sample(&mut self, labels: &[(&str, &str)], value: f64) {
+        self.out.push_str(self.name);
+        if !labels.is_empty() {
+            let labels: Vec<String> = labels
+                .iter()
+                .map(|(name, value)| format!("{}=\"{}\"", name, escape(value)))
+                .collect();
+            let _ = write!(self.out, "{{{}}}", labels.join(","));
+        }
+        let _ = writeln!(self.out, " {}", number(value));
+    }
+}
+
+/// Format a sample value; OpenMetrics spells non-finite values `NaN`,
+/// `+Inf` and `-Inf`, unlike `f64`'s `Display`
+fn number(value: f64) -> String {
+    if value.is_nan() {
+        "NaN".to_string()
+    } else if value.is_infinite() {
+        if value > 0.0 { "+Inf" } else { "-Inf" }.to_string()
+    } else {
+        value.to_string()
+    }
+}
+
+/// Start a gauge family; `unit` must match the name's suffix
+fn gauge<'a>(out: &'a mut String, name: &'static str, unit: Option<&str>, help: &str) -> Family<'a> {
+    let _ = writeln!(out, "# TYPE {} gauge", name);
+    if let Some(unit) = unit {
+        let _ = writeln!(out, "# UNIT {} {}", name, unit);
+    }
+    let _ = writeln!(out, "# HELP {} {}", name, help);
+    Family { out, name }
+}
+
+/// Escape a label value
+fn escape(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+/// Reads one GPU metric; `None` when the GPU doesn't report it
+type GpuValue = fn(&GpuInfo) -> Option<f64>;
+
+/// Render a snapshot in the OpenMetrics text format.
+///
+/// Percentages are reported as the UI shows them (0-100). Processes are the
+/// union of the `top` busiest by CPU and the `top` largest by memory.
+pub fn render(snapshot: &Snapshot, top: usize) -> String {
+    let mut out = String::new();
+
+    gauge(&mut out, "rtui_cpu_usage_percent", None, "Overall CPU usage.")
+        .sample(&[], snapshot.global_cpu_usage as f64);
+
+    let cores: Vec<String> = (0..snapshot.cpus.len()).map(|i| i.to_string()).collect();
+    let mut family = gauge(&mut out, "rtui_cpu_core_usage_percent", None, "CPU usage per core.");
+    for (cpu, core) in snapshot.cpus.iter().zip(&cores) {
+        family.sample(&[("core", core)], cpu.usage as f64);
+    }
+    let mut family = gauge(
+        &mut out,
+        "rtui_cpu_core_frequency_hertz",
+        Some("hertz"),
+        "CPU frequency per core.",
+    );
+    for (cpu, core) in snapshot.cpus.iter().zip(&cores) {
+        family.sample(&[("core", core)], cpu.frequency as f64 * 1_000_000.0);
+    }
+
+    let memory = &snapshot.memory;
+    let bytes = [
+        ("rtui_memory_total_bytes", "Total memory.", memory.total),
+        ("rtui_memory_used_bytes", "Used memory.", memory.used),
+        ("rtui_memory_available_bytes", "Available memory.", memory.available),
+        ("rtui_swap_total_bytes", "Total swap.", memory.swap_total),
+        ("rtui_swap_used_bytes", "Used swap.", memory.swap_used),
+    ];
+    for (name, help, value) in bytes {
+        gauge(&mut out, name, Some("bytes"), help).sample(&[], value as f64);
+    }
+
+    if !snapshot.gpus.is_empty() {
+        let indexes: Vec<String> = snapshot.gpus.iter().map(|gpu| gpu.index.to_string()).collect();
+        let gpus: Vec<_> = snapshot.gpus.iter().zip(&indexes).collect();
+        let families: [(&str, Option<&str>, &str, GpuValue); 13] = [
+            ("rtui_gpu_usage_percent", None, "GPU utilization.", |gpu| Some(gpu.usage as f64)),
+            ("rtui_gpu_memory_used_bytes", Some("bytes"), "Used GPU memory.", |gpu| {
+                Some(gpu.memory_used as f64)
+            }),
+            ("rtui_gpu_memory_total_bytes", Some("bytes"), "Total GPU memory.", |gpu| {
+                Some(gpu.memory_total as f64)
+            }),
+            ("rtui_gpu_temperature_celsius", Some("celsius"), "GPU temperature.", |gpu| {
+                Some(gpu.temperature as f64)
+            }),
+            ("rtui_gpu_power_draw_watts", Some("watts"), "GPU power draw.", |gpu| {
+                gpu.power_draw.map(f64::from)
+            }),
+            ("rtui_gpu_power_limit_watts", Some("watts"), "GPU power limit.", |gpu| {
+                gpu.power_limit.map(f64::from)
+            }),
+            ("rtui_gpu_sm_clock_hertz", Some("hertz"), "GPU SM clock.", |gpu| {
+                gpu.sm_clock.map(|mhz| mhz as f64 * 1_000_000.0)
+            }),
+            ("rtui_gpu_memory_clock_hertz", Some("hertz"), "GPU memory clock.", |gpu| {
+                gpu.memory_clock.map(|mhz| mhz as f64 * 1_000_000.0)
+            }),
+            ("rtui_gpu_fan_speed_percent", None, "GPU fan speed.", |gpu| {
+                gpu.fan_speed.map(f64::from)
+            }),
+            ("rtui_gpu_encoder_usage_percent", None, "GPU video encoder utilization.", |gpu| {
+                gpu.encoder_usage.map(f64::from)
+            }),
+            ("rtui_gpu_decoder_usage_percent", None, "GPU video decoder utilization.", |gpu| {
+                gpu.decoder_usage.map(f64::from)
+            }),
+            (
+                "rtui_gpu_pcie_receive_bytes_per_second",
+                Some("bytes_per_second"),
+                "GPU PCIe receive rate.",
+                |gpu| gpu.pcie_rx_rate,
+            ),
+            (
+                "rtui_gpu_pcie_transmit_bytes_per_second",
+                Some("bytes_per_second"),
+                "GPU PCIe transmit rate.",
+                |gpu| gpu.pcie_tx_rate,
+            ),
+        ];
+        for (name, unit, help, value) in families {
+            // Leave out families no GPU reports, such as power on AMD and Intel
+            if gpus.iter().all(|(gpu, _)| value(gpu).is_none()) {
+                continue;
+            }
+            let mut family = gauge(&mut out, name, unit, help);
+            for &(gpu, index) in &gpus {
+                if let Some(value) = value(gpu) {
+                    family.sample(&[("gpu", index), ("name", &gpu.name)], value);
+                }
+            }
+        }
+
+        let mut family = gauge(
+            &mut out,
+            "rtui_gpu_throttle_reason",
+            None,
+            "Active GPU clock throttle reasons, one sample per reason.",
+        );
+        for &(gpu, index) in &gpus {
+            for reason in &gpu.throttle_reasons {
+                family.sample(&[("gpu", index), ("name", &gpu.name), ("reason", reason)], 1.0);
+            }
+        }
+    }
+
+    let processes = top_processes(snapshot, top);
+    let pids: Vec<String> = processes.iter().map(|p| p.pid.to_string()).collect();
+    let mut family = gauge(
+        &mut out,
+        "rtui_process_cpu_usage_percent",
+        None,
+        "CPU usage of top processes.",
+    );
+    for (process, pid) in processes.iter().zip(&pids) {
+        family.sample(&[("pid", pid), ("name", &process.name)], process.cpu_usage as f64);
+    }
+    let mut family = gauge(
+        &mut out,
+        "rtui_process_memory_bytes",
+        Some("bytes"),
+        "Resident memory of top processes.",
+    );
+    for (process, pid) in processes.iter().zip(&pids) {
+        family.sample(&[("pid", pid), ("name", &process.name)], process.memory as f64);
+    }
+
+    out.push_str("# EOF\n");
+    out
+}
+
+/// The `top` processes by CPU, plus any of the `top` by memory not already in
+fn top_processes(snapshot: &Snapshot, top: usize) -> Vec<&ProcessInfo> {
+    let largest = |column: Column| {
+        let mut processes: Vec<&ProcessInfo> = snapshot.processes.iter().collect();
+        processes.sort_by(|a, b| column.compare(a, b).reverse().then_with(|| a.pid.cmp(&b.pid)));
+        processes.truncate(top);
+        processes
+    };
+
+    let mut processes = largest(Column::Cpu);
+    for process in largest(Column::Memory) {
+        if !processes.iter().any(|p| p.pid == process.pid) {
+            processes.push(process);
+        }
+    }
+    processes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::snapshot::{CpuInfo, GpuInfo, ProcessInfo};
+    use std::collections::HashSet;
+
+    fn process(pid: u32, name: &str, cpu_usage: f32, memory: u64) -> ProcessInfo {
+        ProcessInfo {
+            cpu_usage,
+            memory,
+            ..ProcessInfo::fixture(pid, None, name)
+        }
+    }
+
+    fn snapshot() -> Snapshot {
+        Snapshot {
+            cpus: vec![CpuInfo { usage: 25.0, frequency: 3000 }; 2],
+            global_cpu_usage: 25.0,
+            gpus: vec![GpuInfo {
+                index: 0,
+                name: "GPU \"A\"".to_string(),
+                usage: 50.0,
+                throttle_reasons: vec!["thermal".to_string()],
+                ..Default::default()
+            }],
+            processes: vec![
+                process(1, "busy", 90.0, 10),
+                process(2, "big", 1.0, 1 << 30),
+                process(3, "tricky\\name\n\"x\"", 50.0, 20),
+                process(4, "idle", 0.0, 5),
+            ],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn ends_with_eof() {
+        let output = render(&snapshot(), 2);
+        assert!(output.ends_with("\n# EOF\n"));
+        assert_eq!(output.matches("# EOF").count(), 1);
+    }
+
+    #[test]
+    fn declares_each_family_once_before_its_samples() {
+        let output = render(&snapshot(), 2);
+        let mut declared = HashSet::new();
+
+        for line in output.lines() {
+            if let Some(rest) = line.strip_prefix("# TYPE ") {
+                let name = rest.split(' ').next().unwrap();
+                assert!(declared.insert(name.to_string()), "{} declared twice", name);
+            } else if !line.starts_with('#') {
+                let name = line.split(['{', ' ']).next().unwrap();
+                assert!(declared.contains(name), "{} sampled before its TYPE", name);
+            }
+        }
+        assert!(declared.contains("rtui_gpu_usage_percent"));
+        // No GPU reports power, so the family is left out
+        assert!(!declared.contains("rtui_gpu_power_draw_watts"));
+    }
+
+    #[test]
+    fn escapes_label_values() {
+        let output = render(&snapshot(), 2);
+        assert!(output.contains(r#"rtui_gpu_usage_percent{gpu="0",name="GPU \"A\""} 50"#));
+        assert!(output.contains(r#"rtui_process_cpu_usage_percent{pid="3",name="tricky\\name\n\"x\""} 50"#));
+    }
+
+    #[test]
+    fn reports_the_top_processes_by_cpu_and_memory() {
+        let output = render(&snapshot(), 1);
+        let pids: Vec<&str> = output
+            .lines()
+            .filter(|line| line.starts_with("rtui_process_memory_bytes{"))
+            .map(|line| line.split('"').nth(1).unwrap())
+            .collect();
+        assert_eq!(pids, ["1", "2"]);
+        assert!(output.contains(r#"rtui_cpu_core_frequency_hertz{core="1"} 3000000000"#));
+    }
+
+    #[test]
+    fn spells_non_finite_values_the_openmetrics_way() {
+        let mut snapshot = snapshot();
+        snapshot.global_cpu_usage = f32::NAN;
+        snapshot.cpus[0].usage = f32::INFINITY;
+        snapshot.cpus[1].usage = f32::NEG_INFINITY;
+        snapshot.gpus[0].pcie_rx_rate = Some(-0.5);
+
+        let output = render(&snapshot, 2);
+        assert!(output.contains("rtui_cpu_usage_percent NaN\n"));
+        assert!(output.contains("rtui_cpu_core_usage_percent{core=\"0\"} +Inf\n"));
+        assert!(output.contains("rtui_cpu_core_usage_percent{core=\"1\"} -Inf\n"));
+        assert!(output.contains(r#"rtui_gpu_pcie_receive_bytes_per_second{gpu="0",name="GPU \"A\""} -0.5"#));
+        assert!(!output.contains("inf"));
+    }
+}
@@ -0,0 +1,255 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Lowest and highest nice values
+pub const NICE_RANGE: (i32, i32) = (-20, 19);
+
+/// Highest I/O priority level; 0 is the most favoured
+pub const IO_LEVEL_MAX: u8 = 7;
+
+/// I/O scheduling class, as set by `ionice -c`
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IoClass {
+    /// No class set; the kernel derives best-effort from the nice value
+    None,
+    RealTime,
+    BestEffort,
+    /// Only gets disk time when no one else wants it
+    Idle,
+}
+
+impl IoClass {
+    /// Classes that can be set, in the order the input overlay cycles them
+    pub const SETTABLE: [IoClass; 3] = [IoClass::RealTime, IoClass::BestEffort, IoClass::Idle];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            IoClass::None => "none",
+            IoClass::RealTime => "realtime",
+            IoClass::BestEffort => "best-effort",
+            IoClass::Idle => "idle",
+        }
+    }
+
+    /// Whether the class takes a level
+    pub fn has_level(self) -> bool {
+        matches!(self, IoClass::RealTime | IoClass::BestEffort)
+    }
+}
+
+/// I/O scheduling class and level of a process
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct IoPriority {
+    pub class: IoClass,
+    /// 0 (highest) to 7; ignored for the idle class
+    pub level: u8,
+}
+
+impl fmt::Display for IoPriority {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.class.has_level() {
+            write!(f, "{} {}", self.class.label(), self.level)
+        } else {
+            f.write_str(self.class.label())
+        }
+    }
+}
+
+/// Set the nice value of every thread of a process
+#[cfg(target_family = "unix")]
+pub fn set_nice(pid: u32, nice: i32) -> Result<(), String> {
+    apply_to_threads(pid, &threads(pid), "set the nice value of", |tid| {
+        // SAFETY: setpriority takes plain integers and has no memory effects
+        match unsafe { libc::setpriority(libc::PRIO_PROCESS, tid as libc::id_t, nice) } {
+            0 => Ok(()),
+            _ => Err(std::io::Error::last_os_error()),
+        }
+    })
+}
+
+#[cfg(not(target_family = "unix"))]
+pub fn set_nice(_pid: u32, _nice: i32) -> Result<(), String> {
+    Err("Changing the nice value is not supported on Windows".to_string())
+}
+
+/// `which` argument of ioprio_set/ioprio_get for a single thread
+#[cfg(target_os = "linux")]
+const IOPRIO_WHO_PROCESS: libc::c_int = 1;
+
+/// Bits of an ioprio value below the class
+#[cfg(target_os = "linux")]
+const IOPRIO_CLASS_SHIFT: u32 = 13;
+
+/// Set the I/O scheduling class and level of every thread of a process
+#[cfg(target_os = "linux")]
+pub fn set_io_priority(pid: u32, priority: IoPriority) -> Result<(), String> {
+    let class = match priority.class {
+        IoClass::None => 0,
+        IoClass::RealTime => 1,
+        IoClass::BestEffort => 2,
+        IoClass::Idle => 3,
+    };
+    let value = (class << IOPRIO_CLASS_SHIFT) | priority.level.min(IO_LEVEL_MAX) as libc::c_int;
+
+    apply_to_threads(pid, &threads(pid), "set the I/O priority of", |tid| {
+        // SAFETY: ioprio_set takes plain integers and has no memory effects
+        match unsafe { libc::syscall(libc::SYS_ioprio_set, IOPRIO_WHO_PROCESS, tid, value) } {
+            0 => Ok(()),
+            _ => Err(std::io::Error::last_os_error()),
+        }
+    })
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn set_io_priority(_pid: u32, _priority: IoPriority) -> Result<(), String> {
+    Err("I/O priority is only supported on Linux".to_string())
+}
+
+/// I/O scheduling class and level of a process's main thread
+#[cfg(target_os = "linux")]
+pub fn io_priority(pid: u32) -> Option<IoPriority> {
+    // SAFETY: ioprio_get takes plain integers and has no memory effects
+    let value = unsafe { libc::syscall(libc::SYS_ioprio_get, IOPRIO_WHO_PROCESS, pid) };
+    if value < 0 {
+        return None;
+    }
+
+    let class = match value >> IOPRIO_CLASS_SHIFT {
+        1 => IoClass::RealTime,
+        2 => IoClass::BestEffort,
+        3 => IoClass::Idle,
+        _ => IoClass::None,
+    };
+    let level = (value & ((1 << IOPRIO_CLASS_SHIFT) - 1)) as u8;
+    Some(IoPriority { class, level })
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn io_priority(_pid: u32) -> Option<IoPriority> {
+    None
+}
+
+/// Thread IDs of a process; nice values and I/O priorities are per thread
+/// on Linux, so the whole process only changes if every thread does
+#[cfg(target_family = "unix")]
+fn threads(pid: u32) -> Vec<u32> {
+    let Ok(entries) = std::fs::read_dir(format!("/proc/{}/task", pid)) else {
+        return vec![pid];
+    };
+
+    let mut tids: Vec<u32> = entries
+        .filter_map(|entry| entry.ok()?.file_name().to_str()?.parse().ok())
+        .collect();
+    if tids.is_empty() {
+        tids.push(pid);
+    }
+    tids
+}
+
+/// Run `apply` on every thread in `tids`, carrying on past failures.
+///
+/// Worker threads that exit before their turn (ESRCH) are skipped; the
+/// process itself going away is still an error. When some threads changed
+/// and others didn't, the error says how many did.
+#[cfg(target_family = "unix")]
+fn apply_to_threads(
+    pid: u32,
+    tids: &[u32],
+    what: &str,
+    apply: impl Fn(u32) -> std::io::Result<()>,
+) -> Result<(), String> {
+    let (mut applied, mut exited) = (0, 0);
+    let mut error = None;
+
+    for &tid in tids {
+        match apply(tid) {
+            Ok(()) => applied += 1,
+            Err(err) if tid != pid && err.raw_os_error() == Some(libc::ESRCH) => exited += 1,
+            Err(err) => {
+                error.get_or_insert(err);
+            }
+        }
+    }
+
+    match error {
+        None => Ok(()),
+        Some(err) if applied == 0 => Err(os_error(pid, what, &err)),
+        Some(err) => Err(format!(
+            "{}; applied to {} of {} threads",
+            os_error(pid, what, &err),
+            applied,
+            tids.len() - exited
+        )),
+    }
+}
+
+/// Describe an OS error, with a hint for the usual permission problem
+#[cfg(target_family = "unix")]
+fn os_error(pid: u32, what: &str, err: &std::io::Error) -> String {
+    match err.raw_os_error() {
+        Some(libc::EPERM) | Some(libc::EACCES) => format!(
+            "Permission denied: can't {} process {} (raising priority needs root)",
+            what, pid
+        ),
+        Some(libc::ESRCH) => format!("Process {} not found", pid),
+        _ => format!("Failed to {} process {}: {}", what, pid, err),
+    }
+}
+
+#[cfg(all(test, target_family = "unix"))]
+mod tests {
+    use super::*;
+    use std::io;
+
+    fn fail_with(code: i32) -> io::Result<()> {
+        Err(io::Error::from_raw_os_error(code))
+    }
+
+    #[test]
+    fn renicing_this_process_to_its_current_value_succeeds() {
+        // Keep a second thread alive so there's more than one to change
+        let (stop, wait) = std::sync::mpsc::channel::<()>();
+        let worker = std::thread::spawn(move || wait.recv());
+
+        // SAFETY: getpriority takes plain integers and has no memory effects
+        let nice = unsafe { libc::getpriority(libc::PRIO_PROCESS, 0) };
+        assert_eq!(set_nice(std::process::id(), nice), Ok(()));
+
+        drop(stop);
+        let _ = worker.join();
+    }
+
+    #[test]
+    fn skips_threads_that_have_exited() {
+        let result = apply_to_threads(10, &[10, 11, 12], "renice", |tid| match tid {
+            11 => fail_with(libc::ESRCH),
+            _ => Ok(()),
+        });
+        assert_eq!(result, Ok(()));
+    }
+
+    #[test]
+    fn reports_the_process_itself_being_gone() {
+        let result = apply_to_threads(10, &[10], "renice", |_| fail_with(libc::ESRCH));
+        assert_eq!(result, Err("Process 10 not found".to_string()));
+    }
+
+    #[test]
+    fn reports_how_many_threads_changed_after_a_partial_failure() {
+        let result = apply_to_threads(10, &[10, 11, 12, 13], "renice", |tid| match tid {
+            11 => fail_with(libc::EPERM),
+            12 => fail_with(libc::ESRCH),
+            _ => Ok(()),
+        });
+        let err = result.unwrap_err();
+        assert!(err.starts_with("Permission denied: can't renice process 10"), "{}", err);
+        assert!(err.ends_with("; applied to 2 of 3 threads"), "{}", err);
+    }
+
+    #[test]
+    fn plain_error_when_no_thread_changed() {
+        let result = apply_to_threads(10, &[10, 11], "renice", |_| fail_with(libc::EPERM));
+        assert!(!result.unwrap_err().contains("applied to"));
+    }
+}
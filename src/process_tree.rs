@@ -0,0 +1,352 @@
+use crate::snapshot::ProcessInfo;
+use std::collections::{HashMap, HashSet};
+use sysinfo::Pid;
+
+/// One line of the process list, flat or nested
+#[derive(Debug, Clone)]
+pub struct ProcessRow<'a> {
+    pub process: &'a ProcessInfo,
+    /// Indentation guides drawn before the name (empty in flat mode)
+    pub prefix: String,
+    /// Whether the process has children in the tree
+    pub has_children: bool,
+    /// Whether the subtree under this process is collapsed
+    pub collapsed: bool,
+    /// CPU usage of the process and all its descendants
+    pub subtree_cpu: f32,
+    /// Resident memory of the process and all its descendants
+    pub subtree_memory: u64,
+}
+
+impl<'a> ProcessRow<'a> {
+    /// Row for the flat list
+    pub fn flat(process: &'a ProcessInfo) -> Self {
+        Self {
+            process,
+            prefix: String::new(),
+            has_children: false,
+            collapsed: false,
+            subtree_cpu: process.cpu_usage,
+            subtree_memory: process.memory,
+        }
+    }
+}
+
+/// Nest processes under their parents.
+///
+/// `sorted` fixes the order of siblings at every level. A process is kept if
+/// it matches `filter` or any of its descendants do, so search results still
+/// show where they live in the tree. Children of `collapsed` PIDs are hidden
+/// but still count towards the subtree totals.
+pub fn build_rows<'a>(
+    sorted: &[&'a ProcessInfo],
+    collapsed: &HashSet<Pid>,
+    filter: impl Fn(&ProcessInfo) -> bool,
+) -> Vec<ProcessRow<'a>> {
+    let present: HashSet<Pid> = sorted.iter().map(|p| p.pid).collect();
+
+    let mut children: HashMap<Pid, Vec<&'a ProcessInfo>> = HashMap::new();
+    let mut roots = Vec::new();
+    for process in sorted {
+        match process.parent {
+            Some(parent) if parent != process.pid && present.contains(&parent) => {
+                children.entry(parent).or_default().push(process);
+            }
+            _ => roots.push(*process),
+        }
+    }
+
+    // PID reuse can leave a parent cycle with no root; break it at its first
+    // process in sort order so the cycle still shows up
+    let mut reached = HashSet::new();
+    for root in &roots {
+        reach(root.pid, &children, &mut reached);
+    }
+    for process in sorted {
+        if reached.contains(&process.pid) {
+            continue;
+        }
+        if let Some(siblings) = process.parent.and_then(|parent| children.get_mut(&parent)) {
+            siblings.retain(|sibling| sibling.pid != process.pid);
+        }
+        roots.push(*process);
+        reach(process.pid, &children, &mut reached);
+    }
+
+    let mut tree = Tree {
+        children,
+        collapsed,
+        totals: HashMap::new(),
+        visible: HashMap::new(),
+    };
+    for root in &roots {
+        tree.compute(root, &filter, &mut HashSet::new());
+    }
+
+    let mut rows = Vec::new();
+    let visible_roots: Vec<_> = roots.into_iter().filter(|r| tree.visible[&r.pid]).collect();
+    for (i, root) in visible_roots.iter().enumerate() {
+        tree.walk(root, "", i + 1 == visible_roots.len(), true, &mut rows);
+    }
+    rows
+}
+
+/// Mark `pid` and everything below it as reached
+fn reach(pid: Pid, children: &HashMap<Pid, Vec<&ProcessInfo>>, reached: &mut HashSet<Pid>) {
+    let mut queue = vec![pid];
+    while let Some(parent) = queue.pop() {
+        if reached.insert(parent) {
+            queue.extend(children.get(&parent).into_iter().flatten().map(|child| child.pid));
+        }
+    }
+}
+
+struct Tree<'a, 'c> {
+    children: HashMap<Pid, Vec<&'a ProcessInfo>>,
+    collapsed: &'c HashSet<Pid>,
+    /// Subtree CPU and memory per PID
+    totals: HashMap<Pid, (f32, u64)>,
+    /// Whether a PID or any descendant matches the filter
+    visible: HashMap<Pid, bool>,
+}
+
+impl<'a> Tree<'a, '_> {
+    /// Fill in subtree totals and visibility bottom-up
+    fn compute(
+        &mut self,
+        process: &'a ProcessInfo,
+        filter: &impl Fn(&ProcessInfo) -> bool,
+        path: &mut HashSet<Pid>,
+    ) -> ((f32, u64), bool) {
+        // Guard against PID reuse creating a parent cycle
+        if !path.insert(process.pid) {
+            return ((0.0, 0), false);
+        }
+
+        let mut cpu = process.cpu_usage;
+        let mut memory = process.memory;
+        let mut visible = filter(process);
+
+        let children = self.children.get(&process.pid).cloned().unwrap_or_default();
+        for child in children {
+            let ((child_cpu, child_memory), child_visible) = self.compute(child, filter, path);
+            cpu += child_cpu;
+            memory += child_memory;
+            visible |= child_visible;
+        }
+
+        path.remove(&process.pid);
+        self.totals.insert(process.pid, (cpu, memory));
+        self.visible.insert(process.pid, visible);
+        ((cpu, memory), visible)
+    }
+
+    /// Emit rows depth-first with box-drawing guides
+    fn walk(
+        &self,
+        process: &'a ProcessInfo,
+        indent: &str,
+        last: bool,
+        root: bool,
+        rows: &mut Vec<ProcessRow<'a>>,
+    ) {
+        let visible_children: Vec<_> = self
+            .children
+            .get(&process.pid)
+            .map(|children| {
+                children
+                    .iter()
+                    .filter(|c| self.visible.get(&c.pid).copied().unwrap_or(false))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let collapsed = self.collapsed.contains(&process.pid);
+        let (subtree_cpu, subtree_memory) = self.totals.get(&process.pid).copied().unwrap_or_default();
+
+        let prefix = if root {
+            String::new()
+        } else {
+            format!("{}{}", indent, if last { "└─ " } else { "├─ " })
+        };
+
+        rows.push(ProcessRow {
+            process,
+            prefix,
+            has_children: !visible_children.is_empty(),
+            collapsed,
+            subtree_cpu,
+            subtree_memory,
+        });
+
+        if collapsed {
+            return;
+        }
+
+        let child_indent = if root {
+            String::new()
+        } else {
+            format!("{}{}", indent, if last { "   " } else { "│  " })
+        };
+        for (i, child) in visible_children.iter().enumerate() {
+            self.walk(child, &child_indent, i + 1 == visible_children.len(), false, rows);
+        }
+    }
+}
+
+/// All descendants of `pid`, parents before their children
+pub fn descendants(processes: &[ProcessInfo], pid: Pid) -> Vec<&ProcessInfo> {
+    let mut children: HashMap<Pid, Vec<&ProcessInfo>> = HashMap::new();
+    for process in processes {
+        if let Some(parent) = process.parent.filter(|&parent| parent != process.pid) {
+            children.entry(parent).or_default().push(process);
+        }
+    }
+
+    let mut found = Vec::new();
+    // Guard against PID reuse creating a parent cycle
+    let mut seen = HashSet::from([pid]);
+    let mut queue = vec![pid];
+    while let Some(parent) = queue.pop() {
+        for &child in children.get(&parent).into_iter().flatten() {
+            if seen.insert(child.pid) {
+                found.push(child);
+                queue.push(child.pid);
+            }
+        }
+    }
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn process(pid: u32, parent: Option<u32>, cpu_usage: f32, memory: u64) -> ProcessInfo {
+        ProcessInfo {
+            cpu_usage,
+            memory,
+            ..ProcessInfo::fixture(pid, parent, &format!("proc{}", pid))
+        }
+    }
+
+    /// init(1) -> shell(10) -> {editor(11), build(12) -> compiler(13)}, plus daemon(20)
+    fn processes() -> Vec<ProcessInfo> {
+        vec![
+            process(1, None, 1.0, 100),
+            process(10, Some(1), 2.0, 200),
+            process(11, Some(10), 3.0, 300),
+            process(12, Some(10), 4.0, 400),
+            process(13, Some(12), 5.0, 500),
+            process(20, Some(1), 6.0, 600),
+        ]
+    }
+
+    fn pids(rows: &[ProcessRow]) -> Vec<u32> {
+        rows.iter().map(|row| row.process.pid.as_u32()).collect()
+    }
+
+    fn row<'r, 'a>(rows: &'r [ProcessRow<'a>], pid: u32) -> &'r ProcessRow<'a> {
+        rows.iter().find(|row| row.process.pid.as_u32() == pid).unwrap()
+    }
+
+    #[test]
+    fn nests_children_with_guides() {
+        let processes = processes();
+        let sorted: Vec<_> = processes.iter().collect();
+        let rows = build_rows(&sorted, &HashSet::new(), |_| true);
+
+        assert_eq!(pids(&rows), [1, 10, 11, 12, 13, 20]);
+        let prefixes: Vec<_> = rows.iter().map(|row| row.prefix.as_str()).collect();
+        assert_eq!(prefixes, ["", "├─ ", "│  ├─ ", "│  └─ ", "│     └─ ", "└─ "]);
+        assert!(row(&rows, 12).has_children);
+        assert!(!row(&rows, 13).has_children);
+    }
+
+    #[test]
+    fn keeps_sibling_order_from_sort() {
+        let processes = processes();
+        // Highest CPU first, as the process list sorts by default
+        let mut sorted: Vec<_> = processes.iter().collect();
+        sorted.sort_by(|a, b| b.cpu_usage.total_cmp(&a.cpu_usage));
+        let rows = build_rows(&sorted, &HashSet::new(), |_| true);
+
+        assert_eq!(pids(&rows), [1, 20, 10, 12, 13, 11]);
+    }
+
+    #[test]
+    fn collapsed_rows_hide_children_but_keep_subtotals() {
+        let processes = processes();
+        let sorted: Vec<_> = processes.iter().collect();
+        let collapsed = HashSet::from([Pid::from_u32(10)]);
+        let rows = build_rows(&sorted, &collapsed, |_| true);
+
+        assert_eq!(pids(&rows), [1, 10, 20]);
+        let shell = row(&rows, 10);
+        assert!(shell.collapsed);
+        assert!(shell.has_children);
+        assert_eq!(shell.subtree_cpu, 2.0 + 3.0 + 4.0 + 5.0);
+        assert_eq!(shell.subtree_memory, 200 + 300 + 400 + 500);
+        assert_eq!(row(&rows, 1).subtree_memory, 2100);
+    }
+
+    #[test]
+    fn filter_keeps_ancestors_of_matches() {
+        let processes = processes();
+        let sorted: Vec<_> = processes.iter().collect();
+        let rows = build_rows(&sorted, &HashSet::new(), |p| p.name == "proc13");
+
+        assert_eq!(pids(&rows), [1, 10, 12, 13]);
+        // Totals still cover hidden siblings
+        assert_eq!(row(&rows, 10).subtree_cpu, 14.0);
+    }
+
+    #[test]
+    fn orphans_and_self_parents_become_roots() {
+        let processes = [process(5, Some(99), 0.0, 0), process(6, Some(6), 0.0, 0)];
+        let sorted: Vec<_> = processes.iter().collect();
+        let rows = build_rows(&sorted, &HashSet::new(), |_| true);
+
+        assert_eq!(pids(&rows), [5, 6]);
+        assert!(rows.iter().all(|row| row.prefix.is_empty()));
+    }
+
+    #[test]
+    fn breaks_parent_cycles() {
+        // PID reuse: 30 and 31 each claim the other as parent
+        let mut processes = processes();
+        processes.push(process(30, Some(31), 1.0, 10));
+        processes.push(process(31, Some(30), 1.0, 10));
+        processes.push(process(32, Some(31), 1.0, 10));
+        let sorted: Vec<_> = processes.iter().collect();
+        let rows = build_rows(&sorted, &HashSet::new(), |_| true);
+
+        assert_eq!(pids(&rows), [1, 10, 11, 12, 13, 20, 30, 31, 32]);
+        assert_eq!(row(&rows, 30).prefix, "");
+        assert_eq!(row(&rows, 30).subtree_memory, 30);
+    }
+
+    #[test]
+    fn collects_descendants_parents_first() {
+        let processes = processes();
+        let found: Vec<u32> = descendants(&processes, Pid::from_u32(10))
+            .iter()
+            .map(|p| p.pid.as_u32())
+            .collect();
+
+        assert_eq!(found.len(), 3);
+        let position = |pid| found.iter().position(|&p| p == pid).unwrap();
+        assert!(position(12) < position(13));
+        assert!(found.contains(&11));
+        assert!(descendants(&processes, Pid::from_u32(13)).is_empty());
+    }
+
+    #[test]
+    fn descendants_stop_at_cycles() {
+        let processes = vec![process(30, Some(31), 0.0, 0), process(31, Some(30), 0.0, 0)];
+        let found = descendants(&processes, Pid::from_u32(30));
+
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].pid.as_u32(), 31);
+    }
+}
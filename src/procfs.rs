@@ -0,0 +1,194 @@
+use std::collections::HashMap;
+
+/// Selected fields from `/proc/meminfo`, in bytes
+#[derive(Debug, Clone, Default)]
+pub struct Meminfo {
+    pub cached: u64,
+    pub buffers: u64,
+}
+
+/// Read `/proc/meminfo` (Linux only)
+pub fn read_meminfo() -> Option<Meminfo> {
+    let content = std::fs::read_to_string("/proc/meminfo").ok()?;
+    let fields = parse_meminfo(&content);
+
+    Some(Meminfo {
+        cached: fields.get("Cached").copied().unwrap_or(0)
+            + fields.get("SReclaimable").copied().unwrap_or(0),
+        buffers: fields.get("Buffers").copied().unwrap_or(0),
+    })
+}
+
+/// Parse `/proc/meminfo` content into a map of field name to bytes
+pub fn parse_meminfo(content: &str) -> HashMap<String, u64> {
+    content
+        .lines()
+        .filter_map(|line| {
+            let (key, rest) = line.split_once(':')?;
+            let mut parts = rest.split_whitespace();
+            let value: u64 = parts.next()?.parse().ok()?;
+            let bytes = match parts.next() {
+                Some("kB") => value * 1024,
+                _ => value,
+            };
+            Some((key.trim().to_string(), bytes))
+        })
+        .collect()
+}
+
+/// Scheduling fields from `/proc/<pid>/stat`
+#[derive(Debug, Clone, Default)]
+pub struct ProcessStat {
+    pub priority: i64,
+    pub nice: i64,
+    pub threads: u64,
+}
+
+/// Read `/proc/<pid>/stat` (Linux only)
+pub fn read_process_stat(pid: u32) -> Option<ProcessStat> {
+    let content = std::fs::read_to_string(format!("/proc/{}/stat", pid)).ok()?;
+    parse_process_stat(&content)
+}
+
+/// Parse `/proc/<pid>/stat` content.
+///
+/// The command name is wrapped in parentheses and may itself contain spaces
+/// or parentheses, so fields are counted from the last `)`.
+pub fn parse_process_stat(content: &str) -> Option<ProcessStat> {
+    let (_, rest) = content.rsplit_once(')')?;
+    let fields: Vec<&str> = rest.split_whitespace().collect();
+
+    // `rest` starts at field 3 (state); priority, nice and num_threads are 18-20
+    Some(ProcessStat {
+        priority: fields.get(15)?.parse().ok()?,
+        nice: fields.get(16)?.parse().ok()?,
+        threads: fields.get(17)?.parse().ok()?,
+    })
+}
+
+/// Cumulative I/O counters for one block device from `/proc/diskstats`
+#[derive(Debug, Clone, Default)]
+pub struct DiskStat {
+    pub name: String,
+    pub reads_completed: u64,
+    pub sectors_read: u64,
+    pub writes_completed: u64,
+    pub sectors_written: u64,
+}
+
+/// Size of a `/proc/diskstats` sector in bytes (always 512, regardless of device)
+pub const SECTOR_SIZE: u64 = 512;
+
+/// Read `/proc/diskstats`, keeping whole block devices only (Linux only)
+pub fn read_diskstats() -> Vec<DiskStat> {
+    let Ok(content) = std::fs::read_to_string("/proc/diskstats") else {
+        return Vec::new();
+    };
+
+    parse_diskstats(&content)
+        .into_iter()
+        .filter(|stat| is_whole_device(&stat.name))
+        .collect()
+}
+
+/// Parse `/proc/diskstats` content
+pub fn parse_diskstats(content: &str) -> Vec<DiskStat> {
+    content
+        .lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            if fields.len() < 10 {
+                return None;
+            }
+            let number = |i: usize| fields[i].parse::<u64>().ok();
+
+            Some(DiskStat {
+                name: fields[2].to_string(),
+                reads_completed: number(3)?,
+                sectors_read: number(5)?,
+                writes_completed: number(7)?,
+                sectors_written: number(9)?,
+            })
+        })
+        .collect()
+}
+
+/// Whether a diskstats entry is a real whole device rather than a partition,
+/// loop or ram device
+fn is_whole_device(name: &str) -> bool {
+    if name.starts_with("loop") || name.starts_with("ram") {
+        return false;
+    }
+    std::path::Path::new("/sys/block").join(name).exists()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_meminfo_in_bytes() {
+        let meminfo = parse_meminfo(
+            "MemTotal:       16314584 kB\n\
+             MemAvailable:    9876543 kB\n\
+             HugePages_Total:       0\n\
+             Broken line\n",
+        );
+
+        assert_eq!(meminfo["MemTotal"], 16314584 * 1024);
+        assert_eq!(meminfo["MemAvailable"], 9876543 * 1024);
+        // Counts without a unit are kept as they are
+        assert_eq!(meminfo["HugePages_Total"], 0);
+        assert_eq!(meminfo.len(), 3);
+    }
+
+    #[test]
+    fn parses_process_stat() {
+        let stat = parse_process_stat(
+            "1234 (bash) S 1 1234 1234 34816 1234 4194304 1000 0 0 0 10 5 0 0 20 0 1 0 \
+             12345 10000000 500 18446744073709551615 0 0 0 0 0 0 0 0 0 0 0 0 17 3 0 0 0 0 0",
+        )
+        .unwrap();
+
+        assert_eq!(stat.priority, 20);
+        assert_eq!(stat.nice, 0);
+        assert_eq!(stat.threads, 1);
+    }
+
+    #[test]
+    fn parses_process_stat_with_parentheses_in_name() {
+        // The command name may contain spaces and ')' itself
+        let stat = parse_process_stat(
+            "99 (Web Content) (x)) R 1 99 99 0 -1 4194560 0 0 0 0 0 0 0 0 39 19 28 0 \
+             100 0 0 18446744073709551615",
+        )
+        .unwrap();
+
+        assert_eq!(stat.priority, 39);
+        assert_eq!(stat.nice, 19);
+        assert_eq!(stat.threads, 28);
+    }
+
+    #[test]
+    fn rejects_truncated_process_stat() {
+        assert!(parse_process_stat("1 (init) S 0 1 1").is_none());
+        assert!(parse_process_stat("").is_none());
+    }
+
+    #[test]
+    fn parses_diskstats() {
+        let stats = parse_diskstats(
+            " 259       0 nvme0n1 1000 20 80000 300 2000 40 160000 500 0 800 900\n\
+             \x20259       1 nvme0n1p1 10 0 800 3 20 0 1600 5 0 8 9\n\
+             \x20  7       0 loop0 0\n",
+        );
+
+        assert_eq!(stats.len(), 2);
+        assert_eq!(stats[0].name, "nvme0n1");
+        assert_eq!(stats[0].reads_completed, 1000);
+        assert_eq!(stats[0].sectors_read, 80000);
+        assert_eq!(stats[0].writes_completed, 2000);
+        assert_eq!(stats[0].sectors_written, 160000);
+        assert_eq!(stats[1].name, "nvme0n1p1");
+    }
+}
@@ -0,0 +1,836 @@
+use crate::snapshot::{ProcessInfo, Snapshot};
+use anyhow::{anyhow, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs::{File, OpenOptions},
+    io::{self, BufRead, BufReader, BufWriter, Seek, SeekFrom, Write},
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use sysinfo::Pid;
+
+/// Playback speeds offered by the faster/slower keys
+const SPEEDS: [f64; 7] = [0.25, 0.5, 1.0, 2.0, 4.0, 8.0, 16.0];
+
+/// Longest stretch of recorded time played back in real time; longer gaps
+/// mean the recorder wasn't running
+const MAX_GAP_MS: u64 = 120_000;
+
+/// Frames between keyframes, which hold every process in full so seeking
+/// never has to decode from the start of the recording
+const KEYFRAME_INTERVAL: usize = 300;
+
+/// Process fields that only change when a process execs or is re-parented
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Details {
+    pid: u32,
+    parent: Option<u32>,
+    name: String,
+    command: String,
+    user: Option<String>,
+    exe: Option<PathBuf>,
+    cwd: Option<PathBuf>,
+    start_time: u64,
+}
+
+impl Details {
+    fn of(process: &ProcessInfo) -> Self {
+        Self {
+            pid: process.pid.as_u32(),
+            parent: process.parent.map(|pid| pid.as_u32()),
+            name: process.name.clone(),
+            command: process.command.clone(),
+            user: process.user.clone(),
+            exe: process.exe.clone(),
+            cwd: process.cwd.clone(),
+            start_time: process.start_time,
+        }
+    }
+
+    /// A process with these details and no readings yet
+    fn into_process(self) -> ProcessInfo {
+        ProcessInfo {
+            pid: Pid::from_u32(self.pid),
+            parent: self.parent.map(Pid::from_u32),
+            name: self.name,
+            command: self.command,
+            user: self.user,
+            status: String::new(),
+            exe: self.exe,
+            cwd: self.cwd,
+            threads: None,
+            nice: None,
+            priority: None,
+            cpu_usage: 0.0,
+            memory: 0,
+            virtual_memory: 0,
+            start_time: self.start_time,
+            run_time: 0,
+            disk_read_total: 0,
+            disk_written_total: 0,
+            disk_read_rate: 0.0,
+            disk_write_rate: 0.0,
+            gpu_usage: None,
+            gpu_memory: None,
+        }
+    }
+}
+
+/// Per-tick process readings, written as an array to keep frames small.
+/// Run time isn't stored; it follows from the timestamp and start time.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Reading(
+    /// PID
+    u32,
+    /// Status
+    String,
+    /// Threads
+    Option<u64>,
+    /// Nice value
+    Option<i64>,
+    /// Priority
+    Option<i64>,
+    /// CPU usage percentage
+    f32,
+    /// Resident memory
+    u64,
+    /// Virtual memory
+    u64,
+    /// Total bytes read
+    u64,
+    /// Total bytes written
+    u64,
+    /// Read rate
+    f64,
+    /// Write rate
+    f64,
+    /// GPU usage percentage
+    Option<f32>,
+    /// GPU memory
+    Option<u64>,
+);
+
+impl Reading {
+    fn of(process: &ProcessInfo) -> Self {
+        Self(
+            process.pid.as_u32(),
+            process.status.clone(),
+            process.threads,
+            process.nice,
+            process.priority,
+            process.cpu_usage,
+            process.memory,
+            process.virtual_memory,
+            process.disk_read_total,
+            process.disk_written_total,
+            process.disk_read_rate,
+            process.disk_write_rate,
+            process.gpu_usage,
+            process.gpu_memory,
+        )
+    }
+
+    fn apply(self, process: &mut ProcessInfo) {
+        process.status = self.1;
+        process.threads = self.2;
+        process.nice = self.3;
+        process.priority = self.4;
+        process.cpu_usage = self.5;
+        process.memory = self.6;
+        process.virtual_memory = self.7;
+        process.disk_read_total = self.8;
+        process.disk_written_total = self.9;
+        process.disk_read_rate = self.10;
+        process.disk_write_rate = self.11;
+        process.gpu_usage = self.12;
+        process.gpu_memory = self.13;
+    }
+}
+
+/// One line of a recording.
+///
+/// Keyframes list every process; delta frames only list the processes that
+/// started, changed or exited since the frame before. A plain snapshot line,
+/// as written by `--export`, reads as a keyframe with its processes inline.
+#[derive(Serialize, Deserialize)]
+struct Frame {
+    /// Everything but the processes, which are split out below
+    #[serde(flatten)]
+    snapshot: Snapshot,
+    /// Whether the processes build on the previous frame's
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    delta: bool,
+    /// Details of new processes and of processes whose details changed
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    started: Vec<Details>,
+    /// Readings that changed
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    changed: Vec<Reading>,
+    /// PIDs that are gone
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    exited: Vec<u32>,
+}
+
+/// The part of a frame read while indexing a recording
+#[derive(Deserialize)]
+struct FrameHeader {
+    timestamp: u64,
+    #[serde(default)]
+    delta: bool,
+}
+
+/// Where a frame is in the recording file
+struct IndexEntry {
+    timestamp: u64,
+    offset: u64,
+    keyframe: bool,
+}
+
+/// Appends every snapshot the app sees to a recording file, writing process
+/// details only when they change
+pub struct Recorder {
+    writer: BufWriter<File>,
+    path: PathBuf,
+    /// What the recording holds for each process as of the last frame
+    processes: HashMap<u32, (Details, Reading)>,
+    /// Frames written since the last keyframe
+    since_keyframe: usize,
+}
+
+impl Recorder {
+    /// Open `path` for appending, so a restarted session extends the same file
+    pub fn open(path: &Path) -> Result<Self> {
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .with_context(|| format!("failed to open {}", path.display()))?;
+        Ok(Self::new(file, path))
+    }
+
+    fn new(file: File, path: &Path) -> Self {
+        Self {
+            writer: BufWriter::new(file),
+            path: path.to_path_buf(),
+            processes: HashMap::new(),
+            since_keyframe: 0,
+        }
+    }
+
+    /// Append one snapshot
+    pub fn record(&mut self, snapshot: &Snapshot) -> Result<(), String> {
+        let frame = self.frame(snapshot);
+        self.write(&frame)
+            .map_err(|err| format!("Recording to {} stopped: {}", self.path.display(), err))
+    }
+
+    /// Frame for `snapshot`, relative to the frames already written
+    fn frame(&mut self, snapshot: &Snapshot) -> Frame {
+        // Each session starts with a keyframe, as it may be appended to a
+        // recording whose last frame it knows nothing about
+        let keyframe = self.since_keyframe == 0;
+        self.since_keyframe = (self.since_keyframe + 1) % KEYFRAME_INTERVAL;
+        if keyframe {
+            self.processes.clear();
+        }
+
+        let mut frame = Frame {
+            snapshot: Snapshot {
+                processes: Vec::new(),
+                ..snapshot.clone()
+            },
+            delta: !keyframe,
+            started: Vec::new(),
+            changed: Vec::new(),
+            exited: Vec::new(),
+        };
+
+        let mut processes = HashMap::with_capacity(snapshot.processes.len());
+        for process in &snapshot.processes {
+            let (details, reading) = (Details::of(process), Reading::of(process));
+            let previous = self.processes.remove(&details.pid);
+            if previous.as_ref().map(|(details, _)| details) != Some(&details) {
+                frame.started.push(details.clone());
+            }
+            if previous.as_ref().map(|(_, reading)| reading) != Some(&reading) {
+                frame.changed.push(reading.clone());
+            }
+            processes.insert(details.pid, (details, reading));
+        }
+        frame.exited = self.processes.keys().copied().collect();
+        frame.exited.sort_unstable();
+        self.processes = processes;
+
+        frame
+    }
+
+    fn write(&mut self, frame: &Frame) -> io::Result<()> {
+        serde_json::to_writer(&mut self.writer, frame)?;
+        self.writer.write_all(b"\n")?;
+        self.writer.flush()
+    }
+}
+
+/// Playback of a recorded session.
+///
+/// The play head moves through recorded time at `speed` times real time;
+/// every frame it passes is handed to the app so histories fill in exactly
+/// as they did live. Frames are read from the file as the play head
+/// reaches them, using an index of where each one starts.
+pub struct Replay {
+    file: BufReader<File>,
+    index: Vec<IndexEntry>,
+    /// File offset the next read continues from
+    offset: u64,
+    /// Processes as of frame `decoded`, by PID
+    processes: BTreeMap<u32, ProcessInfo>,
+    /// Last frame read, if reading it succeeded
+    decoded: Option<usize>,
+    /// Frame on screen
+    current: Arc<Snapshot>,
+    /// Index of the frame on screen
+    position: usize,
+    /// Recorded time of the play head, in milliseconds since the Unix epoch
+    cursor_ms: f64,
+    pub playing: bool,
+    speed: usize,
+    last_tick: Instant,
+}
+
+impl Replay {
+    /// Load a recording; a torn last line (from a killed recorder) is skipped
+    pub fn load(path: &Path) -> Result<Self> {
+        let file = File::open(path).with_context(|| format!("failed to read {}", path.display()))?;
+        Self::open(file, path)
+    }
+
+    /// Play back the recording in `file`, indexing it first
+    fn open(file: File, path: &Path) -> Result<Self> {
+        let mut file = BufReader::new(file);
+        let index = Self::build_index(&mut file, path)?;
+        let mut replay = Self {
+            file,
+            cursor_ms: index[0].timestamp as f64,
+            index,
+            offset: 0,
+            processes: BTreeMap::new(),
+            decoded: None,
+            current: Arc::default(),
+            position: 0,
+            playing: true,
+            speed: 2,
+            last_tick: Instant::now(),
+        };
+
+        replay.current = replay
+            .read_frame(0)
+            .with_context(|| format!("failed to parse {} line 1", path.display()))?;
+        Ok(replay)
+    }
+
+    /// Find where every frame starts and when it was recorded
+    fn build_index(file: &mut BufReader<File>, path: &Path) -> Result<Vec<IndexEntry>> {
+        let mut index = Vec::new();
+        let mut torn = None;
+        let (mut line, mut offset, mut number) = (String::new(), 0, 0);
+
+        loop {
+            line.clear();
+            let read = file
+                .read_line(&mut line)
+                .with_context(|| format!("failed to read {}", path.display()))?;
+            if read == 0 {
+                break;
+            }
+            number += 1;
+            if !line.trim().is_empty() {
+                // Only the last line may be torn
+                if let Some((number, err)) = torn.take() {
+                    return Err(err)
+                        .with_context(|| format!("failed to parse {} line {}", path.display(), number));
+                }
+                match serde_json::from_str::<FrameHeader>(&line) {
+                    Ok(header) => index.push(IndexEntry {
+                        timestamp: header.timestamp,
+                        offset,
+                        keyframe: !header.delta,
+                    }),
+                    Err(err) => torn = Some((number, err)),
+                }
+            }
+            offset += read as u64;
+        }
+        file.rewind()
+            .with_context(|| format!("failed to read {}", path.display()))?;
+
+        match torn {
+            Some((number, err)) if index.is_empty() => {
+                Err(err).with_context(|| format!("failed to parse {} line {}", path.display(), number))
+            }
+            _ if index.is_empty() => Err(anyhow!("{} contains no snapshots", path.display())),
+            _ => Ok(index),
+        }
+    }
+
+    /// Read frame `i`, which must directly follow the last frame read
+    /// unless it is a keyframe
+    fn read_frame(&mut self, i: usize) -> Result<Arc<Snapshot>> {
+        self.decoded = None;
+        let offset = self.index[i].offset;
+        if self.offset != offset {
+            self.file.seek(SeekFrom::Start(offset))?;
+        }
+        let mut line = String::new();
+        self.offset = offset + self.file.read_line(&mut line)? as u64;
+
+        let frame: Frame = serde_json::from_str(&line)?;
+        let snapshot = Arc::new(self.apply(frame));
+        self.decoded = Some(i);
+        Ok(snapshot)
+    }
+
+    /// Rebuild the full snapshot a frame stands for
+    fn apply(&mut self, frame: Frame) -> Snapshot {
+        let Frame {
+            mut snapshot,
+            delta,
+            started,
+            changed,
+            exited,
+        } = frame;
+
+        if !delta {
+            self.processes.clear();
+        }
+        for pid in exited {
+            self.processes.remove(&pid);
+        }
+        for details in started {
+            match self.processes.get_mut(&details.pid) {
+                Some(process) => {
+                    let reading = Reading::of(process);
+                    *process = details.into_process();
+                    reading.apply(process);
+                }
+                None => {
+                    self.processes.insert(details.pid, details.into_process());
+                }
+            }
+        }
+        for reading in changed {
+            if let Some(process) = self.processes.get_mut(&reading.0) {
+                reading.apply(process);
+            }
+        }
+
+        if snapshot.processes.is_empty() {
+            let now = snapshot.timestamp / 1000;
+            for process in self.processes.values_mut() {
+                process.run_time = now.saturating_sub(process.start_time);
+            }
+            snapshot.processes = self.processes.values().cloned().collect();
+        } else {
+            // A plain snapshot carries its processes inline
+            self.processes = snapshot
+                .processes
+                .iter()
+                .map(|process| (process.pid.as_u32(), process.clone()))
+                .collect();
+        }
+        snapshot
+    }
+
+    /// Read frames `start..=end` and return them, oldest first; unreadable
+    /// frames are left out
+    fn read_frames(&mut self, start: usize, end: usize) -> Vec<Arc<Snapshot>> {
+        // Delta frames need every frame since the keyframe before them
+        let keyframe = self.index[..=start]
+            .iter()
+            .rposition(|entry| entry.keyframe)
+            .unwrap_or(0);
+        let from = match self.decoded {
+            Some(decoded) if (keyframe..start).contains(&decoded) => decoded + 1,
+            _ => keyframe,
+        };
+
+        let mut frames = Vec::with_capacity(end + 1 - start);
+        for i in from..=end {
+            if let Ok(frame) = self.read_frame(i) {
+                if i >= start {
+                    frames.push(frame);
+                }
+            }
+        }
+        if let Some(last) = frames.last() {
+            self.current = Arc::clone(last);
+        }
+        frames
+    }
+
+    /// Play back `frames`, which must not be empty, from a recording of them
+    #[cfg(test)]
+    pub fn from_frames(frames: Vec<Arc<Snapshot>>) -> Self {
+        let mut file = tempfile::tempfile().unwrap();
+        let mut recorder = Recorder::new(file.try_clone().unwrap(), Path::new("frames"));
+        for frame in &frames {
+            recorder.record(frame).unwrap();
+        }
+        file.rewind().unwrap();
+        Self::open(file, Path::new("frames")).unwrap()
+    }
+
+    /// Frame on screen
+    pub fn current(&self) -> Arc<Snapshot> {
+        Arc::clone(&self.current)
+    }
+
+    /// Frame number on screen (1-based) and frame count
+    pub fn progress(&self) -> (usize, usize) {
+        (self.position + 1, self.index.len())
+    }
+
+    /// Playback speed multiplier
+    pub fn speed(&self) -> f64 {
+        SPEEDS[self.speed]
+    }
+
+    /// Move the play head by the real time since the last tick and return
+    /// the frames it passed, oldest first
+    pub fn tick(&mut self) -> Vec<Arc<Snapshot>> {
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.last_tick);
+        self.last_tick = now;
+        self.advance(elapsed)
+    }
+
+    /// Move the play head by `elapsed` real time
+    fn advance(&mut self, elapsed: Duration) -> Vec<Arc<Snapshot>> {
+        if !self.playing {
+            return Vec::new();
+        }
+        self.cursor_ms += elapsed.as_secs_f64() * 1000.0 * self.speed();
+
+        let from = self.position;
+        while let Some(next) = self.index.get(self.position + 1) {
+            let current = self.index[self.position].timestamp;
+            // Gaps where nothing was recording get a one second pause, not hours
+            if next.timestamp.saturating_sub(current) > MAX_GAP_MS
+                && self.cursor_ms >= (current + 1000) as f64
+            {
+                self.cursor_ms = self.cursor_ms.max(next.timestamp as f64);
+            }
+            if next.timestamp as f64 > self.cursor_ms {
+                break;
+            }
+            self.position += 1;
+        }
+
+        // Stop on the last frame instead of running past the end
+        if self.position + 1 == self.index.len() {
+            self.playing = false;
+            self.cursor_ms = self.index[self.position].timestamp as f64;
+        }
+
+        if self.position == from {
+            return Vec::new();
+        }
+        self.read_frames(from + 1, self.position)
+    }
+
+    /// Pause or resume
+    pub fn toggle_pause(&mut self) {
+        self.playing = !self.playing;
+        self.last_tick = Instant::now();
+    }
+
+    /// Step to the next (or previous) playback speed
+    pub fn change_speed(&mut self, faster: bool) {
+        self.speed = if faster {
+            (self.speed + 1).min(SPEEDS.len() - 1)
+        } else {
+            self.speed.saturating_sub(1)
+        };
+    }
+
+    /// Jump by `delta_ms` of recorded time and return up to `history` frames
+    /// ending at the new position, for rebuilding history graphs
+    pub fn seek(&mut self, delta_ms: i64, history: usize) -> Vec<Arc<Snapshot>> {
+        let first = self.index[0].timestamp as f64;
+        let last = self.index[self.index.len() - 1].timestamp as f64;
+        self.cursor_ms = (self.cursor_ms + delta_ms as f64).clamp(first, last);
+
+        self.position = self
+            .index
+            .partition_point(|entry| entry.timestamp as f64 <= self.cursor_ms)
+            .saturating_sub(1);
+        self.last_tick = Instant::now();
+
+        let start = (self.position + 1).saturating_sub(history.max(1));
+        self.read_frames(start, self.position)
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    /// Frames recorded at these offsets in seconds
+    fn replay(secs: &[u64]) -> Replay {
+        let frames = secs
+            .iter()
+            .map(|&secs| {
+                Arc::new(Snapshot {
+                    timestamp: 1_000_000 + secs * 1000,
+                    ..Default::default()
+                })
+            })
+            .collect();
+        Replay::from_frames(frames)
+    }
+
+    /// Offsets in seconds of the frames a step returned
+    fn offsets(frames: &[Arc<Snapshot>]) -> Vec<u64> {
+        frames.iter().map(|frame| (frame.timestamp - 1_000_000) / 1000).collect()
+    }
+
+    fn advance_ms(replay: &mut Replay, ms: u64) -> Vec<u64> {
+        offsets(&replay.advance(Duration::from_millis(ms)))
+    }
+
+    #[test]
+    fn plays_in_recorded_time() {
+        let mut replay = replay(&[0, 1, 2, 3, 4]);
+        assert_eq!(replay.speed(), 1.0);
+
+        assert!(advance_ms(&mut replay, 500).is_empty());
+        assert_eq!(advance_ms(&mut replay, 600), [1]);
+        assert_eq!(advance_ms(&mut replay, 2000), [2, 3]);
+        assert_eq!(replay.progress(), (4, 5));
+    }
+
+    #[test]
+    fn speed_scales_playback() {
+        let mut replay = replay(&[0, 1, 2, 3, 4]);
+        replay.change_speed(true);
+        replay.change_speed(true);
+        assert_eq!(replay.speed(), 4.0);
+
+        assert_eq!(advance_ms(&mut replay, 500), [1, 2]);
+
+        for _ in 0..10 {
+            replay.change_speed(false);
+        }
+        assert_eq!(replay.speed(), 0.25);
+    }
+
+    #[test]
+    fn stops_on_the_last_frame() {
+        let mut replay = replay(&[0, 1, 2]);
+        assert_eq!(advance_ms(&mut replay, 60_000), [1, 2]);
+        assert!(!replay.playing);
+        assert!(advance_ms(&mut replay, 1000).is_empty());
+        assert_eq!(replay.progress(), (3, 3));
+    }
+
+    #[test]
+    fn pausing_holds_the_play_head() {
+        let mut replay = replay(&[0, 1, 2]);
+        replay.toggle_pause();
+        assert!(advance_ms(&mut replay, 5000).is_empty());
+
+        replay.toggle_pause();
+        assert_eq!(advance_ms(&mut replay, 1000), [1]);
+    }
+
+    #[test]
+    fn long_gaps_pause_for_a_second() {
+        // The recorder was off for over an hour between frames 1 and 2
+        let mut replay = replay(&[0, 1, 4000, 4001]);
+
+        assert_eq!(advance_ms(&mut replay, 1000), [1]);
+        assert!(advance_ms(&mut replay, 900).is_empty());
+        assert_eq!(advance_ms(&mut replay, 200), [4000]);
+        assert_eq!(advance_ms(&mut replay, 1000), [4001]);
+    }
+
+    #[test]
+    fn gaps_up_to_the_limit_play_in_real_time() {
+        let mut replay = replay(&[0, 120]);
+        assert!(advance_ms(&mut replay, 60_000).is_empty());
+        assert_eq!(advance_ms(&mut replay, 60_000), [120]);
+    }
+
+    #[test]
+    fn seeks_to_the_frame_at_or_before_the_play_head() {
+        let mut replay = replay(&[0, 10, 20, 30, 40]);
+
+        assert_eq!(offsets(&replay.seek(25_000, 60)), [0, 10, 20]);
+        assert_eq!(replay.progress(), (3, 5));
+        // History is limited to the last `history` frames
+        assert_eq!(offsets(&replay.seek(10_000, 2)), [20, 30]);
+        assert_eq!(offsets(&replay.seek(-5_000, 60)), [0, 10, 20, 30]);
+    }
+
+    #[test]
+    fn seeks_clamp_to_both_ends() {
+        let mut replay = replay(&[0, 10, 20]);
+
+        assert_eq!(offsets(&replay.seek(1_000_000, 60)), [0, 10, 20]);
+        assert_eq!(replay.progress(), (3, 3));
+        assert_eq!(offsets(&replay.seek(-1_000_000, 60)), [0]);
+        assert_eq!(replay.progress(), (1, 3));
+
+        // Playing on from the start isn't thrown off by the clamped seek
+        assert_eq!(advance_ms(&mut replay, 10_000), [10]);
+    }
+
+    /// Snapshot at `secs` with `count` processes; PIDs from 100, each with a
+    /// long command line
+    fn busy_snapshot(secs: u64, count: u32) -> Snapshot {
+        let processes = (0..count)
+            .map(|i| ProcessInfo {
+                command: format!("/usr/lib/worker --id {} --config /etc/worker/pool.toml", i),
+                start_time: 900,
+                memory: 4096,
+                ..ProcessInfo::fixture(100 + i, Some(1), "worker")
+            })
+            .collect();
+        Snapshot {
+            timestamp: 1_000_000 + secs * 1000,
+            processes,
+            ..Default::default()
+        }
+    }
+
+    fn recording(frames: &[Snapshot]) -> (tempfile::TempDir, PathBuf) {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("session.ndjson");
+        let mut recorder = Recorder::open(&path).unwrap();
+        for frame in frames {
+            recorder.record(frame).unwrap();
+        }
+        (dir, path)
+    }
+
+    /// Processes as the app would see them, for comparing
+    fn processes(snapshot: &Snapshot) -> serde_json::Value {
+        serde_json::to_value(&snapshot.processes).unwrap()
+    }
+
+    #[test]
+    fn records_process_details_only_when_they_change() {
+        let mut frames: Vec<Snapshot> = (0..10).map(|secs| busy_snapshot(secs, 200)).collect();
+        frames[5].processes[3].cpu_usage = 50.0;
+        frames[7].processes[4].name = "renamed".to_string();
+        let (_dir, path) = recording(&frames);
+
+        let content = fs::read_to_string(&path).unwrap();
+        let lines: Vec<&str> = content.lines().collect();
+        assert_eq!(lines.len(), 10);
+        // Only the first frame holds every command line
+        assert_eq!(content.matches("--id 3 ").count(), 1);
+        // Renamed on frame 7 and back on frame 8
+        assert_eq!(content.matches("--id 4 ").count(), 3);
+        assert!(!lines[1].contains("worker"));
+        assert!(lines[5].contains("50.0"));
+
+        let full: usize = frames.iter().map(|frame| serde_json::to_string(frame).unwrap().len()).sum();
+        assert!(content.len() * 5 < full);
+    }
+
+    #[test]
+    fn replays_recorded_processes() {
+        let mut frames: Vec<Snapshot> = (0..8).map(|secs| busy_snapshot(secs, 5)).collect();
+        frames[2].processes[0].cpu_usage = 12.5;
+        frames[3].processes.remove(1);
+        frames[4].processes.push(ProcessInfo::fixture(900, Some(100), "child"));
+        frames[5].processes[0].command = "/usr/lib/worker --reloaded".to_string();
+        frames[6].processes.clear();
+        for frame in &mut frames {
+            for process in &mut frame.processes {
+                process.run_time = (frame.timestamp / 1000).saturating_sub(process.start_time);
+            }
+        }
+        let (_dir, path) = recording(&frames);
+
+        let mut replay = Replay::load(&path).unwrap();
+        assert_eq!(processes(&replay.current()), processes(&frames[0]));
+        let played = replay.advance(Duration::from_secs(60));
+        assert_eq!(played.len(), 7);
+        for (replayed, recorded) in played.iter().zip(&frames[1..]) {
+            assert_eq!(processes(replayed), processes(recorded));
+        }
+
+        // Seeking back rebuilds the processes from the keyframe
+        let rewound = replay.seek(-3_000, 2);
+        assert_eq!(processes(&rewound[1]), processes(&frames[4]));
+        assert_eq!(processes(&replay.current()), processes(&frames[4]));
+    }
+
+    #[test]
+    fn seeks_past_keyframes() {
+        let count = KEYFRAME_INTERVAL as u64 * 2 + 10;
+        let frames: Vec<Snapshot> = (0..count)
+            .map(|secs| {
+                let mut frame = busy_snapshot(secs, 3);
+                frame.processes[0].memory = secs;
+                frame
+            })
+            .collect();
+        let (_dir, path) = recording(&frames);
+        let keyframes = fs::read_to_string(&path)
+            .unwrap()
+            .lines()
+            .filter(|line| !line.contains("\"delta\""))
+            .count();
+        assert_eq!(keyframes, 3);
+
+        let mut replay = Replay::load(&path).unwrap();
+        for (delta, position) in [(600_000, 600), (-400_000, 200), (100_000, 300)] {
+            let frames = replay.seek(delta, 1);
+            assert_eq!(replay.progress().0, position + 1);
+            assert_eq!(frames[0].processes[0].memory, position as u64);
+        }
+    }
+
+    #[test]
+    fn appended_sessions_start_with_a_keyframe() {
+        let (_dir, path) = recording(&[busy_snapshot(0, 3), busy_snapshot(1, 3)]);
+        let mut recorder = Recorder::open(&path).unwrap();
+        recorder.record(&busy_snapshot(100, 2)).unwrap();
+
+        let mut replay = Replay::load(&path).unwrap();
+        let played = replay.advance(Duration::from_secs(1000));
+        assert_eq!(played.len(), 2);
+        assert_eq!(played[1].processes.len(), 2);
+    }
+
+    #[test]
+    fn loads_recordings_and_skips_a_torn_last_line() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("session.ndjson");
+        let line = |secs: u64| {
+            serde_json::to_string(&Snapshot {
+                timestamp: secs * 1000,
+                ..Default::default()
+            })
+            .unwrap()
+        };
+        fs::write(&path, format!("{}\n\n{}\n{{\"timest", line(1), line(2))).unwrap();
+
+        let replay = Replay::load(&path).unwrap();
+        assert_eq!(replay.progress(), (1, 2));
+
+        // Exports hold full snapshots, which replay as they are
+        let export = busy_snapshot(0, 2);
+        fs::write(&path, serde_json::to_string(&export).unwrap()).unwrap();
+        assert_eq!(processes(&Replay::load(&path).unwrap().current()), processes(&export));
+
+        fs::write(&path, "").unwrap();
+        assert!(Replay::load(&path).err().unwrap().to_string().contains("no snapshots"));
+        fs::write(&path, format!("garbage\n{}\n", line(1))).unwrap();
+        assert!(Replay::load(&path).err().unwrap().to_string().contains("line 1"));
+    }
+}
@@ -0,0 +1,336 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::path::PathBuf;
+use sysinfo::Pid;
+
+/// Per-core CPU sample
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CpuInfo {
+    /// Usage percentage (0-100)
+    pub usage: f32,
+    /// Frequency in MHz
+    pub frequency: u64,
+}
+
+/// Memory sample
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MemoryInfo {
+    /// Total memory in bytes
+    pub total: u64,
+    /// Used memory in bytes
+    pub used: u64,
+    /// Available memory in bytes
+    pub available: u64,
+    /// Page cache in bytes (Linux only)
+    pub cached: Option<u64>,
+    /// Kernel buffers in bytes (Linux only)
+    pub buffers: Option<u64>,
+    /// Total swap in bytes
+    pub swap_total: u64,
+    /// Used swap in bytes
+    pub swap_used: u64,
+}
+
+/// Process sample
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProcessInfo {
+    #[serde(with = "pid")]
+    pub pid: Pid,
+    #[serde(with = "pid::option")]
+    pub parent: Option<Pid>,
+    pub name: String,
+    /// Full command line, empty for kernel threads
+    pub command: String,
+    /// Owning user name, if it could be resolved
+    pub user: Option<String>,
+    pub status: String,
+    pub exe: Option<PathBuf>,
+    pub cwd: Option<PathBuf>,
+    /// Number of threads
+    pub threads: Option<u64>,
+    /// Nice value (-20 to 19)
+    pub nice: Option<i64>,
+    /// Kernel scheduling priority
+    pub priority: Option<i64>,
+    /// CPU usage percentage
+    pub cpu_usage: f32,
+    /// Resident memory in bytes
+    pub memory: u64,
+    /// Virtual memory in bytes
+    pub virtual_memory: u64,
+    /// Start time in seconds since the Unix epoch
+    pub start_time: u64,
+    /// Run time in seconds
+    pub run_time: u64,
+    /// Total bytes read from disk
+    pub disk_read_total: u64,
+    /// Total bytes written to disk
+    pub disk_written_total: u64,
+    /// Disk read rate in bytes per second
+    pub disk_read_rate: f64,
+    /// Disk write rate in bytes per second
+    pub disk_write_rate: f64,
+    /// GPU utilization percentage, summed over GPUs; `None` when the process
+    /// has no GPU context or the driver doesn't report per-process load
+    #[serde(default)]
+    pub gpu_usage: Option<f32>,
+    /// Dedicated GPU memory in bytes, summed over GPUs
+    #[serde(default)]
+    pub gpu_memory: Option<u64>,
+}
+
+/// Network interface sample
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct NetworkInfo {
+    pub name: String,
+    /// Receive rate in bytes per second
+    pub rx_rate: f64,
+    /// Transmit rate in bytes per second
+    pub tx_rate: f64,
+    /// Total bytes received
+    pub total_received: u64,
+    /// Total bytes transmitted
+    pub total_transmitted: u64,
+    /// Total packets received
+    pub packets_received: u64,
+    /// Total packets transmitted
+    pub packets_transmitted: u64,
+    /// Total receive errors
+    pub errors_received: u64,
+    /// Total transmit errors
+    pub errors_transmitted: u64,
+}
+
+/// Mounted filesystem sample
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DiskInfo {
+    pub mount_point: PathBuf,
+    pub file_system: String,
+    /// Total space in bytes
+    pub total: u64,
+    /// Available space in bytes
+    pub available: u64,
+}
+
+impl DiskInfo {
+    /// Used space percentage
+    pub fn usage(&self) -> f32 {
+        if self.total == 0 {
+            0.0
+        } else {
+            ((self.total - self.available.min(self.total)) as f32 / self.total as f32) * 100.0
+        }
+    }
+}
+
+/// Block device I/O sample
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BlockDeviceInfo {
+    pub name: String,
+    /// Read throughput in bytes per second
+    pub read_rate: f64,
+    /// Write throughput in bytes per second
+    pub write_rate: f64,
+    /// Completed reads per second
+    pub read_iops: f64,
+    /// Completed writes per second
+    pub write_iops: f64,
+}
+
+/// Kind of hardware sensor
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SensorKind {
+    /// Degrees Celsius
+    Temperature,
+    /// RPM
+    Fan,
+    /// Volts
+    Voltage,
+}
+
+/// Hardware sensor sample
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SensorInfo {
+    pub label: String,
+    pub kind: SensorKind,
+    pub value: f32,
+    pub max: Option<f32>,
+    pub critical: Option<f32>,
+}
+
+impl SensorInfo {
+    /// Key identifying this sensor across snapshots
+    pub fn key(&self) -> String {
+        format!("{:?}:{}", self.kind, self.label)
+    }
+}
+
+/// GPU information.
+///
+/// Power, clocks, fan, codec and PCIe readings are only reported by NVML;
+/// they stay `None` for other GPUs and in older recordings.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GpuInfo {
+    /// Position among all GPUs found; NVIDIA GPUs come first, in NVML order
+    #[serde(default)]
+    pub index: u32,
+    pub name: String,
+    /// Utilization percentage
+    pub usage: f32,
+    /// Used memory in bytes
+    pub memory_used: u64,
+    /// Total memory in bytes
+    pub memory_total: u64,
+    /// Temperature in °C
+    pub temperature: u32,
+    /// Power draw in watts
+    #[serde(default)]
+    pub power_draw: Option<f32>,
+    /// Enforced power limit in watts
+    #[serde(default)]
+    pub power_limit: Option<f32>,
+    /// SM clock in MHz
+    #[serde(default)]
+    pub sm_clock: Option<u32>,
+    /// Memory clock in MHz
+    #[serde(default)]
+    pub memory_clock: Option<u32>,
+    /// Speed of the first fan as a percentage of its maximum
+    #[serde(default)]
+    pub fan_speed: Option<u32>,
+    /// Why clocks are being held down, e.g. "sw_power_cap"; empty when they aren't
+    #[serde(default)]
+    pub throttle_reasons: Vec<String>,
+    /// Video encoder utilization percentage
+    #[serde(default)]
+    pub encoder_usage: Option<f32>,
+    /// Video decoder utilization percentage
+    #[serde(default)]
+    pub decoder_usage: Option<f32>,
+    /// PCIe receive rate in bytes per second
+    #[serde(default)]
+    pub pcie_rx_rate: Option<f64>,
+    /// PCIe transmit rate in bytes per second
+    #[serde(default)]
+    pub pcie_tx_rate: Option<f64>,
+}
+
+impl GpuInfo {
+    /// Memory usage percentage
+    pub fn memory_usage(&self) -> f32 {
+        if self.memory_total == 0 {
+            0.0
+        } else {
+            (self.memory_used as f32 / self.memory_total as f32) * 100.0
+        }
+    }
+
+    /// Power draw as a percentage of the limit
+    pub fn power_usage(&self) -> Option<f32> {
+        let (draw, limit) = (self.power_draw?, self.power_limit?);
+        (limit > 0.0).then(|| draw / limit * 100.0)
+    }
+}
+
+/// Immutable point-in-time view of the system, produced by the collector
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Snapshot {
+    /// When the snapshot was taken, in milliseconds since the Unix epoch
+    pub timestamp: u64,
+    /// Per-core CPU samples
+    pub cpus: Vec<CpuInfo>,
+    /// Overall CPU usage percentage
+    pub global_cpu_usage: f32,
+    /// Memory usage
+    pub memory: MemoryInfo,
+    /// Running processes (unsorted)
+    pub processes: Vec<ProcessInfo>,
+    /// Network interfaces, sorted by name
+    pub networks: Vec<NetworkInfo>,
+    /// Mounted filesystems
+    pub disks: Vec<DiskInfo>,
+    /// Block device I/O rates, sorted by name
+    pub block_devices: Vec<BlockDeviceInfo>,
+    /// Temperature, fan and voltage sensors
+    pub sensors: Vec<SensorInfo>,
+    /// GPUs, by device index (empty if unavailable)
+    #[serde(default)]
+    pub gpus: Vec<GpuInfo>,
+}
+
+impl Snapshot {
+    /// Memory usage percentage
+    pub fn memory_usage(&self) -> f32 {
+        if self.memory.total == 0 {
+            0.0
+        } else {
+            (self.memory.used as f32 / self.memory.total as f32) * 100.0
+        }
+    }
+
+    /// Swap usage percentage
+    pub fn swap_usage(&self) -> f32 {
+        if self.memory.swap_total == 0 {
+            0.0
+        } else {
+            (self.memory.swap_used as f32 / self.memory.swap_total as f32) * 100.0
+        }
+    }
+}
+
+#[cfg(test)]
+impl ProcessInfo {
+    /// Running process with zero readings and no optional details, for
+    /// building snapshots in tests
+    pub fn fixture(pid: u32, parent: Option<u32>, name: &str) -> Self {
+        Self {
+            pid: Pid::from_u32(pid),
+            parent: parent.map(Pid::from_u32),
+            name: name.to_string(),
+            command: String::new(),
+            user: None,
+            status: "Running".to_string(),
+            exe: None,
+            cwd: None,
+            threads: None,
+            nice: None,
+            priority: None,
+            cpu_usage: 0.0,
+            memory: 0,
+            virtual_memory: 0,
+            start_time: 0,
+            run_time: 0,
+            disk_read_total: 0,
+            disk_written_total: 0,
+            disk_read_rate: 0.0,
+            disk_write_rate: 0.0,
+            gpu_usage: None,
+            gpu_memory: None,
+        }
+    }
+}
+
+/// PIDs are written as plain numbers
+mod pid {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(pid: &Pid, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u32(pid.as_u32())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Pid, D::Error> {
+        u32::deserialize(deserializer).map(Pid::from_u32)
+    }
+
+    pub mod option {
+        use super::*;
+
+        pub fn serialize<S: Serializer>(pid: &Option<Pid>, serializer: S) -> Result<S::Ok, S::Error> {
+            pid.map(|pid| pid.as_u32()).serialize(serializer)
+        }
+
+        pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Pid>, D::Error> {
+            Option::<u32>::deserialize(deserializer).map(|pid| pid.map(Pid::from_u32))
+        }
+    }
+}
@@ -0,0 +1,69 @@
+#[cfg(test)]
+pub mod scripted;
+pub mod system;
+
+pub use system::SystemSource;
+
+use crate::snapshot::{
+    BlockDeviceInfo, CpuInfo, DiskInfo, GpuInfo, MemoryInfo, NetworkInfo, ProcessInfo,
+    SensorInfo, Snapshot,
+};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Source of system metrics consumed by the collector.
+///
+/// `refresh` is called once per tick; the getters then report the readings
+/// taken by that refresh. Keeping `App` behind this trait means the UI can be
+/// driven from scripted data as well as the live system.
+pub trait MetricSource: Send {
+    /// Take fresh readings
+    fn refresh(&mut self);
+
+    /// Per-core CPU usage and frequency
+    fn cpus(&self) -> Vec<CpuInfo>;
+
+    /// Overall CPU usage percentage
+    fn global_cpu_usage(&self) -> f32;
+
+    /// Memory usage
+    fn memory(&self) -> MemoryInfo;
+
+    /// Running processes
+    fn processes(&self) -> Vec<ProcessInfo>;
+
+    /// Network interfaces, sorted by name
+    fn networks(&self) -> Vec<NetworkInfo>;
+
+    /// Mounted filesystems
+    fn disks(&self) -> Vec<DiskInfo>;
+
+    /// Block device I/O rates, sorted by name
+    fn block_devices(&self) -> Vec<BlockDeviceInfo>;
+
+    /// Temperature, fan and voltage sensors
+    fn sensors(&self) -> Vec<SensorInfo>;
+
+    /// GPUs, by device index
+    fn gpus(&self) -> Vec<GpuInfo>;
+
+    /// Refresh and build a snapshot from the new readings
+    fn snapshot(&mut self) -> Snapshot {
+        self.refresh();
+
+        Snapshot {
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|elapsed| elapsed.as_millis() as u64)
+                .unwrap_or_default(),
+            cpus: self.cpus(),
+            global_cpu_usage: self.global_cpu_usage(),
+            memory: self.memory(),
+            processes: self.processes(),
+            networks: self.networks(),
+            disks: self.disks(),
+            block_devices: self.block_devices(),
+            sensors: self.sensors(),
+            gpus: self.gpus(),
+        }
+    }
+}
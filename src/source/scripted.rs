@@ -0,0 +1,82 @@
+use super::MetricSource;
+use crate::snapshot::{
+    BlockDeviceInfo, CpuInfo, DiskInfo, GpuInfo, MemoryInfo, NetworkInfo, ProcessInfo,
+    SensorInfo, Snapshot,
+};
+
+/// Deterministic source that plays back a fixed list of snapshots.
+///
+/// Each `refresh` advances to the next frame; once the script runs out the
+/// last frame is repeated. Useful for driving `ui::render` with a
+/// `TestBackend` without depending on the host machine.
+pub struct ScriptedSource {
+    frames: Vec<Snapshot>,
+    position: usize,
+    started: bool,
+}
+
+impl ScriptedSource {
+    pub fn new(frames: Vec<Snapshot>) -> Self {
+        Self {
+            frames,
+            position: 0,
+            started: false,
+        }
+    }
+
+    /// Index of the frame currently being reported
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    fn current(&self) -> Option<&Snapshot> {
+        self.frames.get(self.position)
+    }
+}
+
+impl MetricSource for ScriptedSource {
+    fn refresh(&mut self) {
+        // The first refresh reports frame 0, later ones step forward
+        if !self.started {
+            self.started = true;
+        } else if self.position + 1 < self.frames.len() {
+            self.position += 1;
+        }
+    }
+
+    fn cpus(&self) -> Vec<CpuInfo> {
+        self.current().map(|s| s.cpus.clone()).unwrap_or_default()
+    }
+
+    fn global_cpu_usage(&self) -> f32 {
+        self.current().map(|s| s.global_cpu_usage).unwrap_or(0.0)
+    }
+
+    fn memory(&self) -> MemoryInfo {
+        self.current().map(|s| s.memory.clone()).unwrap_or_default()
+    }
+
+    fn processes(&self) -> Vec<ProcessInfo> {
+        self.current().map(|s| s.processes.clone()).unwrap_or_default()
+    }
+
+    fn networks(&self) -> Vec<NetworkInfo> {
+        self.current().map(|s| s.networks.clone()).unwrap_or_default()
+    }
+
+    fn disks(&self) -> Vec<DiskInfo> {
+        self.current().map(|s| s.disks.clone()).unwrap_or_default()
+    }
+
+    fn block_devices(&self) -> Vec<BlockDeviceInfo> {
+        self.current().map(|s| s.block_devices.clone()).unwrap_or_default()
+    }
+
+    fn sensors(&self) -> Vec<SensorInfo> {
+        self.current().map(|s| s.sensors.clone()).unwrap_or_default()
+    }
+
+    fn gpus(&self) -> Vec<GpuInfo> {
+        self.current().map(|s| s.gpus.clone()).unwrap_or_default()
+    }
+}
@@ -0,0 +1,272 @@
+use super::MetricSource;
+use crate::gpu::{self, GpuBackend, ProcessGpu};
+use crate::hwmon;
+use crate::procfs::{self, DiskStat};
+use crate::snapshot::{
+    BlockDeviceInfo, CpuInfo, DiskInfo, GpuInfo, MemoryInfo, NetworkInfo, ProcessInfo,
+    SensorInfo, SensorKind,
+};
+use std::{
+    collections::HashMap,
+    path::Path,
+    time::{Duration, Instant},
+};
+use sysinfo::{Components, Disks, Networks, System, Users};
+
+/// How often mounted filesystems and user accounts are listed again; disk
+/// space is still updated every refresh
+const LIST_REFRESH_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Live metrics read through sysinfo, procfs/sysfs and the GPU backends
+pub struct SystemSource {
+    system: System,
+    networks: Networks,
+    disks: Disks,
+    components: Components,
+    users: Users,
+    /// Diskstats counters from the previous refresh, by device name
+    diskstats: HashMap<String, DiskStat>,
+    /// Block device rates computed by the last refresh
+    block_devices: Vec<BlockDeviceInfo>,
+    /// When the last refresh happened
+    last_refresh: Instant,
+    /// When disks and users were last listed
+    last_list_refresh: Instant,
+    /// Seconds covered by the last refresh, used to turn deltas into rates
+    elapsed_secs: f64,
+    gpu_backends: Vec<Box<dyn GpuBackend>>,
+    /// GPU readings taken by the last refresh, numbered across backends
+    gpus: Vec<GpuInfo>,
+    /// Per-process GPU usage taken by the last refresh, by PID
+    process_gpus: HashMap<u32, ProcessGpu>,
+}
+
+impl SystemSource {
+    pub fn new() -> Self {
+        let mut system = System::new_all();
+        system.refresh_all();
+
+        Self {
+            system,
+            networks: Networks::new_with_refreshed_list(),
+            disks: Disks::new_with_refreshed_list(),
+            components: Components::new_with_refreshed_list(),
+            users: Users::new_with_refreshed_list(),
+            diskstats: procfs::read_diskstats()
+                .into_iter()
+                .map(|stat| (stat.name.clone(), stat))
+                .collect(),
+            block_devices: Vec::new(),
+            last_refresh: Instant::now(),
+            last_list_refresh: Instant::now(),
+            elapsed_secs: 1.0,
+            gpu_backends: gpu::backends(),
+            gpus: Vec::new(),
+            process_gpus: HashMap::new(),
+        }
+    }
+}
+
+impl SystemSource {
+    /// Turn `/proc/diskstats` counter deltas into per-second rates
+    fn refresh_block_devices(&mut self) {
+        let stats = procfs::read_diskstats();
+        let secs = self.elapsed_secs;
+
+        self.block_devices = stats
+            .iter()
+            .map(|stat| {
+                let previous = self.diskstats.get(&stat.name).cloned().unwrap_or_else(|| stat.clone());
+                let delta = |now: u64, before: u64| now.saturating_sub(before) as f64 / secs;
+
+                BlockDeviceInfo {
+                    name: stat.name.clone(),
+                    read_rate: delta(stat.sectors_read, previous.sectors_read)
+                        * procfs::SECTOR_SIZE as f64,
+                    write_rate: delta(stat.sectors_written, previous.sectors_written)
+                        * procfs::SECTOR_SIZE as f64,
+                    read_iops: delta(stat.reads_completed, previous.reads_completed),
+                    write_iops: delta(stat.writes_completed, previous.writes_completed),
+                }
+            })
+            .collect();
+        self.block_devices.sort_by(|a, b| a.name.cmp(&b.name));
+
+        self.diskstats = stats
+            .into_iter()
+            .map(|stat| (stat.name.clone(), stat))
+            .collect();
+    }
+}
+
+impl MetricSource for SystemSource {
+    fn refresh(&mut self) {
+        self.system.refresh_all();
+        // refresh_list also picks up interfaces that appeared since last tick
+        self.networks.refresh_list();
+
+        self.components.refresh();
+
+        let now = Instant::now();
+        if now.duration_since(self.last_list_refresh) >= LIST_REFRESH_INTERVAL {
+            self.disks.refresh_list();
+            self.users.refresh_list();
+            self.last_list_refresh = now;
+        } else {
+            self.disks.refresh();
+        }
+
+        self.elapsed_secs = now.duration_since(self.last_refresh).as_secs_f64().max(0.001);
+        self.last_refresh = now;
+
+        self.refresh_block_devices();
+
+        self.gpus = self.gpu_backends.iter_mut().flat_map(|backend| backend.gpus()).collect();
+        for (index, gpu) in self.gpus.iter_mut().enumerate() {
+            gpu.index = index as u32;
+        }
+
+        self.process_gpus.clear();
+        for backend in &mut self.gpu_backends {
+            for (pid, usage) in backend.processes() {
+                self.process_gpus.entry(pid).or_default().add(usage);
+            }
+        }
+    }
+
+    fn cpus(&self) -> Vec<CpuInfo> {
+        self.system
+            .cpus()
+            .iter()
+            .map(|cpu| CpuInfo {
+                usage: cpu.cpu_usage(),
+                frequency: cpu.frequency(),
+            })
+            .collect()
+    }
+
+    fn global_cpu_usage(&self) -> f32 {
+        self.system.global_cpu_usage()
+    }
+
+    fn memory(&self) -> MemoryInfo {
+        let meminfo = procfs::read_meminfo();
+
+        MemoryInfo {
+            total: self.system.total_memory(),
+            used: self.system.used_memory(),
+            available: self.system.available_memory(),
+            cached: meminfo.as_ref().map(|m| m.cached),
+            buffers: meminfo.as_ref().map(|m| m.buffers),
+            swap_total: self.system.total_swap(),
+            swap_used: self.system.used_swap(),
+        }
+    }
+
+    fn processes(&self) -> Vec<ProcessInfo> {
+        self.system
+            .processes()
+            .iter()
+            .map(|(pid, process)| {
+                let disk_usage = process.disk_usage();
+                let gpu = self.process_gpus.get(&pid.as_u32());
+                let command: Vec<String> = process
+                    .cmd()
+                    .iter()
+                    .map(|arg| arg.to_string_lossy().to_string())
+                    .collect();
+
+                ProcessInfo {
+                    pid: *pid,
+                    parent: process.parent(),
+                    name: process.name().to_string_lossy().to_string(),
+                    command: command.join(" "),
+                    user: process
+                        .user_id()
+                        .and_then(|uid| self.users.get_user_by_id(uid))
+                        .map(|user| user.name().to_string()),
+                    status: format!("{:?}", process.status()),
+                    exe: process.exe().map(|p| p.to_path_buf()),
+                    cwd: process.cwd().map(|p| p.to_path_buf()),
+                    // sysinfo lists the threads besides the main one
+                    threads: process.tasks().map(|tasks| tasks.len() as u64 + 1),
+                    // Read by the app for the processes it shows
+                    nice: None,
+                    priority: None,
+                    cpu_usage: process.cpu_usage(),
+                    memory: process.memory(),
+                    virtual_memory: process.virtual_memory(),
+                    start_time: process.start_time(),
+                    run_time: process.run_time(),
+                    disk_read_total: disk_usage.total_read_bytes,
+                    disk_written_total: disk_usage.total_written_bytes,
+                    disk_read_rate: disk_usage.read_bytes as f64 / self.elapsed_secs,
+                    disk_write_rate: disk_usage.written_bytes as f64 / self.elapsed_secs,
+                    gpu_usage: gpu.and_then(|gpu| gpu.usage),
+                    gpu_memory: gpu.map(|gpu| gpu.memory),
+                }
+            })
+            .collect()
+    }
+
+    fn networks(&self) -> Vec<NetworkInfo> {
+        let mut networks: Vec<NetworkInfo> = self
+            .networks
+            .iter()
+            .map(|(name, data)| NetworkInfo {
+                name: name.clone(),
+                rx_rate: data.received() as f64 / self.elapsed_secs,
+                tx_rate: data.transmitted() as f64 / self.elapsed_secs,
+                total_received: data.total_received(),
+                total_transmitted: data.total_transmitted(),
+                packets_received: data.total_packets_received(),
+                packets_transmitted: data.total_packets_transmitted(),
+                errors_received: data.total_errors_on_received(),
+                errors_transmitted: data.total_errors_on_transmitted(),
+            })
+            .collect();
+
+        networks.sort_by(|a, b| a.name.cmp(&b.name));
+        networks
+    }
+
+    fn disks(&self) -> Vec<DiskInfo> {
+        self.disks
+            .list()
+            .iter()
+            .map(|disk| DiskInfo {
+                mount_point: disk.mount_point().to_path_buf(),
+                file_system: disk.file_system().to_string_lossy().to_string(),
+                total: disk.total_space(),
+                available: disk.available_space(),
+            })
+            .collect()
+    }
+
+    fn block_devices(&self) -> Vec<BlockDeviceInfo> {
+        self.block_devices.clone()
+    }
+
+    fn sensors(&self) -> Vec<SensorInfo> {
+        let mut sensors: Vec<SensorInfo> = self
+            .components
+            .list()
+            .iter()
+            .filter(|component| !component.temperature().is_nan())
+            .map(|component| SensorInfo {
+                label: component.label().to_string(),
+                kind: SensorKind::Temperature,
+                value: component.temperature(),
+                max: Some(component.max()).filter(|max| !max.is_nan()),
+                critical: component.critical(),
+            })
+            .collect();
+
+        sensors.extend(hwmon::read_sensors(Path::new(hwmon::HWMON_ROOT)));
+        sensors
+    }
+
+    fn gpus(&self) -> Vec<GpuInfo> {
+        self.gpus.clone()
+    }
+}
@@ -0,0 +1,139 @@
+use ratatui::style::Color;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// Colors and thresholds for load-coded values
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Theme {
+    /// Usage percentage where values turn from `ok` to `warning`
+    pub usage_warning: f32,
+    /// Usage percentage where values turn from `warning` to `critical`
+    pub usage_critical: f32,
+    /// Temperature in °C where values turn from `ok` to `warning`
+    pub temp_warning: f32,
+    /// Temperature in °C where values turn from `warning` to `critical`
+    pub temp_critical: f32,
+    #[serde(with = "color")]
+    pub ok: Color,
+    #[serde(with = "color")]
+    pub warning: Color,
+    #[serde(with = "color")]
+    pub critical: Color,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            usage_warning: 60.0,
+            usage_critical: 85.0,
+            temp_warning: 60.0,
+            temp_critical: 80.0,
+            ok: Color::Green,
+            warning: Color::Yellow,
+            critical: Color::Red,
+        }
+    }
+}
+
+impl Theme {
+    /// Get color based on usage percentage
+    pub fn usage_color(&self, usage: f32) -> Color {
+        self.level_color(usage, self.usage_warning, self.usage_critical)
+    }
+
+    /// Get color based on temperature in °C
+    pub fn temp_color(&self, temp: f32) -> Color {
+        self.level_color(temp, self.temp_warning, self.temp_critical)
+    }
+
+    fn level_color(&self, value: f32, warning: f32, critical: f32) -> Color {
+        if value < warning {
+            self.ok
+        } else if value < critical {
+            self.warning
+        } else {
+            self.critical
+        }
+    }
+
+    /// Check that thresholds are in range and ordered
+    pub fn validate(&self) -> Result<(), String> {
+        if !(0.0..=100.0).contains(&self.usage_warning) || !(0.0..=100.0).contains(&self.usage_critical) {
+            return Err("theme.usage_warning and theme.usage_critical must be between 0 and 100".to_string());
+        }
+        if self.usage_warning >= self.usage_critical {
+            return Err(format!(
+                "theme.usage_warning ({}) must be below theme.usage_critical ({})",
+                self.usage_warning, self.usage_critical
+            ));
+        }
+        if self.temp_warning >= self.temp_critical {
+            return Err(format!(
+                "theme.temp_warning ({}) must be below theme.temp_critical ({})",
+                self.temp_warning, self.temp_critical
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Colors are written as names ("green", "light red") or hex ("#ff8800")
+mod color {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(color: &Color, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&color.to_string())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Color, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        name.parse()
+            .map_err(|_| serde::de::Error::custom(format!("unknown color \"{}\"", name)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn theme(toml: &str) -> Theme {
+        toml::from_str(toml).unwrap()
+    }
+
+    #[test]
+    fn colors_values_by_threshold() {
+        let theme = Theme::default();
+        assert_eq!(theme.usage_color(59.9), Color::Green);
+        assert_eq!(theme.usage_color(60.0), Color::Yellow);
+        assert_eq!(theme.usage_color(85.0), Color::Red);
+        assert_eq!(theme.temp_color(79.0), Color::Yellow);
+    }
+
+    #[test]
+    fn reads_color_names_and_hex() {
+        let theme = theme("ok = \"light blue\"\nwarning = \"#ff8800\"");
+        assert_eq!(theme.ok, Color::LightBlue);
+        assert_eq!(theme.warning, Color::Rgb(0xff, 0x88, 0x00));
+        assert_eq!(theme.critical, Color::Red);
+
+        let written = toml::to_string(&theme).unwrap();
+        assert_eq!(toml::from_str::<Theme>(&written).unwrap(), theme);
+    }
+
+    #[test]
+    fn rejects_unknown_colors() {
+        let err = toml::from_str::<Theme>("critical = \"blurple\"").unwrap_err();
+        assert!(err.to_string().contains("unknown color \"blurple\""));
+    }
+
+    #[test]
+    fn validates_thresholds() {
+        assert_eq!(Theme::default().validate(), Ok(()));
+        assert!(theme("usage_critical = 120").validate().unwrap_err().contains("between 0 and 100"));
+        assert!(theme("usage_warning = -5").validate().unwrap_err().contains("between 0 and 100"));
+        assert_eq!(
+            theme("temp_warning = 80\ntemp_critical = 70").validate(),
+            Err("theme.temp_warning (80) must be below theme.temp_critical (70)".to_string())
+        );
+    }
+}
@@ -0,0 +1,76 @@
+use crate::app::{App, ToastLevel};
+use chrono::{DateTime, Local};
+use ratatui::{
+    layout::{Alignment, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+    Frame,
+};
+
+/// Render the alert log: firing alerts first, then events newest first
+pub fn render(f: &mut Frame, app: &App, area: Rect) {
+    let firing = app.alerts.firing();
+
+    let block = Block::default()
+        .title(format!(" Alerts ({} firing) ", firing.len()))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(if firing.is_empty() {
+            Color::Green
+        } else {
+            Color::Red
+        }));
+
+    if firing.is_empty() && app.alerts.log.is_empty() {
+        let paragraph = Paragraph::new(vec![
+            Line::from(""),
+            Line::from(vec![Span::styled(
+                "No alerts raised",
+                Style::default().fg(Color::Gray).add_modifier(Modifier::ITALIC),
+            )]),
+        ])
+        .block(block)
+        .alignment(Alignment::Center);
+        f.render_widget(paragraph, area);
+        return;
+    }
+
+    let mut lines: Vec<Line> = firing
+        .iter()
+        .map(|rule| {
+            Line::from(vec![
+                Span::styled("● ", Style::default().fg(Color::Red)),
+                Span::styled(rule.clone(), Style::default().add_modifier(Modifier::BOLD)),
+            ])
+        })
+        .collect();
+
+    for event in app.alerts.log.iter().rev() {
+        let time = DateTime::from_timestamp_millis(event.timestamp as i64)
+            .map(|time| time.with_timezone(&Local).format("%H:%M:%S").to_string())
+            .unwrap_or_default();
+        let (marker, color) = if event.fired {
+            ("▲ fired  ", level_color(event.level))
+        } else {
+            ("▼ cleared", Color::Green)
+        };
+
+        lines.push(Line::from(vec![
+            Span::styled(format!("{} ", time), Style::default().fg(Color::Gray)),
+            Span::styled(marker, Style::default().fg(color)),
+            Span::raw(format!(" {} ", event.rule)),
+            Span::styled(format!("({})", event.value), Style::default().fg(Color::Gray)),
+        ]));
+    }
+
+    f.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+fn level_color(level: ToastLevel) -> Color {
+    match level {
+        ToastLevel::Info => Color::Blue,
+        ToastLevel::Success => Color::Green,
+        ToastLevel::Warning => Color::Yellow,
+        ToastLevel::Error => Color::Red,
+    }
+}
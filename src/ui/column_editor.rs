@@ -0,0 +1,77 @@
+use crate::app::App;
+use crate::column::Column;
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+/// Render the process column editor overlay
+pub fn render(f: &mut Frame, app: &App) {
+    // One line per column plus the hint lines and borders
+    let area = centered_rect(48, Column::ALL.len() as u16 + 6, f.size());
+
+    // Clear the area first
+    f.render_widget(Clear, area);
+
+    let mut text = vec![
+        Line::from(vec![Span::styled(
+            "Shown columns come first, in table order",
+            Style::default().fg(Color::Gray),
+        )]),
+        Line::from(""),
+    ];
+
+    for (i, (column, shown)) in app.editor_columns().into_iter().enumerate() {
+        let checkbox = if shown { "[x] " } else { "[ ] " };
+        let style = if i == app.column_cursor {
+            Style::default()
+                .fg(Color::Black)
+                .bg(Color::Cyan)
+                .add_modifier(Modifier::BOLD)
+        } else if shown {
+            Style::default().fg(Color::White)
+        } else {
+            Style::default().fg(Color::DarkGray)
+        };
+
+        text.push(Line::from(vec![
+            Span::styled(checkbox, style),
+            Span::styled(format!("{:<13}{}", column.header(), column.label()), style),
+        ]));
+    }
+
+    text.push(Line::from(""));
+    text.push(Line::from(vec![
+        Span::styled("Space", Style::default().fg(Color::Green)),
+        Span::styled(": show/hide  ", Style::default().fg(Color::Gray)),
+        Span::styled("J/K", Style::default().fg(Color::Green)),
+        Span::styled(": move  ", Style::default().fg(Color::Gray)),
+        Span::styled("o/Esc", Style::default().fg(Color::Green)),
+        Span::styled(": close", Style::default().fg(Color::Gray)),
+    ]));
+
+    let paragraph = Paragraph::new(text).block(
+        Block::default()
+            .title(" Process Columns ")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan)),
+    );
+
+    f.render_widget(paragraph, area);
+}
+
+/// Center a fixed-size rect inside `r`, shrinking it if `r` is smaller
+fn centered_rect(width: u16, height: u16, r: Rect) -> Rect {
+    let width = width.min(r.width);
+    let height = height.min(r.height);
+
+    Rect {
+        x: r.x + (r.width - width) / 2,
+        y: r.y + (r.height - height) / 2,
+        width,
+        height,
+    }
+}
@@ -1,38 +1,61 @@
-use crate::app::{ActionConfirmation, ProcessAction};
+use crate::app::{signal_name, ActionConfirmation, ProcessAction, SIGNALS};
 use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Clear, Paragraph},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
     Frame,
 };
+use sysinfo::{Pid, Signal};
 
 /// Render confirmation dialog
 pub fn render(f: &mut Frame, confirmation: &ActionConfirmation) {
-    let area = centered_rect(50, 30, f.size());
-
-    // Clear the area first
-    f.render_widget(Clear, area);
-
     let (action_name, action_color, warning) = match confirmation.action {
+        ProcessAction::Kill if cfg!(target_family = "unix") => (
+            "Terminate Process (SIGTERM)".to_string(),
+            Color::Red,
+            "The process is asked to exit and may clean up first.",
+        ),
         ProcessAction::Kill => (
-            "Kill Process",
+            "Kill Process".to_string(),
             Color::Red,
             "This will forcefully terminate the process!",
         ),
         ProcessAction::Suspend => (
-            "Suspend Process",
+            "Suspend Process".to_string(),
             Color::Magenta,
             "This will pause the process execution.",
         ),
         ProcessAction::Resume => (
-            "Resume Process",
+            "Resume Process".to_string(),
             Color::Green,
             "This will continue the process execution.",
         ),
+        ProcessAction::Signal(signal) => (
+            format!("Send {}", signal_name(signal)),
+            match signal {
+                Signal::Kill | Signal::Quit => Color::Red,
+                Signal::Stop | Signal::Continue => Color::Magenta,
+                _ => Color::Yellow,
+            },
+            SIGNALS
+                .iter()
+                .find(|(s, _, _)| *s == signal)
+                .map_or("", |(_, _, description)| description),
+        ),
+        ProcessAction::Renice(nice) => (
+            format!("Set Nice Value {}", nice),
+            Color::Cyan,
+            "Lower values get more CPU time.",
+        ),
+        ProcessAction::SetIoPriority(io) => (
+            format!("Set I/O Priority {}", io),
+            Color::Cyan,
+            "This changes how disk time is shared.",
+        ),
     };
 
-    let text = vec![
+    let mut text = vec![
         Line::from(""),
         Line::from(vec![Span::styled(
             action_name,
@@ -41,14 +64,50 @@ pub fn render(f: &mut Frame, confirmation: &ActionConfirmation) {
                 .add_modifier(Modifier::BOLD),
         )]),
         Line::from(""),
-        Line::from(vec![Span::styled(
-            format!("Process: {}", confirmation.process_name),
+    ];
+
+    let (roots, descendants) = confirmation
+        .targets
+        .split_at(confirmation.targets.len() - confirmation.descendants);
+    if confirmation.marked {
+        text.push(Line::from(vec![Span::styled(
+            format!("{} marked process{}:", roots.len(), plural(roots.len(), "es")),
+            Style::default().fg(Color::White).add_modifier(Modifier::BOLD),
+        )]));
+        text.push(Line::from(vec![Span::styled(
+            process_names(roots),
             Style::default().fg(Color::White),
-        )]),
-        Line::from(vec![Span::styled(
-            format!("PID: {}", confirmation.pid),
+        )]));
+    } else if let Some((pid, name)) = roots.first() {
+        text.push(Line::from(vec![Span::styled(
+            format!("Process: {}", name),
             Style::default().fg(Color::White),
-        )]),
+        )]));
+        text.push(Line::from(vec![Span::styled(
+            format!("PID: {}", pid),
+            Style::default().fg(Color::White),
+        )]));
+    }
+
+    if !descendants.is_empty() {
+        text.push(Line::from(""));
+        text.push(Line::from(vec![Span::styled(
+            format!(
+                "Also affects {} descendant{}:",
+                descendants.len(),
+                plural(descendants.len(), "s")
+            ),
+            Style::default()
+                .fg(action_color)
+                .add_modifier(Modifier::BOLD),
+        )]));
+        text.push(Line::from(vec![Span::styled(
+            process_names(descendants),
+            Style::default().fg(Color::White),
+        )]));
+    }
+
+    text.extend([
         Line::from(""),
         Line::from(vec![Span::styled(
             warning,
@@ -62,7 +121,22 @@ pub fn render(f: &mut Frame, confirmation: &ActionConfirmation) {
             Span::styled("n/Esc", Style::default().fg(Color::Red)),
             Span::styled(" to cancel", Style::default().fg(Color::Gray)),
         ]),
-    ];
+    ]);
+
+    // Batch and tree actions need room for the list of affected processes;
+    // grow past the usual size when the text wouldn't fit, such as on small
+    // terminals, so the confirm hint is never cut off
+    let screen = f.size();
+    let percent_y = if confirmation.targets.len() == 1 { 30 } else { 50 };
+    let mut area = centered_rect(50, percent_y, screen);
+    let needed = wrapped_height(&text, area.width.saturating_sub(2)) + 2;
+    if area.height < needed {
+        area.height = needed.min(screen.height);
+        area.y = screen.y + (screen.height - area.height) / 2;
+    }
+
+    // Clear the area first
+    f.render_widget(Clear, area);
 
     let paragraph = Paragraph::new(text)
         .block(
@@ -71,11 +145,49 @@ pub fn render(f: &mut Frame, confirmation: &ActionConfirmation) {
                 .borders(Borders::ALL)
                 .border_style(Style::default().fg(action_color)),
         )
-        .alignment(Alignment::Center);
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true });
 
     f.render_widget(paragraph, area);
 }
 
+/// Names of the affected processes with repeats counted, such as
+/// "python (×3), sleep", in list order
+fn process_names(processes: &[(Pid, String)]) -> String {
+    let mut counts: Vec<(&str, usize)> = Vec::new();
+    for (_, name) in processes {
+        match counts.iter_mut().find(|(n, _)| n == name) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((name, 1)),
+        }
+    }
+
+    counts
+        .iter()
+        .map(|(name, count)| match count {
+            1 => name.to_string(),
+            _ => format!("{} (×{})", name, count),
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Rows `text` takes when wrapped to `width`
+fn wrapped_height(text: &[Line], width: u16) -> u16 {
+    let width = width.max(1) as usize;
+    text.iter()
+        .map(|line| line.width().div_ceil(width).max(1) as u16)
+        .sum()
+}
+
+fn plural(count: usize, suffix: &str) -> &str {
+    if count == 1 {
+        ""
+    } else {
+        suffix
+    }
+}
+
 /// Helper function to create centered rect
 fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
     let popup_layout = Layout::default()
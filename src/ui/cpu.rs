@@ -108,8 +108,8 @@ fn render_logo(f: &mut Frame, area: Rect) {
 
 /// Render overall CPU usage
 fn render_overall_cpu(f: &mut Frame, app: &App, area: Rect) {
-    let cpu_usage = app.system.global_cpu_usage();
-    let color = get_usage_color(cpu_usage);
+    let cpu_usage = app.snapshot.global_cpu_usage;
+    let color = app.theme.usage_color(cpu_usage);
 
     let gauge = Gauge::default()
         .block(
@@ -170,12 +170,14 @@ fn render_overall_cpu_graph(f: &mut Frame, app: &App, area: Rect) {
 
 /// Render per-core CPU usage
 fn render_per_core_cpu(f: &mut Frame, app: &App, area: Rect) {
-    let cpus = app.system.cpus();
-    let core_count = cpus.len();
+    let core_count = app.snapshot.cpus.len();
+    if core_count == 0 {
+        return;
+    }
 
     // Calculate how many cores per row (4 columns for better fit)
     let cols = 4.min(core_count);
-    let rows = (core_count + cols - 1) / cols;
+    let rows = core_count.div_ceil(cols);
 
     // Create layout
     let mut constraints = vec![];
@@ -212,10 +214,10 @@ fn render_per_core_cpu(f: &mut Frame, app: &App, area: Rect) {
 
 /// Render individual core (compact version)
 fn render_core(f: &mut Frame, app: &App, area: Rect, core_idx: usize) {
-    let cpu = &app.system.cpus()[core_idx];
-    let usage = cpu.cpu_usage();
-    let frequency = cpu.frequency(); // MHz
-    let color = get_usage_color(usage);
+    let cpu = &app.snapshot.cpus[core_idx];
+    let usage = cpu.usage;
+    let frequency = cpu.frequency; // MHz
+    let color = app.theme.usage_color(usage);
 
     // Get history data for sparkline
     let empty_vec: Vec<f32> = Vec::new();
@@ -272,14 +274,3 @@ fn render_core(f: &mut Frame, app: &App, area: Rect, core_idx: usize) {
         f.render_widget(sparkline, chunks[1]);
     }
 }
-
-/// Get color based on usage percentage
-fn get_usage_color(usage: f32) -> Color {
-    if usage < 60.0 {
-        Color::Green
-    } else if usage < 85.0 {
-        Color::Yellow
-    } else {
-        Color::Red
-    }
-}
@@ -1,33 +1,40 @@
-use crate::app::App;
+use super::{format_bytes, format_rate};
+use crate::app::{App, ProcessHistory};
+use crate::priority::IoClass;
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
+    symbols,
     text::{Line, Span},
-    widgets::{Block, Borders, Gauge, Paragraph, Wrap},
+    widgets::{Axis, Block, Borders, Chart, Dataset, Gauge, GraphType, Paragraph, Wrap},
     Frame,
 };
 
+/// Height of the history charts below the details text
+const HISTORY_HEIGHT: u16 = 18;
+
 /// Render process details
 pub fn render(f: &mut Frame, app: &App, area: Rect) {
-    if let Some((_pid, process)) = app.get_selected_process() {
+    if let Some(process) = app.get_selected_process() {
+        // Charts only when the details text keeps a reasonable height
+        let history = app
+            .process_history
+            .get(&process.pid)
+            .filter(|_| area.height >= 6 + HISTORY_HEIGHT + 12);
+
         let chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
                 Constraint::Length(3),  // CPU gauge
                 Constraint::Length(3),  // Memory gauge
                 Constraint::Min(5),     // Details text
+                Constraint::Length(if history.is_some() { HISTORY_HEIGHT } else { 0 }),
             ])
             .split(area);
 
         // CPU usage gauge
-        let cpu_usage = process.cpu_usage();
-        let cpu_color = if cpu_usage >= 85.0 {
-            Color::Red
-        } else if cpu_usage >= 60.0 {
-            Color::Yellow
-        } else {
-            Color::Green
-        };
+        let cpu_usage = process.cpu_usage;
+        let cpu_color = app.theme.usage_color(cpu_usage);
 
         let cpu_gauge = Gauge::default()
             .block(
@@ -43,9 +50,9 @@ pub fn render(f: &mut Frame, app: &App, area: Rect) {
         f.render_widget(cpu_gauge, chunks[0]);
 
         // Memory usage gauge
-        let mem_usage = process.memory() / 1024 / 1024; // MB
-        let total_mem = app.system.total_memory() / 1024 / 1024; // MB
-        let mem_ratio = (mem_usage as f64 / total_mem as f64).min(1.0);
+        let mem_usage = process.memory / 1024 / 1024; // MB
+        let total_mem = app.snapshot.memory.total / 1024 / 1024; // MB
+        let mem_ratio = (mem_usage as f64 / total_mem.max(1) as f64).min(1.0);
 
         let mem_gauge = Gauge::default()
             .block(
@@ -65,15 +72,15 @@ pub fn render(f: &mut Frame, app: &App, area: Rect) {
 
         details.push(Line::from(vec![
             Span::styled("Name: ", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
-            Span::raw(process.name().to_string_lossy().to_string()),
+            Span::raw(process.name.clone()),
         ]));
 
         details.push(Line::from(vec![
             Span::styled("PID: ", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
-            Span::raw(format!("{}", process.pid())),
+            Span::raw(format!("{}", process.pid)),
         ]));
 
-        if let Some(parent) = process.parent() {
+        if let Some(parent) = process.parent {
             details.push(Line::from(vec![
                 Span::styled("Parent PID: ", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
                 Span::raw(format!("{}", parent)),
@@ -82,17 +89,40 @@ pub fn render(f: &mut Frame, app: &App, area: Rect) {
 
         details.push(Line::from(vec![
             Span::styled("Status: ", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
-            Span::raw(format!("{:?}", process.status())),
+            Span::raw(process.status.clone()),
         ]));
 
-        if let Some(exe) = process.exe() {
+        if let Some(nice) = process.nice {
+            details.push(Line::from(vec![
+                Span::styled("Nice: ", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
+                Span::raw(format!("{}", nice)),
+                Span::styled(
+                    process.priority.map(|p| format!("  (priority {})", p)).unwrap_or_default(),
+                    Style::default().fg(Color::Gray),
+                ),
+            ]));
+        }
+
+        if let Some(io) = app.selected_io_priority() {
+            // Without a class the kernel derives best-effort from the nice value
+            let text = match io.class {
+                IoClass::None => "none (follows nice)".to_string(),
+                _ => io.to_string(),
+            };
+            details.push(Line::from(vec![
+                Span::styled("I/O Priority: ", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
+                Span::raw(text),
+            ]));
+        }
+
+        if let Some(ref exe) = process.exe {
             details.push(Line::from(vec![
                 Span::styled("Executable: ", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
                 Span::raw(exe.to_string_lossy().to_string()),
             ]));
         }
 
-        if let Some(cwd) = process.cwd() {
+        if let Some(ref cwd) = process.cwd {
             details.push(Line::from(vec![
                 Span::styled("Working Dir: ", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
                 Span::raw(cwd.to_string_lossy().to_string()),
@@ -104,38 +134,66 @@ pub fn render(f: &mut Frame, app: &App, area: Rect) {
             Span::styled("Disk Usage: ", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
         ]));
 
-        let disk_usage = process.disk_usage();
         details.push(Line::from(vec![
             Span::raw("  Read: "),
             Span::styled(
-                format!("{} bytes", disk_usage.total_read_bytes),
+                format!("{} bytes", process.disk_read_total),
                 Style::default().fg(Color::Cyan),
             ),
         ]));
         details.push(Line::from(vec![
             Span::raw("  Write: "),
             Span::styled(
-                format!("{} bytes", disk_usage.total_written_bytes),
+                format!("{} bytes", process.disk_written_total),
                 Style::default().fg(Color::Cyan),
             ),
         ]));
 
+        if process.gpu_usage.is_some() || process.gpu_memory.is_some() {
+            details.push(Line::from(""));
+            details.push(Line::from(vec![
+                Span::styled("GPU Usage: ", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
+            ]));
+
+            if let Some(usage) = process.gpu_usage {
+                details.push(Line::from(vec![
+                    Span::raw("  Utilization: "),
+                    Span::styled(format!("{:.1}%", usage), Style::default().fg(app.theme.usage_color(usage))),
+                ]));
+            }
+            if let Some(memory) = process.gpu_memory {
+                details.push(Line::from(vec![
+                    Span::raw("  Memory: "),
+                    Span::styled(format_bytes(memory), Style::default().fg(Color::Cyan)),
+                ]));
+            }
+        }
+
         details.push(Line::from(""));
         details.push(Line::from(vec![
             Span::styled("Virtual Memory: ", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
-            Span::raw(format!("{} MB", process.virtual_memory() / 1024 / 1024)),
+            Span::raw(format!("{} MB", process.virtual_memory / 1024 / 1024)),
         ]));
 
+        let title = if app.pinned.contains(&process.pid) {
+            " Process Details (pinned) "
+        } else {
+            " Process Details "
+        };
         let paragraph = Paragraph::new(details)
             .block(
                 Block::default()
-                    .title(" Process Details ")
+                    .title(title)
                     .borders(Borders::ALL)
                     .border_style(Style::default().fg(Color::Cyan)),
             )
             .wrap(Wrap { trim: true });
 
         f.render_widget(paragraph, chunks[2]);
+
+        if let Some(history) = history {
+            render_history(f, app, history, chunks[3]);
+        }
     } else {
         // No process selected
         let text = vec![Line::from(vec![Span::styled(
@@ -155,3 +213,99 @@ pub fn render(f: &mut Frame, app: &App, area: Rect) {
         f.render_widget(paragraph, area);
     }
 }
+
+/// Render CPU and RSS side by side, with disk I/O below
+fn render_history(f: &mut Frame, app: &App, history: &ProcessHistory, area: Rect) {
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(10), Constraint::Min(0)])
+        .split(area);
+    let top = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(rows[0]);
+
+    let points = |values: &mut dyn Iterator<Item = f64>| -> Vec<(f64, f64)> {
+        values.enumerate().map(|(i, v)| (i as f64, v)).collect()
+    };
+    let cpu_data = points(&mut history.cpu.iter().map(|&v| v as f64));
+    let memory_data = points(&mut history.memory.iter().map(|&v| v as f64));
+    let read_data = points(&mut history.disk_read.iter().copied());
+    let write_data = points(&mut history.disk_write.iter().copied());
+
+    // CPU can pass 100% for multi-threaded processes
+    let cpu_max = history.cpu.iter().fold(100.0_f32, |max, &v| max.max(v)) as f64;
+    let chart = history_chart(
+        app,
+        " CPU History ",
+        vec![dataset("CPU %", Color::Cyan, &cpu_data)],
+        cpu_max,
+        format!("{:.0}%", cpu_max),
+    );
+    f.render_widget(chart, top[0]);
+
+    // Scale to the peak so slow leaks show up as a slope
+    let memory_max = history.memory.iter().copied().max().unwrap_or(0).max(1024 * 1024) as f64;
+    let chart = history_chart(
+        app,
+        " RSS History ",
+        vec![dataset("RSS", Color::Blue, &memory_data)],
+        memory_max,
+        format_bytes(memory_max as u64),
+    );
+    f.render_widget(chart, top[1]);
+
+    // 1 KB/s floor so an idle process stays flat
+    let disk_max = history
+        .disk_read
+        .iter()
+        .chain(history.disk_write.iter())
+        .fold(1024.0_f64, |max, &v| max.max(v));
+    let chart = history_chart(
+        app,
+        " Disk I/O History ",
+        vec![
+            dataset("Read", Color::Green, &read_data),
+            dataset("Write", Color::Magenta, &write_data),
+        ],
+        disk_max,
+        format_rate(disk_max),
+    );
+    f.render_widget(chart, rows[1]);
+}
+
+fn dataset<'a>(name: &'a str, color: Color, data: &'a [(f64, f64)]) -> Dataset<'a> {
+    Dataset::default()
+        .name(name)
+        .marker(symbols::Marker::Braille)
+        .graph_type(GraphType::Line)
+        .style(Style::default().fg(color))
+        .data(data)
+}
+
+fn history_chart<'a>(
+    app: &App,
+    title: &'a str,
+    datasets: Vec<Dataset<'a>>,
+    y_max: f64,
+    y_label: String,
+) -> Chart<'a> {
+    Chart::new(datasets)
+        .block(
+            Block::default()
+                .title(title)
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Cyan)),
+        )
+        .x_axis(
+            Axis::default()
+                .style(Style::default().fg(Color::Gray))
+                .bounds([0.0, app.history_size as f64]),
+        )
+        .y_axis(
+            Axis::default()
+                .style(Style::default().fg(Color::Gray))
+                .bounds([0.0, y_max])
+                .labels(vec![Span::raw("0"), Span::raw(y_label)]),
+        )
+}
@@ -0,0 +1,125 @@
+use super::{format_bytes, format_rate};
+use crate::app::App;
+use crate::snapshot::{BlockDeviceInfo, DiskInfo};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    symbols,
+    text::{Line, Span},
+    widgets::{Block, Borders, LineGauge, Paragraph, Sparkline},
+    Frame,
+};
+
+/// Render disks panel (filesystem usage and block device I/O)
+pub fn render(f: &mut Frame, app: &App, area: Rect) {
+    let block = Block::default()
+        .title(" Disks ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::LightYellow));
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let disks = &app.snapshot.disks;
+    let devices = &app.snapshot.block_devices;
+
+    // Filesystems get one line each, up to half the panel
+    let fs_height = (disks.len() as u16).min(inner.height / 2).max(1);
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(fs_height), Constraint::Min(0)])
+        .split(inner);
+
+    let fs_rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(vec![Constraint::Length(1); fs_height as usize])
+        .split(chunks[0]);
+    for (disk, row) in disks.iter().zip(fs_rows.iter()) {
+        render_filesystem(f, app, disk, *row);
+    }
+
+    // Each block device takes a text line plus a sparkline
+    let device_count = (chunks[1].height / 2) as usize;
+    let device_rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(vec![Constraint::Length(2); device_count])
+        .split(chunks[1]);
+    for (device, row) in devices.iter().zip(device_rows.iter()) {
+        render_block_device(f, app, device, *row);
+    }
+}
+
+/// Render a filesystem usage gauge
+fn render_filesystem(f: &mut Frame, app: &App, disk: &DiskInfo, area: Rect) {
+    let usage = disk.usage();
+    let used = disk.total.saturating_sub(disk.available);
+
+    let gauge = LineGauge::default()
+        .line_set(symbols::line::THICK)
+        .filled_style(Style::default().fg(app.theme.usage_color(usage)))
+        .unfilled_style(Style::default().fg(Color::DarkGray))
+        .label(Span::styled(
+            format!(
+                "{:<12} {:<6} {:>9} / {:<9}",
+                truncate_mount(&disk.mount_point.to_string_lossy(), 12),
+                disk.file_system,
+                format_bytes(used),
+                format_bytes(disk.total)
+            ),
+            Style::default().fg(Color::White),
+        ))
+        .ratio((usage as f64 / 100.0).min(1.0));
+
+    f.render_widget(gauge, area);
+}
+
+/// Render a block device's rates with a throughput sparkline
+fn render_block_device(f: &mut Frame, app: &App, device: &BlockDeviceInfo, area: Rect) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(1), Constraint::Length(1)])
+        .split(area);
+
+    let line = Line::from(vec![
+        Span::styled(
+            format!("{:<8}", device.name),
+            Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+        ),
+        Span::styled("R ", Style::default().fg(Color::Cyan)),
+        Span::raw(format!("{:<11}", format_rate(device.read_rate))),
+        Span::styled("W ", Style::default().fg(Color::Magenta)),
+        Span::raw(format!("{:<11}", format_rate(device.write_rate))),
+        Span::styled(
+            format!("{:.0}/{:.0} io/s", device.read_iops, device.write_iops),
+            Style::default().fg(Color::Gray),
+        ),
+    ]);
+    f.render_widget(Paragraph::new(line), chunks[0]);
+
+    // Combined read + write throughput
+    if let Some(history) = app.disk_history.get(&device.name) {
+        let data: Vec<u64> = history
+            .read
+            .iter()
+            .zip(history.write.iter())
+            .map(|(r, w)| (r + w) as u64)
+            .collect();
+
+        if data.len() >= 2 {
+            let sparkline = Sparkline::default()
+                .data(&data)
+                .style(Style::default().fg(Color::LightYellow));
+            f.render_widget(sparkline, chunks[1]);
+        }
+    }
+}
+
+/// Keep the tail of long mount paths, which is usually the informative part
+fn truncate_mount(mount: &str, max_len: usize) -> String {
+    let count = mount.chars().count();
+    if count <= max_len {
+        mount.to_string()
+    } else {
+        let tail: String = mount.chars().skip(count - (max_len - 1)).collect();
+        format!("…{}", tail)
+    }
+}
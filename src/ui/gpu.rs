@@ -1,33 +1,100 @@
-use crate::app::App;
+use super::{format_bytes, format_rate, key_hint};
+use crate::app::{App, GpuHistory};
+use crate::keymap::Action;
+use crate::snapshot::GpuInfo;
 use ratatui::{
-    layout::Rect,
+    layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
+    symbols,
     text::{Line, Span},
-    widgets::{Block, Borders},
+    widgets::{Axis, Block, Borders, Chart, Dataset, Gauge, GraphType, Paragraph, Wrap},
     Frame,
 };
 
-#[cfg(feature = "gpu-nvidia")]
-use ratatui::{
-    layout::{Constraint, Direction, Layout},
-    symbols,
-    widgets::{Axis, Chart, Dataset, Gauge, GraphType},
-};
+/// Height of the gauges and chart for one GPU
+const DETAIL_HEIGHT: u16 = 17;
 
-/// Render GPU information
-#[cfg(feature = "gpu-nvidia")]
+/// Render GPU information: a list when there are several GPUs, then the
+/// gauges and chart of the selected one
 pub fn render(f: &mut Frame, app: &App, area: Rect) {
-    if let Some(ref gpu_info) = app.gpu_info {
-        render_nvidia_gpu(f, app, area, gpu_info);
-    } else {
+    let gpus = &app.snapshot.gpus;
+    let Some(gpu_info) = gpus.get(app.selected_gpu) else {
         render_no_gpu(f, area);
+        return;
+    };
+    let history = app.gpu_history.get(app.selected_gpu);
+
+    if gpus.len() == 1 {
+        render_gpu_detail(f, app, area, gpu_info, history);
+        return;
+    }
+
+    // The list gets priority; details only when there's room for them
+    let list_height = (gpus.len() as u16 + 2).min(area.height);
+    if area.height < list_height + DETAIL_HEIGHT {
+        render_gpu_list(f, app, area);
+        return;
     }
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(list_height), Constraint::Min(0)])
+        .split(area);
+
+    render_gpu_list(f, app, chunks[0]);
+    render_gpu_detail(f, app, chunks[1], gpu_info, history);
 }
 
-/// Render GPU information (no GPU feature)
-#[cfg(not(feature = "gpu-nvidia"))]
-pub fn render(f: &mut Frame, _app: &App, area: Rect) {
-    render_no_gpu(f, area);
+/// Render one compact row per GPU, marking the selected one
+fn render_gpu_list(f: &mut Frame, app: &App, area: Rect) {
+    let gpus = &app.snapshot.gpus;
+    let name_width = gpus.iter().map(|gpu| gpu.name.chars().count()).max().unwrap_or(0);
+
+    let lines: Vec<Line> = gpus
+        .iter()
+        .enumerate()
+        .map(|(i, gpu)| {
+            let selected = i == app.selected_gpu;
+            let name_style = if selected {
+                Style::default().fg(Color::Magenta).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            };
+
+            Line::from(vec![
+                Span::styled(if selected { "▶ " } else { "  " }, name_style),
+                Span::styled(format!("{:<2} {:<w$}  ", gpu.index, gpu.name, w = name_width), name_style),
+                Span::styled(
+                    format!("{:>5.1}%  ", gpu.usage),
+                    Style::default().fg(app.theme.usage_color(gpu.usage)),
+                ),
+                Span::styled(
+                    format!("{:>3}°C  ", gpu.temperature),
+                    Style::default().fg(app.theme.temp_color(gpu.temperature as f32)),
+                ),
+                Span::raw(memory_text(gpu)),
+            ])
+        })
+        .collect();
+
+    let mut title = format!(" GPUs ({}) ", gpus.len());
+    if let Some(hint) = key_hint(app, &[Action::NextGpu], "next") {
+        title.push_str(&format!("[{}] ", hint));
+    }
+    let block = Block::default()
+        .title(title)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Magenta));
+    f.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+/// Used and total memory, or a note for GPUs without dedicated memory
+fn memory_text(gpu: &GpuInfo) -> String {
+    if gpu.memory_total == 0 {
+        "Shared system memory".to_string()
+    } else {
+        format!("{} / {}", format_bytes(gpu.memory_used), format_bytes(gpu.memory_total))
+    }
 }
 
 /// Render when no GPU monitoring available
@@ -41,13 +108,18 @@ fn render_no_gpu(f: &mut Frame, area: Rect) {
                 .add_modifier(Modifier::BOLD),
         )]),
         Line::from(""),
+        Line::from(vec![Span::styled(
+            "No AMD or Intel GPU found in /sys/class/drm",
+            Style::default().fg(Color::Gray),
+        )]),
         Line::from(vec![Span::styled(
             "Compile with --features gpu-nvidia for NVIDIA GPU support",
             Style::default().fg(Color::Gray),
         )]),
     ];
 
-    let paragraph = ratatui::widgets::Paragraph::new(text)
+    let paragraph = Paragraph::new(text)
+        .wrap(Wrap { trim: true })
         .block(
             Block::default()
                 .title(" GPU Monitor ")
@@ -59,26 +131,56 @@ fn render_no_gpu(f: &mut Frame, area: Rect) {
     f.render_widget(paragraph, area);
 }
 
-#[cfg(feature = "gpu-nvidia")]
-fn render_nvidia_gpu(f: &mut Frame, app: &App, area: Rect, gpu_info: &crate::app::GpuInfo) {
+/// Render the gauges and charts of one GPU, whichever backend reported it
+fn render_gpu_detail(
+    f: &mut Frame,
+    app: &App,
+    area: Rect,
+    gpu_info: &GpuInfo,
+    history: Option<&GpuHistory>,
+) {
+    let empty = GpuHistory::default();
+    let history = history.unwrap_or(&empty);
+    let has_power = gpu_info.power_usage().is_some();
+    let has_telemetry = gpu_info.sm_clock.is_some()
+        || gpu_info.fan_speed.is_some()
+        || gpu_info.encoder_usage.is_some()
+        || gpu_info.pcie_rx_rate.is_some();
+    // The PCIe graph only goes in when everything above keeps its height
+    let fixed_height = 9 + if has_power { 3 } else { 0 } + if has_telemetry { 5 } else { 0 };
+    let has_pcie = !history.pcie_rx.is_empty() && area.height >= fixed_height + 8 + 8;
+
+    // Sections only NVML can fill are left out for other GPUs
+    let mut constraints = vec![
+        Constraint::Length(3), // GPU usage gauge
+        Constraint::Length(3), // Memory gauge
+        Constraint::Length(3), // Temperature gauge
+    ];
+    if has_power {
+        constraints.push(Constraint::Length(3)); // Power gauge
+    }
+    if has_telemetry {
+        constraints.push(Constraint::Length(5)); // Clocks, codecs, PCIe, throttling
+    }
+    constraints.push(Constraint::Min(8)); // Usage graph
+    if has_pcie {
+        constraints.push(Constraint::Length(8)); // PCIe graph
+    }
+
     let chunks = Layout::default()
         .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Length(3), // GPU usage gauge
-            Constraint::Length(3), // Memory gauge
-            Constraint::Length(3), // Temperature gauge
-            Constraint::Length(8), // Usage graph
-        ])
+        .constraints(constraints)
         .split(area);
+    let mut next = chunks.iter().copied();
 
     // GPU usage gauge
-    let gpu_usage = gpu_info.usage as f64 / 100.0;
-    let usage_color = get_gpu_usage_color(gpu_info.usage);
+    let gpu_usage = (gpu_info.usage as f64 / 100.0).min(1.0);
+    let usage_color = app.theme.usage_color(gpu_info.usage);
 
     let gauge = Gauge::default()
         .block(
             Block::default()
-                .title(format!(" {} - GPU Usage ", gpu_info.name))
+                .title(format!(" GPU {}: {} - Usage ", gpu_info.index, gpu_info.name))
                 .borders(Borders::ALL)
                 .border_style(Style::default().fg(Color::Magenta)),
         )
@@ -91,10 +193,10 @@ fn render_nvidia_gpu(f: &mut Frame, app: &App, area: Rect, gpu_info: &crate::app
         .ratio(gpu_usage)
         .label(format!("{:.1}%", gpu_info.usage));
 
-    f.render_widget(gauge, chunks[0]);
+    f.render_widget(gauge, next.next().unwrap());
 
     // Memory usage gauge
-    let mem_ratio = gpu_info.memory_used as f64 / gpu_info.memory_total as f64;
+    let mem_ratio = (gpu_info.memory_usage() as f64 / 100.0).min(1.0);
     let mem_gauge = Gauge::default()
         .block(
             Block::default()
@@ -109,18 +211,17 @@ fn render_nvidia_gpu(f: &mut Frame, app: &App, area: Rect, gpu_info: &crate::app
                 .add_modifier(Modifier::BOLD),
         )
         .ratio(mem_ratio)
-        .label(format!(
-            "{} MB / {} MB ({:.1}%)",
-            gpu_info.memory_used,
-            gpu_info.memory_total,
-            mem_ratio * 100.0
-        ));
+        .label(if gpu_info.memory_total == 0 {
+            memory_text(gpu_info)
+        } else {
+            format!("{} ({:.1}%)", memory_text(gpu_info), mem_ratio * 100.0)
+        });
 
-    f.render_widget(mem_gauge, chunks[1]);
+    f.render_widget(mem_gauge, next.next().unwrap());
 
     // Temperature gauge
     let temp_ratio = (gpu_info.temperature as f64 / 100.0).min(1.0);
-    let temp_color = get_temp_color(gpu_info.temperature);
+    let temp_color = app.theme.temp_color(gpu_info.temperature as f32);
 
     let temp_gauge = Gauge::default()
         .block(
@@ -138,29 +239,71 @@ fn render_nvidia_gpu(f: &mut Frame, app: &App, area: Rect, gpu_info: &crate::app
         .ratio(temp_ratio)
         .label(format!("{}°C", gpu_info.temperature));
 
-    f.render_widget(temp_gauge, chunks[2]);
+    f.render_widget(temp_gauge, next.next().unwrap());
 
-    // GPU usage history graph
-    let data: Vec<(f64, f64)> = app
-        .gpu_usage_history
-        .iter()
-        .enumerate()
-        .map(|(i, &v)| (i as f64, v as f64))
-        .collect();
+    // Power gauge
+    if let (Some(power), Some(draw), Some(limit)) =
+        (gpu_info.power_usage(), gpu_info.power_draw, gpu_info.power_limit)
+    {
+        let power_gauge = Gauge::default()
+            .block(
+                Block::default()
+                    .title(" GPU Power ")
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::Magenta)),
+            )
+            .gauge_style(
+                Style::default()
+                    .fg(app.theme.usage_color(power))
+                    .bg(Color::Black)
+                    .add_modifier(Modifier::BOLD),
+            )
+            .ratio((power as f64 / 100.0).clamp(0.0, 1.0))
+            .label(format!("{:.1} W / {:.1} W ({:.1}%)", draw, limit, power));
 
-    if !data.is_empty() {
-        let datasets = vec![Dataset::default()
-            .name("GPU Usage %")
-            .marker(symbols::Marker::Braille)
-            .graph_type(GraphType::Line)
-            .style(Style::default().fg(Color::Magenta))
-            .data(&data)];
+        f.render_widget(power_gauge, next.next().unwrap());
+    }
+
+    if has_telemetry {
+        render_telemetry(f, gpu_info, next.next().unwrap());
+    }
+
+    // GPU history graph
+    let points = |values: &[f32]| -> Vec<(f64, f64)> {
+        values
+            .iter()
+            .enumerate()
+            .map(|(i, &v)| (i as f64, v as f64))
+            .collect()
+    };
+    let series = [
+        ("GPU Usage %", Color::Magenta, points(&history.usage)),
+        ("Memory %", Color::Blue, points(&history.memory)),
+        ("Power %", Color::Yellow, points(&history.power)),
+        ("Encoder %", Color::Green, points(&history.encoder)),
+        ("Decoder %", Color::Cyan, points(&history.decoder)),
+    ];
+    let chart_area = next.next().unwrap();
+
+    if !history.usage.is_empty() {
+        let datasets = series
+            .iter()
+            .filter(|(_, _, data)| !data.is_empty())
+            .map(|(name, color, data)| {
+                Dataset::default()
+                    .name(*name)
+                    .marker(symbols::Marker::Braille)
+                    .graph_type(GraphType::Line)
+                    .style(Style::default().fg(*color))
+                    .data(data)
+            })
+            .collect();
 
         let x_max = app.history_size as f64;
         let chart = Chart::new(datasets)
             .block(
                 Block::default()
-                    .title(" GPU Usage History ")
+                    .title(" GPU History ")
                     .borders(Borders::ALL)
                     .border_style(Style::default().fg(Color::Magenta)),
             )
@@ -176,28 +319,106 @@ fn render_nvidia_gpu(f: &mut Frame, app: &App, area: Rect, gpu_info: &crate::app
                     .labels(vec![Span::raw("0"), Span::raw("50"), Span::raw("100")]),
             );
 
-        f.render_widget(chart, chunks[3]);
+        f.render_widget(chart, chart_area);
     }
-}
 
-#[cfg(feature = "gpu-nvidia")]
-fn get_gpu_usage_color(usage: f32) -> Color {
-    if usage < 60.0 {
-        Color::Green
-    } else if usage < 85.0 {
-        Color::Yellow
-    } else {
-        Color::Red
+    if has_pcie {
+        render_pcie_history(f, app, history, next.next().unwrap());
     }
 }
 
-#[cfg(feature = "gpu-nvidia")]
-fn get_temp_color(temp: u32) -> Color {
-    if temp < 60 {
-        Color::Green
-    } else if temp < 80 {
-        Color::Yellow
+/// Render clocks, fan, encoder/decoder, PCIe rates and throttle reasons
+fn render_telemetry(f: &mut Frame, gpu_info: &GpuInfo, area: Rect) {
+    let label_style = Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD);
+    let reading = |label: &'static str, value: Option<String>| {
+        value.map(|value| vec![Span::styled(label, label_style), Span::raw(format!("{}  ", value))])
+    };
+    let line = |readings: Vec<Option<Vec<Span<'static>>>>| {
+        Line::from(readings.into_iter().flatten().flatten().collect::<Vec<_>>())
+    };
+
+    let throttle = if gpu_info.throttle_reasons.is_empty() {
+        Span::styled("none", Style::default().fg(Color::Green))
     } else {
-        Color::Red
-    }
+        Span::styled(gpu_info.throttle_reasons.join(", "), Style::default().fg(Color::Red))
+    };
+
+    let text = vec![
+        line(vec![
+            reading("SM ", gpu_info.sm_clock.map(|mhz| format!("{} MHz", mhz))),
+            reading("Mem ", gpu_info.memory_clock.map(|mhz| format!("{} MHz", mhz))),
+            reading("Fan ", gpu_info.fan_speed.map(|percent| format!("{}%", percent))),
+        ]),
+        line(vec![
+            reading("Enc ", gpu_info.encoder_usage.map(|usage| format!("{:.0}%", usage))),
+            reading("Dec ", gpu_info.decoder_usage.map(|usage| format!("{:.0}%", usage))),
+            reading(
+                "PCIe ",
+                gpu_info
+                    .pcie_rx_rate
+                    .zip(gpu_info.pcie_tx_rate)
+                    .map(|(rx, tx)| format!("↓ {} ↑ {}", format_rate(rx), format_rate(tx))),
+            ),
+        ]),
+        Line::from(vec![Span::styled("Throttle: ", label_style), throttle]),
+    ];
+
+    let block = Block::default()
+        .title(" Clocks & Throttling ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Magenta));
+    f.render_widget(Paragraph::new(text).block(block), area);
+}
+
+/// Render PCIe receive and transmit rates over time
+fn render_pcie_history(f: &mut Frame, app: &App, history: &GpuHistory, area: Rect) {
+    let points = |values: &[f64]| -> Vec<(f64, f64)> {
+        values.iter().enumerate().map(|(i, &v)| (i as f64, v)).collect()
+    };
+    let rx_data = points(&history.pcie_rx);
+    let tx_data = points(&history.pcie_tx);
+
+    // Scale to the busiest sample, with a 1 MB/s floor so an idle bus stays flat
+    let y_max = history
+        .pcie_rx
+        .iter()
+        .chain(history.pcie_tx.iter())
+        .fold(1024.0 * 1024.0_f64, |max, &v| max.max(v));
+
+    let datasets = vec![
+        Dataset::default()
+            .name("RX")
+            .marker(symbols::Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(Style::default().fg(Color::Cyan))
+            .data(&rx_data),
+        Dataset::default()
+            .name("TX")
+            .marker(symbols::Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(Style::default().fg(Color::Magenta))
+            .data(&tx_data),
+    ];
+
+    let x_max = app.history_size as f64;
+    let chart = Chart::new(datasets)
+        .block(
+            Block::default()
+                .title(" PCIe Throughput ")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Magenta)),
+        )
+        .x_axis(
+            Axis::default()
+                .style(Style::default().fg(Color::Gray))
+                .bounds([0.0, x_max]),
+        )
+        .y_axis(
+            Axis::default()
+                .style(Style::default().fg(Color::Gray))
+                .bounds([0.0, y_max])
+                .labels(vec![Span::raw("0"), Span::raw(format_rate(y_max))]),
+        );
+
+    f.render_widget(chart, area);
 }
@@ -1,3 +1,5 @@
+use crate::app::App;
+use crate::keymap::{Action, Keymap};
 use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
@@ -6,140 +8,164 @@ use ratatui::{
     Frame,
 };
 
-/// Render help overlay
-pub fn render(f: &mut Frame) {
-    let area = centered_rect(60, 80, f.size());
+// Each help section lists the actions sharing a line and what they do
+const GENERAL: [(&[Action], &str); 2] = [
+    (&[Action::Help], "Toggle this help screen"),
+    (&[Action::Quit], "Quit application (normal mode only)"),
+];
+
+const NAVIGATION: [(&[Action], &str); 3] = [
+    (&[Action::Up], "Navigate up in process list"),
+    (&[Action::Down], "Navigate down in process list"),
+    (&[Action::Details], "Show process details"),
+];
+
+const VIEW_OPTIONS: [(&[Action], &str); 11] = [
+    (&[Action::FasterRefresh], "Increase refresh rate (faster)"),
+    (&[Action::SlowerRefresh], "Decrease refresh rate (slower)"),
+    (&[Action::TreeView], "Toggle process tree view"),
+    (&[Action::Pin], "Pin/unpin process (keep its history)"),
+    (&[Action::Mark, Action::MarkMatching], "Mark / mark all matching (Esc clears)"),
+    (&[Action::Collapse, Action::Expand], "Collapse/expand subtree (tree view)"),
+    (&[Action::NextInterface], "Cycle network interface"),
+    (&[Action::NextGpu], "Cycle GPU"),
+    (&[Action::AlertLog], "Show/hide the alert log panel"),
+    (&[Action::Export], "Export snapshot to a file"),
+    (&[Action::SaveConfig], "Save settings to config file"),
+];
+
+const SEARCH_AND_SORT: [(&[Action], &str); 7] = [
+    (&[Action::Search], "Enter search mode"),
+    (&[Action::SortCpu], "Sort by CPU usage (again to reverse)"),
+    (&[Action::SortMemory], "Sort by Memory usage"),
+    (&[Action::SortPid], "Sort by PID"),
+    (&[Action::SortRuntime], "Sort by runtime"),
+    (&[Action::SortPrevColumn, Action::SortNextColumn], "Sort by previous/next column"),
+    (&[Action::ColumnEditor], "Choose and reorder process columns"),
+];
+
+const SIGNAL_ACTIONS: [(&[Action], &str); 3] = [
+    (&[Action::Kill], "Terminate process (SIGTERM)"),
+    (&[Action::KillTree], "Terminate process and its descendants"),
+    (&[Action::SignalMenu], "Send a signal (t in menu: whole tree)"),
+];
+
+const STOP_ACTIONS: [(&[Action], &str); 2] = [
+    (&[Action::Suspend], "Suspend selected process (Unix only)"),
+    (&[Action::Resume], "Resume selected process (Unix only)"),
+];
+
+const PRIORITY_ACTIONS: [(&[Action], &str); 2] = [
+    (&[Action::Renice], "Set nice value (Unix only)"),
+    (&[Action::IoPriority], "Set I/O class and level (Linux only)"),
+];
+
+const REPLAY: [(&[Action], &str); 3] = [
+    (&[Action::ReplayPause], "Play/pause"),
+    (&[Action::ReplaySlower, Action::ReplayFaster], "Slower/faster playback"),
+    (&[Action::ReplayBack, Action::ReplayForward], "Seek back/forward"),
+];
+
+/// Render help overlay with the keys currently bound to each action
+pub fn render(f: &mut Frame, app: &App) {
+    let area = centered_rect(80, 80, f.size());
 
     // Clear the area first
     f.render_widget(Clear, area);
 
-    // Create the help content
-    let help_text = vec![
-        Line::from(vec![Span::styled(
-            "ResMan TUI - Keyboard Shortcuts",
-            Style::default()
-                .fg(Color::Cyan)
-                .add_modifier(Modifier::BOLD),
-        )]),
-        Line::from(""),
-        Line::from(vec![Span::styled(
-            "General",
-            Style::default()
-                .fg(Color::Yellow)
-                .add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
-        )]),
-        Line::from(vec![
-            Span::styled("  ?          ", Style::default().fg(Color::Green)),
-            Span::raw("Toggle this help screen"),
-        ]),
-        Line::from(vec![
-            Span::styled("  q / Q      ", Style::default().fg(Color::Green)),
-            Span::raw("Quit application (normal mode only)"),
-        ]),
-        Line::from(vec![
-            Span::styled("  Esc        ", Style::default().fg(Color::Green)),
-            Span::raw("Close overlay/exit mode"),
-        ]),
-        Line::from(""),
-        Line::from(vec![Span::styled(
-            "Navigation",
-            Style::default()
-                .fg(Color::Yellow)
-                .add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
-        )]),
-        Line::from(vec![
-            Span::styled("  ↑ / k      ", Style::default().fg(Color::Green)),
-            Span::raw("Navigate up in process list"),
-        ]),
-        Line::from(vec![
-            Span::styled("  ↓ / j      ", Style::default().fg(Color::Green)),
-            Span::raw("Navigate down in process list"),
-        ]),
-        Line::from(vec![
-            Span::styled("  Enter      ", Style::default().fg(Color::Green)),
-            Span::raw("Show process details"),
-        ]),
-        Line::from(""),
-        Line::from(vec![Span::styled(
-            "View Options",
-            Style::default()
-                .fg(Color::Yellow)
-                .add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
-        )]),
-        Line::from(vec![
-            Span::styled("  + / =      ", Style::default().fg(Color::Green)),
-            Span::raw("Increase refresh rate (faster)"),
-        ]),
-        Line::from(vec![
-            Span::styled("  -          ", Style::default().fg(Color::Green)),
-            Span::raw("Decrease refresh rate (slower)"),
-        ]),
-        Line::from(vec![
-            Span::styled("  t          ", Style::default().fg(Color::Green)),
-            Span::raw("Toggle process tree view / Sort by runtime"),
-        ]),
-        Line::from(""),
-        Line::from(vec![Span::styled(
-            "Search & Sort",
-            Style::default()
-                .fg(Color::Yellow)
-                .add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
-        )]),
-        Line::from(vec![
-            Span::styled("  /          ", Style::default().fg(Color::Green)),
-            Span::raw("Enter search mode"),
-        ]),
-        Line::from(vec![
-            Span::styled("  c          ", Style::default().fg(Color::Green)),
-            Span::raw("Sort by CPU usage"),
-        ]),
-        Line::from(vec![
-            Span::styled("  m          ", Style::default().fg(Color::Green)),
-            Span::raw("Sort by Memory usage"),
-        ]),
-        Line::from(vec![
-            Span::styled("  p          ", Style::default().fg(Color::Green)),
-            Span::raw("Sort by PID"),
-        ]),
-        Line::from(""),
-        Line::from(vec![Span::styled(
-            "Process Actions",
-            Style::default()
-                .fg(Color::Yellow)
-                .add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
-        )]),
-        Line::from(vec![
-            Span::styled("  k          ", Style::default().fg(Color::Red)),
-            Span::raw("Kill selected process"),
-        ]),
-        Line::from(vec![
-            Span::styled("  s          ", Style::default().fg(Color::Magenta)),
-            Span::raw("Suspend selected process (Unix only)"),
-        ]),
-        Line::from(vec![
-            Span::styled("  r          ", Style::default().fg(Color::Magenta)),
-            Span::raw("Resume selected process (Unix only)"),
-        ]),
-        Line::from(""),
-        Line::from(vec![
-            Span::styled("Press ", Style::default().fg(Color::Gray)),
-            Span::styled("?", Style::default().fg(Color::Green)),
-            Span::styled(" or ", Style::default().fg(Color::Gray)),
-            Span::styled("Esc", Style::default().fg(Color::Green)),
-            Span::styled(" to close this help", Style::default().fg(Color::Gray)),
-        ]),
-    ];
-
-    let paragraph = Paragraph::new(help_text)
-        .block(
-            Block::default()
-                .title(" Help ")
-                .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::Cyan)),
-        )
-        .alignment(Alignment::Left);
-
-    f.render_widget(paragraph, area);
+    let block = Block::default()
+        .title(" Help ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan));
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(2), // Title
+            Constraint::Min(0),    // Shortcuts
+            Constraint::Length(1), // Footer
+        ])
+        .split(inner);
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(rows[1]);
+
+    let title = Paragraph::new(Line::from(vec![Span::styled(
+        "ResMan TUI - Keyboard Shortcuts",
+        Style::default()
+            .fg(Color::Cyan)
+            .add_modifier(Modifier::BOLD),
+    )]));
+    f.render_widget(title, rows[0]);
+
+    // Shortcuts are split over two columns to fit shorter terminals
+    let mut left_text = vec![section("General")];
+    left_text.extend(shortcuts(&app.keymap, &GENERAL, Color::Green));
+    left_text.push(fixed("Esc", "Close overlay/exit mode", Color::Green));
+    left_text.push(Line::from(""));
+    left_text.push(section("Navigation"));
+    left_text.extend(shortcuts(&app.keymap, &NAVIGATION, Color::Green));
+    left_text.push(Line::from(""));
+    left_text.push(section("View Options"));
+    left_text.extend(shortcuts(&app.keymap, &VIEW_OPTIONS, Color::Green));
+
+    let mut right_text = vec![section("Search & Sort")];
+    right_text.extend(shortcuts(&app.keymap, &SEARCH_AND_SORT, Color::Green));
+    right_text.push(Line::from(""));
+    right_text.push(section("Process Actions"));
+    right_text.extend(shortcuts(&app.keymap, &SIGNAL_ACTIONS, Color::Red));
+    right_text.extend(shortcuts(&app.keymap, &STOP_ACTIONS, Color::Magenta));
+    right_text.extend(shortcuts(&app.keymap, &PRIORITY_ACTIONS, Color::Cyan));
+    right_text.push(Line::from(""));
+    right_text.push(section("Replay (--replay)"));
+    right_text.extend(shortcuts(&app.keymap, &REPLAY, Color::Green));
+
+    let mut footer = vec![Span::styled("Press ", Style::default().fg(Color::Gray))];
+    for key in app.keymap.keys(Action::Help) {
+        footer.push(Span::styled(key, Style::default().fg(Color::Green)));
+        footer.push(Span::styled(" or ", Style::default().fg(Color::Gray)));
+    }
+    footer.extend([
+        Span::styled("Esc", Style::default().fg(Color::Green)),
+        Span::styled(" to close this help", Style::default().fg(Color::Gray)),
+    ]);
+    let footer = Paragraph::new(Line::from(footer));
+
+    f.render_widget(Paragraph::new(left_text).alignment(Alignment::Left), columns[0]);
+    f.render_widget(Paragraph::new(right_text).alignment(Alignment::Left), columns[1]);
+    f.render_widget(footer, rows[2]);
+}
+
+/// Bold underlined section heading
+fn section(title: &str) -> Line<'static> {
+    Line::from(vec![Span::styled(
+        title.to_string(),
+        Style::default()
+            .fg(Color::Yellow)
+            .add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
+    )])
+}
+
+/// One line per shortcut, leaving out actions with no keys bound
+fn shortcuts(keymap: &Keymap, entries: &[(&[Action], &str)], color: Color) -> Vec<Line<'static>> {
+    entries
+        .iter()
+        .filter_map(|(actions, description)| {
+            let keys: Vec<String> = actions.iter().flat_map(|&action| keymap.keys(action)).collect();
+            (!keys.is_empty()).then(|| fixed(&keys.join(" / "), description, color))
+        })
+        .collect()
+}
+
+/// Shortcut line for a key that can't be rebound
+fn fixed(keys: &str, description: &str, color: Color) -> Line<'static> {
+    Line::from(vec![
+        Span::styled(format!("  {:<11}", keys), Style::default().fg(color)),
+        Span::raw(description.to_string()),
+    ])
 }
 
 /// Helper function to create centered rect
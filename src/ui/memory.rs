@@ -0,0 +1,192 @@
+use super::format_bytes;
+use crate::app::App;
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    symbols,
+    text::{Line, Span},
+    widgets::{Axis, Block, Borders, Chart, Dataset, Gauge, GraphType, Paragraph},
+    Frame,
+};
+
+/// Render memory and swap panel
+pub fn render(f: &mut Frame, app: &App, area: Rect) {
+    // Short panels drop the history graph and keep the gauges readable
+    if area.height < 15 {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(3), // RAM gauge
+                Constraint::Length(3), // Swap gauge
+                Constraint::Min(0),    // Breakdown
+            ])
+            .split(area);
+
+        render_ram_gauge(f, app, chunks[0]);
+        render_swap_gauge(f, app, chunks[1]);
+        render_breakdown(f, app, chunks[2]);
+        return;
+    }
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // RAM gauge
+            Constraint::Length(3), // Swap gauge
+            Constraint::Length(4), // Breakdown
+            Constraint::Min(5),    // History graph
+        ])
+        .split(area);
+
+    render_ram_gauge(f, app, chunks[0]);
+    render_swap_gauge(f, app, chunks[1]);
+    render_breakdown(f, app, chunks[2]);
+    render_history(f, app, chunks[3]);
+}
+
+/// Render RAM usage gauge
+fn render_ram_gauge(f: &mut Frame, app: &App, area: Rect) {
+    let memory = &app.snapshot.memory;
+    let usage = app.snapshot.memory_usage();
+
+    let gauge = Gauge::default()
+        .block(
+            Block::default()
+                .title(" Memory ")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Blue)),
+        )
+        .gauge_style(
+            Style::default()
+                .fg(app.theme.usage_color(usage))
+                .bg(Color::Black)
+                .add_modifier(Modifier::BOLD),
+        )
+        .ratio((usage as f64 / 100.0).min(1.0))
+        .label(format!(
+            "{} / {} ({:.1}%)",
+            format_bytes(memory.used),
+            format_bytes(memory.total),
+            usage
+        ));
+
+    f.render_widget(gauge, area);
+}
+
+/// Render swap usage gauge
+fn render_swap_gauge(f: &mut Frame, app: &App, area: Rect) {
+    let memory = &app.snapshot.memory;
+    let usage = app.snapshot.swap_usage();
+
+    let label = if memory.swap_total == 0 {
+        "No swap".to_string()
+    } else {
+        format!(
+            "{} / {} ({:.1}%)",
+            format_bytes(memory.swap_used),
+            format_bytes(memory.swap_total),
+            usage
+        )
+    };
+
+    let gauge = Gauge::default()
+        .block(
+            Block::default()
+                .title(" Swap ")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Blue)),
+        )
+        .gauge_style(
+            Style::default()
+                .fg(app.theme.usage_color(usage))
+                .bg(Color::Black)
+                .add_modifier(Modifier::BOLD),
+        )
+        .ratio((usage as f64 / 100.0).min(1.0))
+        .label(label);
+
+    f.render_widget(gauge, area);
+}
+
+/// Render used/cached/available/buffers breakdown
+fn render_breakdown(f: &mut Frame, app: &App, area: Rect) {
+    let memory = &app.snapshot.memory;
+    let label_style = Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD);
+    let optional = |value: Option<u64>| value.map(format_bytes).unwrap_or_else(|| "n/a".to_string());
+
+    let text = vec![
+        Line::from(vec![
+            Span::styled("Used: ", label_style),
+            Span::raw(format!("{:<12}", format_bytes(memory.used))),
+            Span::styled("Cached: ", label_style),
+            Span::raw(optional(memory.cached)),
+        ]),
+        Line::from(vec![
+            Span::styled("Avail: ", label_style),
+            Span::raw(format!("{:<11}", format_bytes(memory.available))),
+            Span::styled("Buffers: ", label_style),
+            Span::raw(optional(memory.buffers)),
+        ]),
+    ];
+
+    let paragraph = Paragraph::new(text).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Blue)),
+    );
+
+    f.render_widget(paragraph, area);
+}
+
+/// Render memory and swap history graph
+fn render_history(f: &mut Frame, app: &App, area: Rect) {
+    let memory_data: Vec<(f64, f64)> = app
+        .memory_history
+        .iter()
+        .enumerate()
+        .map(|(i, &v)| (i as f64, v as f64))
+        .collect();
+    let swap_data: Vec<(f64, f64)> = app
+        .swap_history
+        .iter()
+        .enumerate()
+        .map(|(i, &v)| (i as f64, v as f64))
+        .collect();
+
+    let datasets = vec![
+        Dataset::default()
+            .name("RAM %")
+            .marker(symbols::Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(Style::default().fg(Color::Blue))
+            .data(&memory_data),
+        Dataset::default()
+            .name("Swap %")
+            .marker(symbols::Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(Style::default().fg(Color::Magenta))
+            .data(&swap_data),
+    ];
+
+    let x_max = app.history_size as f64;
+    let chart = Chart::new(datasets)
+        .block(
+            Block::default()
+                .title(" Memory History ")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Blue)),
+        )
+        .x_axis(
+            Axis::default()
+                .style(Style::default().fg(Color::Gray))
+                .bounds([0.0, x_max]),
+        )
+        .y_axis(
+            Axis::default()
+                .style(Style::default().fg(Color::Gray))
+                .bounds([0.0, 100.0])
+                .labels(vec![Span::raw("0"), Span::raw("50"), Span::raw("100")]),
+        );
+
+    f.render_widget(chart, area);
+}
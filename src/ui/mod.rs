@@ -1,13 +1,22 @@
 pub mod cpu;
+pub mod disks;
 pub mod gpu;
+pub mod memory;
+pub mod network;
 pub mod processes;
+pub mod sensors;
 pub mod details;
 pub mod help;
 pub mod status_bar;
 pub mod toast;
 pub mod confirmation;
+pub mod column_editor;
+pub mod signal_menu;
+pub mod priority_input;
+pub mod alerts;
 
-use crate::app::App;
+use crate::app::{App, Panel};
+use crate::keymap::Action;
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     Frame,
@@ -53,13 +62,28 @@ pub fn render(f: &mut Frame, app: &App) {
         confirmation::render(f, pending);
     }
 
+    // Render column editor if open
+    if app.show_column_editor {
+        column_editor::render(f, app);
+    }
+
+    // Render signal menu if open
+    if app.show_signal_menu {
+        signal_menu::render(f, app);
+    }
+
+    // Render priority input if open
+    if let Some(ref input) = app.priority_input {
+        priority_input::render(f, app, input);
+    }
+
     // Render help overlay on top if active
     if app.show_help {
-        help::render(f);
+        help::render(f, app);
     }
 }
 
-/// Render the overview (CPU, GPU, processes)
+/// Render the overview (CPU, side panels, processes)
 fn render_overview(f: &mut Frame, app: &App, area: Rect) {
     // Split into left (CPU overview + GPU) and right (per-core CPUs + processes)
     let main_chunks = Layout::default()
@@ -67,14 +91,23 @@ fn render_overview(f: &mut Frame, app: &App, area: Rect) {
         .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
         .split(area);
 
-    // Left side: CPU overview and GPU
-    let left_chunks = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([Constraint::Percentage(55), Constraint::Percentage(45)])
-        .split(main_chunks[0]);
+    // Left side: CPU overview with the side panel grid below
+    if app.panels.is_empty() {
+        cpu::render_overview(f, app, main_chunks[0]);
+    } else {
+        // Give the grid more room once it needs more than one row
+        let cpu_percent = if app.panels.len() > 2 { 40 } else { 55 };
+        let left_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Percentage(cpu_percent),
+                Constraint::Percentage(100 - cpu_percent),
+            ])
+            .split(main_chunks[0]);
 
-    cpu::render_overview(f, app, left_chunks[0]);
-    gpu::render(f, app, left_chunks[1]);
+        cpu::render_overview(f, app, left_chunks[0]);
+        render_panel_grid(f, app, left_chunks[1]);
+    }
 
     // Right side: Per-core CPUs and Process list
     let right_chunks = Layout::default()
@@ -85,3 +118,543 @@ fn render_overview(f: &mut Frame, app: &App, area: Rect) {
     cpu::render_cores(f, app, right_chunks[0]);
     processes::render(f, app, right_chunks[1]);
 }
+
+/// Render the side panels two per row
+fn render_panel_grid(f: &mut Frame, app: &App, area: Rect) {
+    let rows: Vec<&[Panel]> = app.panels.chunks(2).collect();
+
+    let row_chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(vec![Constraint::Ratio(1, rows.len() as u32); rows.len()])
+        .split(area);
+
+    for (row, panels) in rows.iter().enumerate() {
+        let col_chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints(vec![Constraint::Ratio(1, panels.len() as u32); panels.len()])
+            .split(row_chunks[row]);
+
+        for (col, panel) in panels.iter().enumerate() {
+            render_panel(f, app, col_chunks[col], *panel);
+        }
+    }
+}
+
+/// Render a single side panel
+fn render_panel(f: &mut Frame, app: &App, area: Rect, panel: Panel) {
+    match panel {
+        Panel::Gpu => gpu::render(f, app, area),
+        Panel::Memory => memory::render(f, app, area),
+        Panel::Network => network::render(f, app, area),
+        Panel::Disks => disks::render(f, app, area),
+        Panel::Sensors => sensors::render(f, app, area),
+        Panel::Alerts => alerts::render(f, app, area),
+    }
+}
+
+/// "keys: label" using the first key of each action; `None` if none are bound
+fn key_hint(app: &App, actions: &[Action], label: &str) -> Option<String> {
+    let keys: Vec<String> = actions
+        .iter()
+        .filter_map(|&action| app.keymap.keys(action).into_iter().next())
+        .collect();
+    (!keys.is_empty()).then(|| format!("{}: {}", keys.join("/"), label))
+}
+
+fn join_hints<const N: usize>(hints: [Option<String>; N]) -> String {
+    hints.into_iter().flatten().collect::<Vec<_>>().join(" | ")
+}
+
+/// Format a byte count with a binary unit suffix
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KB", "MB", "GB", "TB"];
+
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{} {}", bytes, UNITS[0])
+    } else {
+        format!("{:.1} {}", value, UNITS[unit])
+    }
+}
+
+/// Format a transfer rate in bytes per second
+pub fn format_rate(bytes_per_sec: f64) -> String {
+    format!("{}/s", format_bytes(bytes_per_sec as u64))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::{PriorityField, ProcessAction, SIGNALS};
+    use crate::column::Column;
+    use crate::config::Config;
+    use crate::keymap::{KeyBindings, Keymap};
+    use crate::snapshot::{
+        BlockDeviceInfo, CpuInfo, DiskInfo, GpuInfo, MemoryInfo, NetworkInfo, ProcessInfo,
+        SensorInfo, SensorKind, Snapshot,
+    };
+    use crate::source::{scripted::ScriptedSource, MetricSource};
+    use ratatui::{backend::TestBackend, buffer::Buffer, style::Color, Terminal};
+    use std::sync::Arc;
+
+    const GIB: u64 = 1024 * 1024 * 1024;
+
+    fn process(pid: u32, name: &str, cpu_usage: f32) -> ProcessInfo {
+        ProcessInfo {
+            command: format!("/usr/bin/{} --serve", name),
+            user: Some("alice".to_string()),
+            threads: Some(4),
+            nice: Some(0),
+            priority: Some(20),
+            cpu_usage,
+            memory: 256 * 1024 * 1024,
+            virtual_memory: GIB,
+            run_time: 3600,
+            ..ProcessInfo::fixture(pid, Some(1), name)
+        }
+    }
+
+    fn frame(usage: f32) -> Snapshot {
+        Snapshot {
+            timestamp: 0,
+            cpus: vec![
+                CpuInfo {
+                    usage,
+                    frequency: 3200,
+                };
+                4
+            ],
+            global_cpu_usage: usage,
+            memory: MemoryInfo {
+                total: 16 * GIB,
+                used: 4 * GIB,
+                available: 12 * GIB,
+                cached: Some(2 * GIB),
+                buffers: None,
+                swap_total: 2 * GIB,
+                swap_used: 0,
+            },
+            processes: vec![process(101, "webserver", usage), process(102, "database", 5.0)],
+            networks: vec![NetworkInfo {
+                name: "eth0".to_string(),
+                rx_rate: 2048.0,
+                tx_rate: 1024.0,
+                ..Default::default()
+            }],
+            disks: vec![DiskInfo {
+                mount_point: "/home".into(),
+                file_system: "ext4".to_string(),
+                total: 100 * GIB,
+                available: 40 * GIB,
+            }],
+            block_devices: vec![BlockDeviceInfo {
+                name: "nvme0n1".to_string(),
+                read_rate: 4096.0,
+                ..Default::default()
+            }],
+            sensors: vec![SensorInfo {
+                label: "Package id 0".to_string(),
+                kind: SensorKind::Temperature,
+                value: 55.0,
+                max: None,
+                critical: Some(100.0),
+            }],
+            gpus: vec![GpuInfo {
+                index: 0,
+                name: "Radeon RX 7600".to_string(),
+                usage: 40.0,
+                memory_used: 2 * GIB,
+                memory_total: 8 * GIB,
+                temperature: 60,
+                ..Default::default()
+            }],
+        }
+    }
+
+    /// App fed every frame of a scripted run
+    fn scripted_app() -> App {
+        let mut source = ScriptedSource::new(vec![frame(10.0), frame(30.0), frame(50.0)]);
+        let mut app = App::new(Arc::new(source.snapshot()), Config::default(), None);
+        while source.position() < 2 {
+            app.update(Arc::new(source.snapshot()));
+        }
+        app
+    }
+
+    fn draw(app: &App, width: u16, height: u16, render: impl Fn(&mut Frame, &App, Rect)) -> Buffer {
+        let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+        terminal.draw(|f| render(f, app, f.size())).unwrap();
+        terminal.backend().buffer().clone()
+    }
+
+    fn contains(buffer: &Buffer, text: &str) -> bool {
+        let area = buffer.area;
+        (area.top()..area.bottom()).any(|y| {
+            let line: String = (area.left()..area.right())
+                .map(|x| buffer.get(x, y).symbol())
+                .collect();
+            line.contains(text)
+        })
+    }
+
+    /// Text of the cells of row `y` between `x` and `x + width`
+    fn row_text(buffer: &Buffer, x: u16, y: u16, width: u16) -> String {
+        (x..x + width).map(|x| buffer.get(x, y).symbol()).collect()
+    }
+
+    /// Text of row `y` across the whole buffer
+    fn row(buffer: &Buffer, y: u16) -> String {
+        row_text(buffer, 0, y, buffer.area.width)
+    }
+
+    /// Check a bordered block fills exactly `area`, with `title` at the top
+    /// left, and return the text of its inner rows
+    fn boxed(buffer: &Buffer, area: Rect, title: &str) -> Vec<String> {
+        let Rect { x, y, width, height } = area;
+        let (right, bottom) = (x + width - 1, y + height - 1);
+
+        let top = row_text(buffer, x, y, width);
+        assert!(top.starts_with(&format!("┌{}", title)), "top border: {:?}", top);
+        assert!(top.ends_with('┐'), "top border: {:?}", top);
+        assert_eq!(
+            row_text(buffer, x, bottom, width),
+            format!("└{}┘", "─".repeat(width as usize - 2))
+        );
+
+        (y + 1..bottom)
+            .map(|y| {
+                assert_eq!(buffer.get(x, y).symbol(), "│", "left border of row {}", y);
+                assert_eq!(buffer.get(right, y).symbol(), "│", "right border of row {}", y);
+                row_text(buffer, x + 1, y, width - 2).trim_end().to_string()
+            })
+            .collect()
+    }
+
+    /// Full screen at a small terminal size
+    fn draw_small(app: &App) -> Buffer {
+        let mut terminal = Terminal::new(TestBackend::new(80, 24)).unwrap();
+        terminal.draw(|f| render(f, app)).unwrap();
+        terminal.backend().buffer().clone()
+    }
+
+    /// App with the first process (the busiest) selected
+    fn selected_app() -> App {
+        let mut app = scripted_app();
+        app.selected_process = Some(0);
+        app
+    }
+
+    #[test]
+    fn scripted_source_steps_through_frames_and_repeats_the_last() {
+        let mut source = ScriptedSource::new(vec![frame(10.0), frame(30.0)]);
+        let usage: Vec<f32> = (0..3).map(|_| source.snapshot().global_cpu_usage).collect();
+        assert_eq!(usage, [10.0, 30.0, 30.0]);
+        assert_eq!(source.position(), 1);
+    }
+
+    #[test]
+    fn app_keeps_history_of_scripted_frames() {
+        let app = scripted_app();
+        // The first frame seeds the app; history starts with the updates
+        assert_eq!(app.overall_cpu_history, [30.0, 50.0]);
+        assert_eq!(app.cpu_history.len(), 4);
+    }
+
+    #[test]
+    fn renders_cpu_panels() {
+        let app = scripted_app();
+        let buffer = draw(&app, 80, 20, cpu::render_overview);
+        assert!(contains(&buffer, "Overall CPU Usage"));
+        assert!(contains(&buffer, "50.0%"));
+
+        let buffer = draw(&app, 80, 20, cpu::render_cores);
+        assert!(contains(&buffer, "Core 0"));
+        assert!(contains(&buffer, "Core 3"));
+    }
+
+    #[test]
+    fn renders_gpu_panel() {
+        let buffer = draw(&scripted_app(), 80, 24, gpu::render);
+        assert!(contains(&buffer, "Radeon RX 7600"));
+        assert!(contains(&buffer, "GPU Memory"));
+    }
+
+    #[test]
+    fn renders_memory_panel() {
+        let buffer = draw(&scripted_app(), 60, 20, memory::render);
+        assert!(contains(&buffer, "Memory"));
+        assert!(contains(&buffer, "Swap"));
+    }
+
+    #[test]
+    fn renders_network_panel() {
+        let buffer = draw(&scripted_app(), 60, 12, network::render);
+        assert!(contains(&buffer, "Network: eth0 (1/1)"));
+        assert!(contains(&buffer, "2.0 KB/s"));
+    }
+
+    #[test]
+    fn renders_disks_panel() {
+        let buffer = draw(&scripted_app(), 60, 12, disks::render);
+        assert!(contains(&buffer, "Disks"));
+        assert!(contains(&buffer, "/home"));
+        assert!(contains(&buffer, "nvme0n1"));
+    }
+
+    #[test]
+    fn renders_sensors_panel() {
+        let buffer = draw(&scripted_app(), 60, 12, sensors::render);
+        assert!(contains(&buffer, "Sensors (1)"));
+        assert!(contains(&buffer, "Package id 0"));
+    }
+
+    #[test]
+    fn renders_process_list() {
+        let buffer = draw(&scripted_app(), 100, 12, processes::render);
+        assert!(contains(&buffer, "webserver"));
+        assert!(contains(&buffer, "database"));
+    }
+
+    #[test]
+    fn renders_process_details() {
+        let mut app = scripted_app();
+        app.show_details = true;
+        app.selected_process = Some(0);
+        let name = app.get_selected_process().unwrap().name.clone();
+
+        let buffer = draw(&app, 80, 30, details::render);
+        assert!(contains(&buffer, "Process Details"));
+        assert!(contains(&buffer, &name));
+    }
+
+    #[test]
+    fn renders_full_screen() {
+        let app = scripted_app();
+        let mut terminal = Terminal::new(TestBackend::new(160, 50)).unwrap();
+        terminal.draw(|f| render(f, &app)).unwrap();
+        assert!(contains(terminal.backend().buffer(), "webserver"));
+    }
+
+    fn draw_screen(app: &App) -> Buffer {
+        let mut terminal = Terminal::new(TestBackend::new(160, 50)).unwrap();
+        terminal.draw(|f| render(f, app)).unwrap();
+        terminal.backend().buffer().clone()
+    }
+
+    #[test]
+    fn hints_follow_default_keymap() {
+        let mut app = scripted_app();
+        let buffer = draw_screen(&app);
+        assert!(contains(&buffer, "↑/↓: Navigate | Enter: Details | /: Search | c/m/p/T/</>: Sort"));
+
+        app.show_help = true;
+        let buffer = draw_screen(&app);
+        assert!(contains(&buffer, "? or Esc: Close Help"));
+        assert!(contains(&buffer, "  ↑          Navigate up in process list"));
+        assert!(contains(&buffer, "  ↓ / j      Navigate down in process list"));
+        assert!(contains(&buffer, "  k          Terminate process (SIGTERM)"));
+    }
+
+    #[test]
+    fn hints_follow_rebound_keys() {
+        let mut app = scripted_app();
+        let overrides = KeyBindings::from([
+            (Action::Kill, vec!["x".to_string()]),
+            (Action::KillTree, vec!["F9".to_string()]),
+            (Action::Search, vec!["f".to_string()]),
+            (Action::Export, Vec::new()),
+        ]);
+        app.keymap = Keymap::new(&overrides).unwrap();
+
+        let buffer = draw_screen(&app);
+        assert!(contains(&buffer, "f: Search"));
+
+        app.show_help = true;
+        let buffer = draw_screen(&app);
+        assert!(contains(&buffer, "  x          Terminate process (SIGTERM)"));
+        assert!(contains(&buffer, "  F9         Terminate process and its descendants"));
+        assert!(contains(&buffer, "  f          Enter search mode"));
+        // Unbound actions are left out
+        assert!(!contains(&buffer, "Export snapshot"));
+    }
+
+    #[test]
+    fn panel_titles_follow_rebound_keys() {
+        let mut app = scripted_app();
+        let mut snapshot = (*app.snapshot).clone();
+        snapshot.gpus.push(GpuInfo {
+            index: 1,
+            name: "Arc A380".to_string(),
+            ..Default::default()
+        });
+        app.update(Arc::new(snapshot));
+
+        let buffer = draw(&app, 80, 12, gpu::render);
+        assert!(contains(&buffer, " GPUs (2) [g: next] "));
+        let buffer = draw(&app, 60, 12, network::render);
+        assert!(contains(&buffer, "[i: next]"));
+        let buffer = draw(&app, 100, 12, processes::render);
+        assert!(contains(&buffer, " Processes (↑/↓: Navigate, Enter: View Details, q: Quit) "));
+
+        let overrides = KeyBindings::from([
+            (Action::Quit, vec!["Q".to_string()]),
+            (Action::NextGpu, vec!["G".to_string()]),
+            (Action::NextInterface, vec!["F6".to_string()]),
+            (Action::TreeView, vec!["F5".to_string()]),
+            (Action::Details, Vec::new()),
+        ]);
+        app.keymap = Keymap::new(&overrides).unwrap();
+
+        let buffer = draw(&app, 80, 12, gpu::render);
+        assert!(contains(&buffer, " GPUs (2) [G: next] "));
+        let buffer = draw(&app, 60, 12, network::render);
+        assert!(contains(&buffer, "[F6: next]"));
+        let buffer = draw(&app, 100, 12, processes::render);
+        assert!(contains(&buffer, " Processes (↑/↓: Navigate, Q: Quit) "));
+
+        app.tree_view = true;
+        let buffer = draw(&app, 100, 12, processes::render);
+        assert!(contains(&buffer, " Process Tree (↑/↓: Navigate, ←/→: Collapse/Expand, F5: Flat List) "));
+    }
+
+    #[test]
+    fn lays_out_network_panel() {
+        let buffer = draw(&scripted_app(), 60, 12, network::render);
+        let inner = boxed(&buffer, Rect::new(0, 0, 60, 12), " Network: eth0 (1/1) [i: next] ");
+
+        assert_eq!(inner[0], "↓ 2.0 KB/s      ↑ 1.0 KB/s");
+        assert_eq!(inner[1], "Total: ↓ 0 B ↑ 0 B");
+        // Graph axis labels line up on the left of the chart
+        assert!(inner[3].starts_with("2.0 KB/s│"));
+        assert!(inner[9].starts_with("0       │"));
+    }
+
+    #[test]
+    fn lays_out_process_columns() {
+        let buffer = draw(&scripted_app(), 100, 6, processes::render);
+        let inner = boxed(
+            &buffer,
+            Rect::new(0, 0, 100, 6),
+            " Processes (↑/↓: Navigate, Enter: View Details, q: Quit) ",
+        );
+
+        // PID and name left-aligned, numbers right-aligned under their headers
+        let columns = |pid: &str, name: &str, cpu: &str, memory: &str| {
+            format!("{:<7} {:<71} {:>7} {:>10}", pid, name, cpu, memory)
+        };
+        assert_eq!(inner[0], columns("PID", "Process Name", "CPU ▼", "Memory"));
+        assert_eq!(inner[1], columns("101", "webserver", "50.0%", "256.0 MB"));
+        assert_eq!(inner[2], columns("102", "database", "5.0%", "256.0 MB"));
+        assert_eq!(inner[3], "");
+    }
+
+    #[test]
+    fn lays_out_alerts_panel_at_a_small_size() {
+        let config = Config {
+            alerts: vec![toml::from_str("metric = \"cpu\"\nabove = 40").unwrap()],
+            ..Default::default()
+        };
+        let frames = [10.0, 30.0, 50.0, 20.0, 60.0].map(frame).to_vec();
+        let mut source = ScriptedSource::new(frames);
+        let mut app = App::new(Arc::new(source.snapshot()), config, None);
+        while source.position() < 4 {
+            app.update(Arc::new(source.snapshot()));
+        }
+
+        let buffer = draw(&app, 40, 7, alerts::render);
+        let inner = boxed(&buffer, Rect::new(0, 0, 40, 7), " Alerts (1 firing) ");
+
+        // Firing rules first, then events newest first after an 8-char time
+        assert_eq!(inner[0], "● cpu > 40.0%");
+        assert_eq!(&inner[1][8..], " ▲ fired   cpu > 40.0% (60.0%)");
+        assert_eq!(&inner[2][8..], " ▼ cleared cpu > 40.0% (20.0%)");
+        assert_eq!(&inner[3][8..], " ▲ fired   cpu > 40.0% (50.0%)");
+        assert_eq!(inner[4], "");
+        assert_eq!(buffer.get(1, 1).fg, Color::Red);
+        assert_eq!(buffer.get(0, 0).fg, Color::Red);
+    }
+
+    #[test]
+    fn lays_out_empty_alerts_panel() {
+        let buffer = draw(&scripted_app(), 40, 5, alerts::render);
+        let inner = boxed(&buffer, Rect::new(0, 0, 40, 5), " Alerts (0 firing) ");
+        assert_eq!(inner, ["", "           No alerts raised", ""]);
+    }
+
+    #[test]
+    fn signal_menu_fits_a_small_terminal() {
+        let mut app = selected_app();
+        app.open_signal_menu();
+        app.signal_cursor = 1;
+
+        let buffer = draw_small(&app);
+        let height = SIGNALS.len() as u16 + 8;
+        let area = Rect::new(12, (24 - height) / 2, 56, height);
+        let inner = boxed(&buffer, area, " Send Signal ");
+
+        assert_eq!(inner[0], "Send to webserver (PID 101)");
+        assert_eq!(inner[2], "SIGTERM   Ask the process to exit cleanly");
+        assert_eq!(inner[inner.len() - 3], "[ ] Include all descendants");
+        assert_eq!(inner[inner.len() - 1], "Enter: send  ↑/↓: select  t: tree  K/Esc: close");
+        // The cursor row is highlighted across the text
+        let cursor = area.y + 4;
+        assert_eq!(row_text(&buffer, area.x + 1, cursor, 6), "SIGINT");
+        assert_eq!(buffer.get(area.x + 1, cursor).bg, Color::Cyan);
+        assert_eq!(buffer.get(area.x + 1, cursor - 1).bg, Color::Reset);
+        assert!(row(&buffer, 23).starts_with("↑/↓: Select | t: Whole Tree | Enter: Send | K/Esc: Close"));
+    }
+
+    #[test]
+    fn confirmation_grows_to_keep_its_hint_on_a_small_terminal() {
+        let mut app = selected_app();
+        app.request_action(ProcessAction::Kill);
+
+        let buffer = draw_small(&app);
+        let inner = boxed(&buffer, Rect::new(20, 6, 40, 12), " Confirmation ");
+
+        assert_eq!(inner[1].trim(), "Terminate Process (SIGTERM)");
+        assert_eq!(inner[3].trim(), "Process: webserver");
+        assert_eq!(inner[4].trim(), "PID: 101");
+        assert_eq!(inner[6].trim(), "The process is asked to exit and may");
+        assert_eq!(inner[7].trim(), "clean up first.");
+        assert_eq!(inner[9].trim(), "Press y to confirm or n/Esc to cancel");
+    }
+
+    #[test]
+    fn column_editor_fits_a_small_terminal() {
+        let mut app = selected_app();
+        app.toggle_column_editor();
+
+        let buffer = draw_small(&app);
+        let height = Column::ALL.len() as u16 + 6;
+        let inner = boxed(&buffer, Rect::new(16, (24 - height) / 2, 48, height), " Process Columns ");
+
+        assert_eq!(inner[0], "Shown columns come first, in table order");
+        assert_eq!(inner[2], "[x] PID          Process ID");
+        assert_eq!(inner[5], "[x] Memory       Resident memory (RSS)");
+        assert_eq!(inner[6], "[ ] USER         User");
+        assert_eq!(inner[inner.len() - 1], "Space: show/hide  J/K: move  o/Esc: close");
+        assert_eq!(buffer.get(17, (24 - height) / 2 + 3).bg, Color::Cyan);
+    }
+
+    #[test]
+    fn priority_input_fits_a_small_terminal() {
+        let mut app = selected_app();
+        app.open_priority_input(PriorityField::Nice);
+
+        let buffer = draw_small(&app);
+        let inner = boxed(&buffer, Rect::new(14, 7, 52, 9), " Renice ");
+
+        assert_eq!(inner[0], "Set for webserver (PID 101)");
+        assert_eq!(inner[2], "Nice:  0▏  -20 (highest) to 19 (lowest)");
+        assert_eq!(inner[4], "Lowering the nice value needs root");
+        assert_eq!(inner[6], "Enter: apply  ↑/↓: adjust  Esc: cancel");
+        assert!(row(&buffer, 23).starts_with("Type a value | ↑/↓: Adjust | Enter: Apply | Esc: Cancel"));
+    }
+}
@@ -0,0 +1,174 @@
+use super::{format_bytes, format_rate, key_hint};
+use crate::app::App;
+use crate::keymap::Action;
+use crate::snapshot::NetworkInfo;
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    symbols,
+    text::{Line, Span},
+    widgets::{Axis, Block, Borders, Chart, Dataset, GraphType, Paragraph},
+    Frame,
+};
+
+/// Render network interface panel
+pub fn render(f: &mut Frame, app: &App, area: Rect) {
+    let networks = &app.snapshot.networks;
+
+    let Some(network) = networks.get(app.selected_interface) else {
+        render_no_network(f, area);
+        return;
+    };
+
+    let mut title = format!(
+        " Network: {} ({}/{}) ",
+        network.name,
+        app.selected_interface + 1,
+        networks.len()
+    );
+    if let Some(hint) = key_hint(app, &[Action::NextInterface], "next") {
+        title.push_str(&format!("[{}] ", hint));
+    }
+
+    let block = Block::default()
+        .title(title)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Green));
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Rates and counters
+            Constraint::Min(3),    // Throughput graph
+        ])
+        .split(inner);
+
+    render_counters(f, network, chunks[0]);
+    render_history(f, app, network, chunks[1]);
+}
+
+/// Render current rates, totals, packets and errors
+fn render_counters(f: &mut Frame, network: &NetworkInfo, area: Rect) {
+    let label_style = Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD);
+    let error_style = if network.errors_received + network.errors_transmitted > 0 {
+        Style::default().fg(Color::Red)
+    } else {
+        Style::default()
+    };
+
+    let text = vec![
+        Line::from(vec![
+            Span::styled("↓ ", Style::default().fg(Color::Cyan)),
+            Span::raw(format!("{:<14}", format_rate(network.rx_rate))),
+            Span::styled("↑ ", Style::default().fg(Color::Magenta)),
+            Span::raw(format_rate(network.tx_rate)),
+        ]),
+        Line::from(vec![
+            Span::styled("Total: ", label_style),
+            Span::raw(format!(
+                "↓ {} ↑ {}",
+                format_bytes(network.total_received),
+                format_bytes(network.total_transmitted)
+            )),
+        ]),
+        Line::from(vec![
+            Span::styled("Pkts: ", label_style),
+            Span::raw(format!(
+                "↓ {} ↑ {}  ",
+                network.packets_received, network.packets_transmitted
+            )),
+            Span::styled("Errs: ", label_style),
+            Span::styled(
+                format!(
+                    "↓ {} ↑ {}",
+                    network.errors_received, network.errors_transmitted
+                ),
+                error_style,
+            ),
+        ]),
+    ];
+
+    f.render_widget(Paragraph::new(text), area);
+}
+
+/// Render RX/TX throughput history
+fn render_history(f: &mut Frame, app: &App, network: &NetworkInfo, area: Rect) {
+    let Some(history) = app.network_history.get(&network.name) else {
+        return;
+    };
+
+    let rx_data: Vec<(f64, f64)> = history
+        .rx
+        .iter()
+        .enumerate()
+        .map(|(i, &v)| (i as f64, v))
+        .collect();
+    let tx_data: Vec<(f64, f64)> = history
+        .tx
+        .iter()
+        .enumerate()
+        .map(|(i, &v)| (i as f64, v))
+        .collect();
+
+    // Scale to the busiest sample, with a 1 KB/s floor so idle links stay flat
+    let y_max = history
+        .rx
+        .iter()
+        .chain(history.tx.iter())
+        .fold(1024.0_f64, |max, &v| max.max(v));
+
+    let datasets = vec![
+        Dataset::default()
+            .name("Down")
+            .marker(symbols::Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(Style::default().fg(Color::Cyan))
+            .data(&rx_data),
+        Dataset::default()
+            .name("Up")
+            .marker(symbols::Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(Style::default().fg(Color::Magenta))
+            .data(&tx_data),
+    ];
+
+    let x_max = app.history_size as f64;
+    let chart = Chart::new(datasets)
+        .x_axis(
+            Axis::default()
+                .style(Style::default().fg(Color::Gray))
+                .bounds([0.0, x_max]),
+        )
+        .y_axis(
+            Axis::default()
+                .style(Style::default().fg(Color::Gray))
+                .bounds([0.0, y_max])
+                .labels(vec![Span::raw("0"), Span::raw(format_rate(y_max))]),
+        );
+
+    f.render_widget(chart, area);
+}
+
+/// Render when no network interfaces are available
+fn render_no_network(f: &mut Frame, area: Rect) {
+    let text = vec![
+        Line::from(""),
+        Line::from(vec![Span::styled(
+            "No network interfaces found",
+            Style::default().fg(Color::Gray).add_modifier(Modifier::ITALIC),
+        )]),
+    ];
+
+    let paragraph = Paragraph::new(text)
+        .block(
+            Block::default()
+                .title(" Network ")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Green)),
+        )
+        .alignment(Alignment::Center);
+
+    f.render_widget(paragraph, area);
+}
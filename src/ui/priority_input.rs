@@ -0,0 +1,118 @@
+use crate::app::{App, PriorityField, PriorityInput};
+use crate::priority::{IoClass, IO_LEVEL_MAX, NICE_RANGE};
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+/// Render the nice / I/O priority input overlay
+pub fn render(f: &mut Frame, app: &App, input: &PriorityInput) {
+    let io = input.field == PriorityField::Io;
+    // Header, value, note and hint lines plus borders; I/O adds the class line
+    let area = centered_rect(52, if io { 10 } else { 9 }, f.size());
+
+    // Clear the area first
+    f.render_widget(Clear, area);
+
+    let target = if app.marked.is_empty() {
+        app.get_selected_process()
+            .map(|p| format!("{} (PID {})", p.name, p.pid))
+            .unwrap_or_default()
+    } else {
+        format!("{} marked processes", app.marked.len())
+    };
+    let label = Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD);
+
+    let mut text = vec![
+        Line::from(vec![Span::styled(
+            format!("Set for {}", target),
+            Style::default().fg(Color::Gray),
+        )]),
+        Line::from(""),
+    ];
+
+    if io {
+        text.push(Line::from(vec![
+            Span::styled("Class: ", label),
+            Span::styled("◀ ", Style::default().fg(Color::Gray)),
+            Span::styled(
+                input.class.label(),
+                Style::default().fg(Color::White).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(" ▶", Style::default().fg(Color::Gray)),
+        ]));
+    }
+
+    let (name, range) = match input.field {
+        PriorityField::Nice => (
+            "Nice:  ",
+            format!("{} (highest) to {} (lowest)", NICE_RANGE.0, NICE_RANGE.1),
+        ),
+        PriorityField::Io if input.class.has_level() => (
+            "Level: ",
+            format!("0 (highest) to {} (lowest)", IO_LEVEL_MAX),
+        ),
+        PriorityField::Io => ("Level: ", "not used by this class".to_string()),
+    };
+    text.push(Line::from(vec![
+        Span::styled(name, label),
+        Span::styled(
+            format!("{}▏", input.text),
+            Style::default().fg(Color::Black).bg(Color::Cyan),
+        ),
+        Span::styled(format!("  {}", range), Style::default().fg(Color::Gray)),
+    ]));
+
+    // Raising priority is the usual reason for a permission error
+    let note = match input.field {
+        PriorityField::Nice => "Lowering the nice value needs root",
+        PriorityField::Io if input.class == IoClass::RealTime => "The realtime class needs root",
+        PriorityField::Io => "Applies to every thread of the process",
+    };
+    text.push(Line::from(""));
+    text.push(Line::from(vec![Span::styled(note, Style::default().fg(Color::DarkGray))]));
+
+    text.push(Line::from(""));
+    let mut hints = vec![
+        Span::styled("Enter", Style::default().fg(Color::Green)),
+        Span::styled(": apply  ", Style::default().fg(Color::Gray)),
+        Span::styled("↑/↓", Style::default().fg(Color::Green)),
+        Span::styled(": adjust  ", Style::default().fg(Color::Gray)),
+    ];
+    if io {
+        hints.push(Span::styled("←/→", Style::default().fg(Color::Green)));
+        hints.push(Span::styled(": class  ", Style::default().fg(Color::Gray)));
+    }
+    hints.push(Span::styled("Esc", Style::default().fg(Color::Green)));
+    hints.push(Span::styled(": cancel", Style::default().fg(Color::Gray)));
+    text.push(Line::from(hints));
+
+    let title = match input.field {
+        PriorityField::Nice => " Renice ",
+        PriorityField::Io => " I/O Priority ",
+    };
+    let paragraph = Paragraph::new(text).block(
+        Block::default()
+            .title(title)
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan)),
+    );
+
+    f.render_widget(paragraph, area);
+}
+
+/// Center a fixed-size rect inside `r`, shrinking it if `r` is smaller
+fn centered_rect(width: u16, height: u16, r: Rect) -> Rect {
+    let width = width.min(r.width);
+    let height = height.min(r.height);
+
+    Rect {
+        x: r.x + (r.width - width) / 2,
+        y: r.y + (r.height - height) / 2,
+        width,
+        height,
+    }
+}
@@ -1,161 +1,300 @@
+use super::{format_bytes, format_rate, key_hint};
 use crate::app::App;
+use crate::column::Column;
+use crate::keymap::Action;
+use crate::process_tree::ProcessRow;
+use chrono::{DateTime, Local};
 use ratatui::{
     layout::Rect,
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, List, ListItem},
+    widgets::{Block, Borders, List, ListItem, Paragraph},
     Frame,
 };
 
 /// Render process list
 pub fn render(f: &mut Frame, app: &App, area: Rect) {
-    let processes = app.get_display_processes();
+    let rows = app.get_process_rows();
 
-    // Calculate how many processes can fit in the view
-    let visible_count = (area.height.saturating_sub(2)) as usize;
+    // Calculate how many processes can fit below the header
+    let visible_count = (area.height.saturating_sub(3)) as usize;
 
     // Adjust scroll offset if needed
-    let scroll_offset = if let Some(selected) = app.selected_process {
-        if selected < app.scroll_offset {
-            selected
-        } else if selected >= app.scroll_offset + visible_count {
-            selected - visible_count + 1
-        } else {
-            app.scroll_offset
-        }
-    } else {
-        0
-    };
+    let scroll_offset = app.process_scroll(visible_count);
+    app.process_rows.set(visible_count);
+
+    let columns = layout_columns(&app.visible_columns(), area.width.saturating_sub(2) as usize);
 
     // Create list items
-    let items: Vec<ListItem> = processes
+    let items: Vec<ListItem> = rows
         .iter()
         .enumerate()
         .skip(scroll_offset)
         .take(visible_count)
-        .map(|(idx, (pid, process))| {
-            let name = process.name().to_string_lossy();
-            let cpu = process.cpu_usage();
-            let mem = process.memory() / 1024 / 1024; // Convert to MB
-
-            // Highlight selected process
-            let style = if Some(idx) == app.selected_process {
-                Style::default()
-                    .fg(Color::Black)
-                    .bg(Color::Cyan)
-                    .add_modifier(Modifier::BOLD)
-            } else {
-                Style::default().fg(Color::White)
-            };
+        .map(|(idx, row)| {
+            let selected = Some(idx) == app.selected_process;
 
-            let cpu_color = if cpu >= 85.0 {
-                Color::Red
-            } else if cpu >= 60.0 {
-                Color::Yellow
-            } else {
-                Color::Green
-            };
+            let mut spans = Vec::new();
+            for (i, &(column, width)) in columns.iter().enumerate() {
+                if i > 0 {
+                    spans.push(Span::raw(" "));
+                }
+                let text = pad(column, &cell_text(column, row), width);
+                // The selection highlight wins over per-cell colors
+                let style = match cell_color(app, column, row) {
+                    Some(color) if !selected => Style::default().fg(color),
+                    _ => Style::default(),
+                };
+                spans.push(Span::styled(text, style));
+            }
 
-            let content = vec![Line::from(vec![
-                Span::styled(format!("{:<8}", pid), style),
-                Span::raw(" "),
-                Span::styled(format!("{:<30}", truncate_string(&name, 30)), style),
-                Span::raw(" "),
-                Span::styled(
-                    format!("{:>6.1}%", cpu),
-                    if Some(idx) == app.selected_process {
-                        style
-                    } else {
-                        Style::default().fg(cpu_color)
-                    },
-                ),
-                Span::raw(" "),
-                Span::styled(format!("{:>8} MB", mem), style),
-            ])];
-
-            ListItem::new(content)
+            let item = ListItem::new(vec![Line::from(spans)]);
+            let marked = app.marked.contains(&row.process.pid);
+            if selected {
+                item.style(
+                    Style::default()
+                        .fg(Color::Black)
+                        .bg(if marked { Color::Magenta } else { Color::Cyan })
+                        .add_modifier(Modifier::BOLD),
+                )
+            } else if marked {
+                item.style(Style::default().fg(Color::Magenta).add_modifier(Modifier::BOLD))
+            } else if app.pinned.contains(&row.process.pid) {
+                item.style(Style::default().fg(Color::Yellow))
+            } else {
+                item
+            }
         })
         .collect();
 
-    // Header with sort indicators
-    let pid_header = match app.sort_order {
-        crate::app::SortOrder::Pid => "PID ▼   ",
-        _ => "PID     ",
-    };
-    let cpu_header = match app.sort_order {
-        crate::app::SortOrder::Cpu => "CPU ▼ ",
-        _ => "CPU   ",
-    };
-    let mem_header = match app.sort_order {
-        crate::app::SortOrder::Memory => "Memory ▼",
-        _ => "Memory  ",
-    };
+    // Header with sort indicator
+    let header_style = Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD);
+    let mut header_spans = Vec::new();
+    for (i, &(column, width)) in columns.iter().enumerate() {
+        if i > 0 {
+            header_spans.push(Span::raw(" "));
+        }
+        header_spans.push(Span::styled(pad(column, &header_text(app, column), width), header_style));
+    }
+    let header = vec![Line::from(header_spans)];
 
-    let header = vec![Line::from(vec![
-        Span::styled(pid_header, Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
-        Span::raw(" "),
-        Span::styled(
-            format!("{:<30}", "Process Name"),
-            Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD),
-        ),
-        Span::raw(" "),
-        Span::styled(cpu_header, Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
-        Span::raw(" "),
-        Span::styled(mem_header, Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
-    ])];
-
-    let title = if app.search_mode {
+    let mut title = if app.search_mode {
         format!(" Search: {} ", app.search_query)
-    } else if app.show_details {
-        " Processes (↑/↓: Navigate, Enter: Details, Esc: Close Details) ".to_string()
     } else {
-        " Processes (↑/↓: Navigate, Enter: View Details, q: Quit) ".to_string()
+        let navigate = key_hint(app, &[Action::Up, Action::Down], "Navigate");
+        let (name, hints) = if app.tree_view {
+            (
+                "Process Tree",
+                [
+                    navigate,
+                    key_hint(app, &[Action::Collapse, Action::Expand], "Collapse/Expand"),
+                    key_hint(app, &[Action::TreeView], "Flat List"),
+                ],
+            )
+        } else if app.show_details {
+            (
+                "Processes",
+                [
+                    navigate,
+                    key_hint(app, &[Action::Details], "Details"),
+                    Some("Esc: Close Details".to_string()),
+                ],
+            )
+        } else {
+            (
+                "Processes",
+                [
+                    navigate,
+                    key_hint(app, &[Action::Details], "View Details"),
+                    key_hint(app, &[Action::Quit], "Quit"),
+                ],
+            )
+        };
+        let hints: Vec<String> = hints.into_iter().flatten().collect();
+        if hints.is_empty() {
+            format!(" {} ", name)
+        } else {
+            format!(" {} ({}) ", name, hints.join(", "))
+        }
     };
 
+    // Filters set from the command line stay on until restart
+    let mut filters = Vec::new();
+    if !app.search_mode && !app.search_query.is_empty() {
+        filters.push(format!("filter: {}", app.search_query));
+    }
+    if let Some(ref user) = app.user_filter {
+        filters.push(format!("user: {}", user));
+    }
+    if !app.pid_filter.is_empty() {
+        let pids: Vec<String> = app.pid_filter.iter().map(|pid| pid.to_string()).collect();
+        filters.push(format!("pid: {}", pids.join(",")));
+    }
+    if !filters.is_empty() {
+        title.push_str(&format!("[{}] ", filters.join(" | ")));
+    }
+
     let border_color = if app.search_mode {
         Color::Yellow
     } else {
         Color::Cyan
     };
 
-    let list = List::new(items)
-        .block(
-            Block::default()
-                .title(title)
-                .borders(Borders::ALL)
-                .border_style(Style::default().fg(border_color)),
-        )
-        .style(Style::default().fg(Color::White));
-
-    // Render header separately
-    if area.height > 2 {
-        let header_area = Rect {
-            x: area.x + 1,
-            y: area.y + 1,
-            width: area.width.saturating_sub(2),
-            height: 1,
-        };
+    let block = Block::default()
+        .title(title)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(border_color));
+    let inner = block.inner(area);
+    f.render_widget(block, area);
 
-        let header_paragraph = ratatui::widgets::Paragraph::new(header);
-        f.render_widget(header_paragraph, header_area);
+    // Header takes the first inner line, the list the rest
+    if inner.height > 0 {
+        let header_area = Rect { height: 1, ..inner };
+        f.render_widget(Paragraph::new(header), header_area);
     }
 
-    // Render process list
     let list_area = Rect {
-        x: area.x,
-        y: area.y,
-        width: area.width,
-        height: area.height,
+        y: inner.y + 1,
+        height: inner.height.saturating_sub(1),
+        ..inner
     };
+    let list = List::new(items).style(Style::default().fg(Color::White));
 
     f.render_widget(list, list_area);
 }
 
+/// Column header, with an arrow on the sort column
+pub fn header_text(app: &App, column: Column) -> String {
+    if column == app.sort_column {
+        let arrow = if app.sort_descending { "▼" } else { "▲" };
+        format!("{} {}", column.header(), arrow)
+    } else {
+        column.header().to_string()
+    }
+}
+
+/// Fit the columns into `width`: columns that don't fit are dropped from the
+/// right, and spare space goes to the name and command columns
+pub fn layout_columns(columns: &[Column], width: usize) -> Vec<(Column, usize)> {
+    let mut fitted: Vec<Column> = columns.to_vec();
+    let needed = |cols: &[Column]| -> usize {
+        cols.iter().map(|c| c.width()).sum::<usize>() + cols.len().saturating_sub(1)
+    };
+    while fitted.len() > 1 && needed(&fitted) > width {
+        fitted.pop();
+    }
+
+    let mut spare = width.saturating_sub(needed(&fitted));
+    let has_command = fitted.contains(&Column::Command);
+
+    fitted
+        .into_iter()
+        .map(|column| {
+            let extra = match column {
+                // Name stops growing at 30 when the command line can take the rest
+                Column::Name if has_command => spare.min(30 - Column::Name.width()),
+                Column::Name | Column::Command => spare,
+                _ => 0,
+            };
+            spare -= extra;
+            (column, column.width() + extra)
+        })
+        .collect()
+}
+
+/// Text for one cell of the table
+pub fn cell_text(column: Column, row: &ProcessRow) -> String {
+    let process = row.process;
+    let optional = |value: Option<i64>| value.map(|v| v.to_string()).unwrap_or_else(|| "-".to_string());
+
+    match column {
+        Column::Pid => process.pid.to_string(),
+        Column::User => process.user.clone().unwrap_or_else(|| "-".to_string()),
+        Column::Name => {
+            // Tree guides and a fold marker go in front of the name
+            let marker = match (row.has_children, row.collapsed) {
+                (true, true) => "▸ ",
+                (true, false) => "▾ ",
+                _ => "",
+            };
+            format!("{}{}{}", row.prefix, marker, process.name)
+        }
+        Column::State => process.status.clone(),
+        Column::Threads => optional(process.threads.map(|t| t as i64)),
+        Column::Nice => optional(process.nice),
+        Column::Priority => optional(process.priority),
+        Column::Cpu => format!("{:.1}%", process.cpu_usage),
+        Column::Memory => format_bytes(process.memory),
+        Column::VirtualMemory => format_bytes(process.virtual_memory),
+        Column::StartTime => format_start_time(process.start_time),
+        Column::RunTime => format_run_time(process.run_time),
+        Column::DiskRead => format_rate(process.disk_read_rate),
+        Column::DiskWrite => format_rate(process.disk_write_rate),
+        Column::GpuUsage => process
+            .gpu_usage
+            .map(|usage| format!("{:.1}%", usage))
+            .unwrap_or_else(|| "-".to_string()),
+        Column::GpuMemory => process.gpu_memory.map(format_bytes).unwrap_or_else(|| "-".to_string()),
+        Column::Command if process.command.is_empty() => format!("[{}]", process.name),
+        Column::Command => process.command.clone(),
+        Column::SubtreeCpu => format!("{:.1}%", row.subtree_cpu),
+        Column::SubtreeMemory => format_bytes(row.subtree_memory),
+    }
+}
+
+/// Color for cells that encode load; None keeps the row color
+fn cell_color(app: &App, column: Column, row: &ProcessRow) -> Option<Color> {
+    match column {
+        Column::Cpu => Some(app.theme.usage_color(row.process.cpu_usage)),
+        Column::GpuUsage => row.process.gpu_usage.map(|usage| app.theme.usage_color(usage)),
+        _ => None,
+    }
+}
+
+/// Truncate and align a cell to exactly `width` characters
+pub fn pad(column: Column, text: &str, width: usize) -> String {
+    let text = truncate_string(text, width);
+    if column.is_numeric() {
+        format!("{:>width$}", text, width = width)
+    } else {
+        format!("{:<width$}", text, width = width)
+    }
+}
+
+/// Show the clock time for processes started today, the date otherwise
+fn format_start_time(epoch_secs: u64) -> String {
+    let Some(started) = DateTime::from_timestamp(epoch_secs as i64, 0) else {
+        return "-".to_string();
+    };
+    let started = started.with_timezone(&Local);
+
+    if started.date_naive() == Local::now().date_naive() {
+        started.format("%H:%M:%S").to_string()
+    } else {
+        started.format("%b %d").to_string()
+    }
+}
+
+/// Format a duration in seconds as H:MM:SS, or days and hours when long
+fn format_run_time(secs: u64) -> String {
+    let days = secs / 86400;
+    let hours = (secs % 86400) / 3600;
+    let minutes = (secs % 3600) / 60;
+    let seconds = secs % 60;
+
+    if days > 0 {
+        format!("{}d {:02}h", days, hours)
+    } else {
+        format!("{}:{:02}:{:02}", hours, minutes, seconds)
+    }
+}
+
 /// Truncate string to max length
 fn truncate_string(s: &str, max_len: usize) -> String {
-    if s.len() <= max_len {
+    if s.chars().count() <= max_len {
         s.to_string()
     } else {
-        format!("{}...", &s[..max_len - 3])
+        let truncated: String = s.chars().take(max_len.saturating_sub(1)).collect();
+        format!("{}…", truncated)
     }
 }
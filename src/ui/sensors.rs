@@ -0,0 +1,126 @@
+use crate::app::App;
+use crate::snapshot::{SensorInfo, SensorKind};
+use crate::theme::Theme;
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Sparkline},
+    Frame,
+};
+
+/// Render hardware sensors panel
+pub fn render(f: &mut Frame, app: &App, area: Rect) {
+    let sensors = &app.snapshot.sensors;
+
+    let block = Block::default()
+        .title(format!(" Sensors ({}) ", sensors.len()))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::LightRed));
+
+    if sensors.is_empty() {
+        let paragraph = Paragraph::new(vec![
+            Line::from(""),
+            Line::from(vec![Span::styled(
+                "No sensors found",
+                Style::default().fg(Color::Gray).add_modifier(Modifier::ITALIC),
+            )]),
+        ])
+        .block(block)
+        .alignment(Alignment::Center);
+        f.render_widget(paragraph, area);
+        return;
+    }
+
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(vec![Constraint::Length(1); inner.height as usize])
+        .split(inner);
+
+    for (sensor, row) in sensors.iter().zip(rows.iter()) {
+        render_sensor(f, app, sensor, *row);
+    }
+}
+
+/// Render one sensor: label, reading and a history sparkline
+fn render_sensor(f: &mut Frame, app: &App, sensor: &SensorInfo, area: Rect) {
+    let chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(65), Constraint::Percentage(35)])
+        .split(area);
+
+    let color = sensor_color(&app.theme, sensor);
+    let label_width = (chunks[0].width as usize).saturating_sub(18).max(4);
+
+    let mut spans = vec![
+        Span::styled(
+            format!("{:<width$}", truncate_label(&sensor.label, label_width), width = label_width),
+            Style::default().fg(Color::White),
+        ),
+        Span::styled(
+            format!("{:>9}", format_reading(sensor.kind, sensor.value)),
+            Style::default().fg(color).add_modifier(Modifier::BOLD),
+        ),
+    ];
+    // Show the critical threshold, or the highest reading seen if there is none
+    if let Some(critical) = sensor.critical {
+        spans.push(Span::styled(
+            format!(" /{}", format_reading(sensor.kind, critical)),
+            Style::default().fg(Color::DarkGray),
+        ));
+    } else if let Some(max) = sensor.max {
+        spans.push(Span::styled(
+            format!(" ↑{}", format_reading(sensor.kind, max)),
+            Style::default().fg(Color::DarkGray),
+        ));
+    }
+    f.render_widget(Paragraph::new(Line::from(spans)), chunks[0]);
+
+    if let Some(history) = app.sensor_history.get(&sensor.key()) {
+        if history.len() >= 2 {
+            // Sparklines take integers; scale so voltages keep their shape
+            let data: Vec<u64> = history.iter().map(|&v| (v * 100.0).max(0.0) as u64).collect();
+            let sparkline = Sparkline::default()
+                .data(&data)
+                .style(Style::default().fg(color));
+            f.render_widget(sparkline, chunks[1]);
+        }
+    }
+}
+
+/// Color a reading: temperatures use the shared thresholds, other sensors
+/// only turn red once they pass their critical value
+fn sensor_color(theme: &Theme, sensor: &SensorInfo) -> Color {
+    match sensor.kind {
+        SensorKind::Temperature => match sensor.critical {
+            Some(critical) if sensor.value >= critical => theme.critical,
+            _ => theme.temp_color(sensor.value),
+        },
+        SensorKind::Fan | SensorKind::Voltage => match sensor.critical {
+            Some(critical) if sensor.value >= critical => theme.critical,
+            _ => Color::Cyan,
+        },
+    }
+}
+
+/// Format a reading with its unit
+fn format_reading(kind: SensorKind, value: f32) -> String {
+    match kind {
+        SensorKind::Temperature => format!("{:.0}°C", value),
+        SensorKind::Fan => format!("{:.0} RPM", value),
+        SensorKind::Voltage => format!("{:.2} V", value),
+    }
+}
+
+/// Truncate a label to `max_len` characters
+fn truncate_label(label: &str, max_len: usize) -> String {
+    if label.chars().count() <= max_len {
+        label.to_string()
+    } else {
+        let truncated: String = label.chars().take(max_len.saturating_sub(1)).collect();
+        format!("{}…", truncated)
+    }
+}
@@ -0,0 +1,96 @@
+use crate::app::{App, SIGNALS};
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+/// Render the signal picker overlay
+pub fn render(f: &mut Frame, app: &App) {
+    // One line per signal plus the header, tree toggle, hint lines and borders
+    let area = centered_rect(56, SIGNALS.len() as u16 + 8, f.size());
+
+    // Clear the area first
+    f.render_widget(Clear, area);
+
+    let process = if app.marked.is_empty() {
+        app.get_selected_process()
+            .map(|p| format!("{} (PID {})", p.name, p.pid))
+            .unwrap_or_default()
+    } else {
+        format!("{} marked processes", app.marked.len())
+    };
+
+    let mut text = vec![
+        Line::from(vec![Span::styled(
+            format!("Send to {}", process),
+            Style::default().fg(Color::Gray),
+        )]),
+        Line::from(""),
+    ];
+
+    for (i, (signal, name, description)) in SIGNALS.iter().enumerate() {
+        let style = if i == app.signal_cursor {
+            Style::default()
+                .fg(Color::Black)
+                .bg(Color::Cyan)
+                .add_modifier(Modifier::BOLD)
+        } else if *signal == sysinfo::Signal::Kill {
+            Style::default().fg(Color::Red)
+        } else {
+            Style::default().fg(Color::White)
+        };
+
+        text.push(Line::from(vec![Span::styled(
+            format!("{:<10}{}", name, description),
+            style,
+        )]));
+    }
+
+    text.push(Line::from(""));
+    text.push(Line::from(vec![
+        Span::styled(
+            if app.signal_tree { "[x] " } else { "[ ] " },
+            Style::default().fg(Color::White),
+        ),
+        Span::styled(
+            "Include all descendants",
+            Style::default().fg(if app.signal_tree { Color::White } else { Color::DarkGray }),
+        ),
+    ]));
+    text.push(Line::from(""));
+    text.push(Line::from(vec![
+        Span::styled("Enter", Style::default().fg(Color::Green)),
+        Span::styled(": send  ", Style::default().fg(Color::Gray)),
+        Span::styled("↑/↓", Style::default().fg(Color::Green)),
+        Span::styled(": select  ", Style::default().fg(Color::Gray)),
+        Span::styled("t", Style::default().fg(Color::Green)),
+        Span::styled(": tree  ", Style::default().fg(Color::Gray)),
+        Span::styled("K/Esc", Style::default().fg(Color::Green)),
+        Span::styled(": close", Style::default().fg(Color::Gray)),
+    ]));
+
+    let paragraph = Paragraph::new(text).block(
+        Block::default()
+            .title(" Send Signal ")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan)),
+    );
+
+    f.render_widget(paragraph, area);
+}
+
+/// Center a fixed-size rect inside `r`, shrinking it if `r` is smaller
+fn centered_rect(width: u16, height: u16, r: Rect) -> Rect {
+    let width = width.min(r.width);
+    let height = height.min(r.height);
+
+    Rect {
+        x: r.x + (r.width - width) / 2,
+        y: r.y + (r.height - height) / 2,
+        width,
+        height,
+    }
+}
@@ -1,4 +1,7 @@
-use crate::app::App;
+use super::{join_hints, key_hint};
+use crate::app::{App, PriorityField};
+use crate::keymap::Action;
+use chrono::{DateTime, Local};
 use ratatui::{
     layout::Rect,
     style::{Color, Modifier, Style},
@@ -9,30 +12,118 @@ use ratatui::{
 
 /// Render status bar at the bottom of the screen
 pub fn render(f: &mut Frame, app: &App, area: Rect) {
-    let process_count = app.system.processes().len();
+    let process_count = app.snapshot.processes.len();
     let refresh_rate_ms = app.refresh_rate_ms;
 
-    // Context-aware key hints
+    // Context-aware key hints; normal mode ones follow the keymap
     let hints = if app.show_help {
-        "? or Esc: Close Help"
+        let mut keys = app.keymap.keys(Action::Help);
+        keys.push("Esc".to_string());
+        format!("{}: Close Help", keys.join(" or "))
+    } else if app.show_column_editor {
+        "↑/↓: Select | Space: Show/Hide | J/K: Move | o/Esc: Close".to_string()
+    } else if let Some(ref input) = app.priority_input {
+        match input.field {
+            PriorityField::Nice => "Type a value | ↑/↓: Adjust | Enter: Apply | Esc: Cancel",
+            PriorityField::Io => "Type a level | ↑/↓: Adjust | ←/→: Class | Enter: Apply | Esc: Cancel",
+        }
+        .to_string()
+    } else if app.show_signal_menu {
+        "↑/↓: Select | t: Whole Tree | Enter: Send | K/Esc: Close".to_string()
     } else if app.search_mode {
-        "Type to search | Enter/Esc: Exit search"
+        "Type to search | Enter/Esc: Exit search".to_string()
     } else if app.show_details {
-        "↑/↓: Navigate | Esc: Close Details | ?: Help"
+        join_hints([
+            key_hint(app, &[Action::Up, Action::Down], "Navigate"),
+            Some("Esc: Close Details".to_string()),
+            key_hint(app, &[Action::Help], "Help"),
+        ])
+    } else if app.replay.is_some() {
+        join_hints([
+            key_hint(app, &[Action::ReplayPause], "Play/Pause"),
+            key_hint(app, &[Action::ReplaySlower, Action::ReplayFaster], "Speed"),
+            key_hint(app, &[Action::ReplayBack, Action::ReplayForward], "Seek"),
+            key_hint(app, &[Action::Up, Action::Down], "Navigate"),
+            key_hint(app, &[Action::Details], "Details"),
+            key_hint(app, &[Action::Help], "Help"),
+        ])
+    } else if !app.marked.is_empty() {
+        join_hints([
+            key_hint(app, &[Action::Mark], "Mark"),
+            key_hint(app, &[Action::MarkMatching], "Mark All Matching"),
+            key_hint(
+                app,
+                &[Action::Kill, Action::Suspend, Action::Resume, Action::SignalMenu, Action::KillTree],
+                "Act on Marked",
+            ),
+            Some("Esc: Clear Marks".to_string()),
+            key_hint(app, &[Action::Help], "Help"),
+        ])
+    } else if app.tree_view {
+        join_hints([
+            key_hint(app, &[Action::Up, Action::Down], "Navigate"),
+            key_hint(app, &[Action::Collapse, Action::Expand], "Collapse/Expand"),
+            key_hint(app, &[Action::TreeView], "Flat List"),
+            key_hint(app, &[Action::Search], "Search"),
+            key_hint(app, &[Action::Help], "Help"),
+        ])
     } else {
-        "↑/↓: Navigate | Enter: Details | /: Search | c/m/p/t: Sort | ?: Help"
+        join_hints([
+            key_hint(app, &[Action::Up, Action::Down], "Navigate"),
+            key_hint(app, &[Action::Details], "Details"),
+            key_hint(app, &[Action::Search], "Search"),
+            key_hint(
+                app,
+                &[
+                    Action::SortCpu,
+                    Action::SortMemory,
+                    Action::SortPid,
+                    Action::SortRuntime,
+                    Action::SortPrevColumn,
+                    Action::SortNextColumn,
+                ],
+                "Sort",
+            ),
+            key_hint(app, &[Action::ColumnEditor], "Columns"),
+            key_hint(app, &[Action::TreeView], "Tree"),
+            key_hint(app, &[Action::Help], "Help"),
+        ])
     };
 
-    let right_content = format!(
-        "Processes: {} | Refresh: {}ms",
-        process_count, refresh_rate_ms
-    );
+    let right_content = if let Some(ref replay) = app.replay {
+        let (frame, frames) = replay.progress();
+        let state = if replay.playing { "▶" } else { "⏸" };
+        let recorded = DateTime::from_timestamp_millis(app.snapshot.timestamp as i64)
+            .map(|time| time.with_timezone(&Local).format("%Y-%m-%d %H:%M:%S").to_string())
+            .unwrap_or_default();
+        format!(
+            "Replay {} {}x | {} | {}/{}",
+            state,
+            replay.speed(),
+            recorded,
+            frame,
+            frames
+        )
+    } else {
+        let marked = if app.marked.is_empty() {
+            String::new()
+        } else {
+            format!(" | Marked: {}", app.marked.len())
+        };
+        format!(
+            "Processes: {}{} | Refresh: {}ms{}",
+            process_count,
+            marked,
+            refresh_rate_ms,
+            if app.recorder.is_some() { " | ● REC" } else { "" }
+        )
+    };
 
     // Calculate spacing to push right content to the right
-    let left_text = hints;
+    let left_text = hints.as_str();
     let available_width = area.width as usize;
-    let right_text_len = right_content.len();
-    let left_text_len = left_text.len();
+    let right_text_len = right_content.chars().count();
+    let left_text_len = left_text.chars().count();
 
     let spacing = if left_text_len + right_text_len + 3 < available_width {
         available_width - left_text_len - right_text_len